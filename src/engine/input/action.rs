@@ -1,5 +1,6 @@
 // Game action definitions and mappings
 
+use super::gamepad::{AxisDirection, GamepadAxis, GamepadButton};
 use winit::event::MouseButton;
 use winit::keyboard::KeyCode;
 
@@ -22,13 +23,50 @@ pub enum Action {
     Menu,
 }
 
+impl Action {
+    /// Every action, in the order they are listed in binding files
+    pub const ALL: [Action; 9] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Jump,
+        Action::Duck,
+        Action::Ability1,
+        Action::Ability2,
+        Action::Ability3,
+        Action::Pause,
+        Action::Menu,
+    ];
+
+    /// Get the name used in binding files
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MoveLeft => "move_left",
+            Self::MoveRight => "move_right",
+            Self::Jump => "jump",
+            Self::Duck => "duck",
+            Self::Ability1 => "ability_1",
+            Self::Ability2 => "ability_2",
+            Self::Ability3 => "ability_3",
+            Self::Pause => "pause",
+            Self::Menu => "menu",
+        }
+    }
+
+    /// Find an action by its binding file name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
 /// Represents an input source (keyboard key, mouse button, or controller button)
+/// Gamepad sources don't name a device; each pad is assigned to one player
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum InputSource {
     Keyboard(KeyCode),
     Mouse(MouseButton),
-    // Future: Add controller support
-    // GamepadButton(gilrs::Button),
+    GamepadButton(GamepadButton),
+    /// A stick pushed past `AXIS_PRESS_THRESHOLD` in one direction
+    GamepadAxis(GamepadAxis, AxisDirection),
 }
 
 impl InputSource {
@@ -41,6 +79,73 @@ impl InputSource {
     pub fn mouse(button: MouseButton) -> Self {
         Self::Mouse(button)
     }
+
+    /// Create a gamepad button input source
+    pub fn gamepad(button: GamepadButton) -> Self {
+        Self::GamepadButton(button)
+    }
+
+    /// Create a stick direction input source
+    pub fn stick(axis: GamepadAxis, direction: AxisDirection) -> Self {
+        Self::GamepadAxis(axis, direction)
+    }
+
+    /// Check if this source is on a gamepad
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Self::GamepadButton(_) | Self::GamepadAxis(..))
+    }
+
+    /// Parse a source written by `Display` ("key:KeyA", "mouse:Left",
+    /// "pad:South", "stick:LeftStickX-")
+    pub fn parse(text: &str) -> Option<Self> {
+        let (kind, name) = text.trim().split_once(':')?;
+        match kind {
+            "key" => parse_variant(name).map(Self::Keyboard),
+            "mouse" => parse_mouse_button(name).map(Self::Mouse),
+            "pad" => parse_variant(name).map(Self::GamepadButton),
+            "stick" => {
+                let direction = match name.chars().last()? {
+                    '-' => AxisDirection::Negative,
+                    '+' => AxisDirection::Positive,
+                    _ => return None,
+                };
+                parse_variant(&name[..name.len() - 1])
+                    .map(|axis| Self::GamepadAxis(axis, direction))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Keyboard(code) => write!(f, "key:{:?}", code),
+            Self::Mouse(MouseButton::Other(button)) => write!(f, "mouse:{}", button),
+            Self::Mouse(button) => write!(f, "mouse:{:?}", button),
+            Self::GamepadButton(button) => write!(f, "pad:{:?}", button),
+            Self::GamepadAxis(axis, AxisDirection::Negative) => write!(f, "stick:{:?}-", axis),
+            Self::GamepadAxis(axis, AxisDirection::Positive) => write!(f, "stick:{:?}+", axis),
+        }
+    }
+}
+
+/// Parse a unit enum variant from its name (e.g. "KeyA" as `KeyCode::KeyA`)
+fn parse_variant<T: serde::de::DeserializeOwned>(name: &str) -> Option<T> {
+    use serde::de::{value::StrDeserializer, IntoDeserializer};
+    let deserializer: StrDeserializer<serde::de::value::Error> = name.into_deserializer();
+    T::deserialize(deserializer).ok()
+}
+
+fn parse_mouse_button(name: &str) -> Option<MouseButton> {
+    match name {
+        "Left" => Some(MouseButton::Left),
+        "Right" => Some(MouseButton::Right),
+        "Middle" => Some(MouseButton::Middle),
+        "Back" => Some(MouseButton::Back),
+        "Forward" => Some(MouseButton::Forward),
+        other => other.parse().ok().map(MouseButton::Other),
+    }
 }
 
 /// Default keyboard/mouse bindings for Player 1
@@ -58,6 +163,90 @@ pub fn default_p1_bindings() -> Vec<(InputSource, Action)> {
     ]
 }
 
+/// Default keyboard bindings for Player 2 (arrow keys, abilities on , . /)
+pub fn default_p2_bindings() -> Vec<(InputSource, Action)> {
+    vec![
+        (InputSource::key(KeyCode::ArrowLeft), Action::MoveLeft),
+        (InputSource::key(KeyCode::ArrowRight), Action::MoveRight),
+        (InputSource::key(KeyCode::ArrowUp), Action::Jump),
+        (InputSource::key(KeyCode::ArrowDown), Action::Duck),
+        (InputSource::key(KeyCode::Comma), Action::Ability1),
+        (InputSource::key(KeyCode::Period), Action::Ability2),
+        (InputSource::key(KeyCode::Slash), Action::Ability3),
+    ]
+}
+
+/// Default keyboard bindings for Player 3 (IJKL, abilities on U O H)
+pub fn default_p3_bindings() -> Vec<(InputSource, Action)> {
+    vec![
+        (InputSource::key(KeyCode::KeyJ), Action::MoveLeft),
+        (InputSource::key(KeyCode::KeyL), Action::MoveRight),
+        (InputSource::key(KeyCode::KeyI), Action::Jump),
+        (InputSource::key(KeyCode::KeyK), Action::Duck),
+        (InputSource::key(KeyCode::KeyU), Action::Ability1),
+        (InputSource::key(KeyCode::KeyO), Action::Ability2),
+        (InputSource::key(KeyCode::KeyH), Action::Ability3),
+    ]
+}
+
+/// Default keyboard bindings for Player 4 (numpad 8456, abilities on 7 9 0)
+pub fn default_p4_bindings() -> Vec<(InputSource, Action)> {
+    vec![
+        (InputSource::key(KeyCode::Numpad4), Action::MoveLeft),
+        (InputSource::key(KeyCode::Numpad6), Action::MoveRight),
+        (InputSource::key(KeyCode::Numpad8), Action::Jump),
+        (InputSource::key(KeyCode::Numpad5), Action::Duck),
+        (InputSource::key(KeyCode::Numpad7), Action::Ability1),
+        (InputSource::key(KeyCode::Numpad9), Action::Ability2),
+        (InputSource::key(KeyCode::Numpad0), Action::Ability3),
+    ]
+}
+
+/// Default bindings for a player (players past the fourth have none)
+pub fn default_bindings(player_id: usize) -> Vec<(InputSource, Action)> {
+    match player_id {
+        0 => default_p1_bindings(),
+        1 => default_p2_bindings(),
+        2 => default_p3_bindings(),
+        3 => default_p4_bindings(),
+        _ => Vec::new(),
+    }
+}
+
+/// Default gamepad bindings, the same for every player
+/// (left stick or d-pad to move, south to jump, west/north/east for abilities)
+pub fn default_gamepad_bindings() -> Vec<(InputSource, Action)> {
+    use AxisDirection::{Negative, Positive};
+    vec![
+        (
+            InputSource::stick(GamepadAxis::LeftStickX, Negative),
+            Action::MoveLeft,
+        ),
+        (
+            InputSource::stick(GamepadAxis::LeftStickX, Positive),
+            Action::MoveRight,
+        ),
+        (
+            InputSource::stick(GamepadAxis::LeftStickY, Negative),
+            Action::Duck,
+        ),
+        (
+            InputSource::gamepad(GamepadButton::DPadLeft),
+            Action::MoveLeft,
+        ),
+        (
+            InputSource::gamepad(GamepadButton::DPadRight),
+            Action::MoveRight,
+        ),
+        (InputSource::gamepad(GamepadButton::DPadDown), Action::Duck),
+        (InputSource::gamepad(GamepadButton::South), Action::Jump),
+        (InputSource::gamepad(GamepadButton::West), Action::Ability1),
+        (InputSource::gamepad(GamepadButton::North), Action::Ability2),
+        (InputSource::gamepad(GamepadButton::East), Action::Ability3),
+        (InputSource::gamepad(GamepadButton::Start), Action::Menu),
+    ]
+}
+
 /// Global bindings (not player-specific)
 pub fn global_bindings() -> Vec<(InputSource, Action)> {
     vec![
@@ -131,6 +320,69 @@ mod tests {
         assert!(!bindings.is_empty());
     }
 
+    #[test]
+    fn test_split_keyboard_bindings_do_not_overlap() {
+        let mut seen_sources: std::collections::HashSet<InputSource> = global_bindings()
+            .into_iter()
+            .map(|(source, _)| source)
+            .collect();
+        for player_id in 0..4 {
+            let bindings = default_bindings(player_id);
+            assert_eq!(
+                bindings.len(),
+                7,
+                "Player {} is missing bindings",
+                player_id
+            );
+            for (source, _) in bindings {
+                assert!(
+                    seen_sources.insert(source),
+                    "{:?} is bound for more than one player",
+                    source
+                );
+            }
+        }
+        assert!(default_bindings(4).is_empty());
+    }
+
+    #[test]
+    fn test_gamepad_bindings_cover_every_player_action() {
+        let bindings = default_gamepad_bindings();
+        assert!(bindings.iter().all(|(source, _)| source.is_gamepad()));
+        for action in [
+            Action::MoveLeft,
+            Action::MoveRight,
+            Action::Jump,
+            Action::Duck,
+            Action::Ability1,
+            Action::Ability2,
+            Action::Ability3,
+        ] {
+            assert!(bindings.iter().any(|(_, a)| *a == action), "{:?}", action);
+        }
+    }
+
+    #[test]
+    fn test_source_names_round_trip() {
+        let sources = [
+            InputSource::key(KeyCode::Numpad8),
+            InputSource::mouse(MouseButton::Middle),
+            InputSource::mouse(MouseButton::Other(7)),
+            InputSource::gamepad(GamepadButton::DPadLeft),
+            InputSource::stick(GamepadAxis::LeftStickY, AxisDirection::Negative),
+        ];
+        for source in sources {
+            assert_eq!(InputSource::parse(&source.to_string()), Some(source));
+        }
+        assert_eq!(InputSource::parse("key:NotAKey"), None);
+        assert_eq!(InputSource::parse("stick:LeftStickX"), None);
+        assert_eq!(InputSource::parse("KeyA"), None);
+
+        for action in Action::ALL {
+            assert_eq!(Action::from_name(action.name()), Some(action));
+        }
+    }
+
     #[test]
     fn test_no_duplicate_inputs_in_p1() {
         let bindings = default_p1_bindings();
@@ -0,0 +1,112 @@
+// Analog movement axes, deadzones and response curves
+//
+// Each player has a horizontal and a vertical axis in -1.0..=1.0. Sticks feed
+// raw values that are shaped by `AxisSettings`; keys, buttons and the d-pad
+// count as full deflection through the movement actions, so the game reads
+// both through `PlayerInput::axis`.
+
+/// A movement axis of a player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputAxis {
+    /// Left is negative
+    Horizontal,
+    /// Down is negative
+    Vertical,
+}
+
+/// How stick deflection maps to axis output once outside the deadzone
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ResponseCurve {
+    /// Output grows evenly with deflection
+    #[default]
+    Linear,
+    /// Finer control near the center
+    Quadratic,
+    /// Even finer control near the center
+    Cubic,
+}
+
+impl ResponseCurve {
+    /// Shape a deflection in 0.0..=1.0
+    pub fn apply(&self, amount: f32) -> f32 {
+        match self {
+            Self::Linear => amount,
+            Self::Quadratic => amount * amount,
+            Self::Cubic => amount * amount * amount,
+        }
+    }
+}
+
+/// How raw stick values become axis values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisSettings {
+    /// Deflection ignored around the center (stick drift)
+    pub deadzone: f32,
+    /// Deflection that already counts as fully pushed
+    pub saturation: f32,
+    pub curve: ResponseCurve,
+}
+
+impl Default for AxisSettings {
+    fn default() -> Self {
+        Self {
+            deadzone: 0.15,
+            saturation: 0.95,
+            curve: ResponseCurve::Linear,
+        }
+    }
+}
+
+impl AxisSettings {
+    /// Set the inner and outer deadzones
+    pub fn with_deadzone(mut self, deadzone: f32, saturation: f32) -> Self {
+        self.deadzone = deadzone;
+        self.saturation = saturation;
+        self
+    }
+
+    /// Set the response curve
+    pub fn with_curve(mut self, curve: ResponseCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Turn a raw stick value into an axis value in -1.0..=1.0
+    /// Values inside the deadzone are 0; the rest is rescaled to start at 0
+    pub fn apply(&self, raw: f32) -> f32 {
+        let magnitude = raw.abs();
+        if magnitude <= self.deadzone {
+            return 0.0;
+        }
+        let range = (self.saturation - self.deadzone).max(f32::EPSILON);
+        let amount = ((magnitude - self.deadzone) / range).min(1.0);
+        self.curve.apply(amount).copysign(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadzone_and_saturation() {
+        let settings = AxisSettings::default().with_deadzone(0.2, 0.8);
+        assert_eq!(settings.apply(0.15), 0.0);
+        assert_eq!(settings.apply(-0.2), 0.0);
+        assert!((settings.apply(0.5) - 0.5).abs() < 1e-6);
+        assert!((settings.apply(-0.5) + 0.5).abs() < 1e-6);
+        assert_eq!(settings.apply(0.9), 1.0);
+        assert_eq!(settings.apply(-1.0), -1.0);
+    }
+
+    #[test]
+    fn test_response_curves() {
+        let linear = AxisSettings::default().with_deadzone(0.0, 1.0);
+        let quadratic = linear.with_curve(ResponseCurve::Quadratic);
+        let cubic = linear.with_curve(ResponseCurve::Cubic);
+        assert_eq!(linear.apply(0.5), 0.5);
+        assert_eq!(quadratic.apply(-0.5), -0.25);
+        assert_eq!(cubic.apply(0.5), 0.125);
+        assert_eq!(cubic.apply(1.0), 1.0);
+    }
+}
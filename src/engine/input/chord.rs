@@ -0,0 +1,172 @@
+// Modifier chords and tracking which bindings hold an action down
+//
+// A `Chord` is a trigger source plus modifiers that must already be held when
+// the trigger goes down, like Shift+Click. A plain binding is a chord without
+// modifiers. When a trigger is pressed the bound chord with the most held
+// modifiers wins, so Shift+Click does not also fire Click's action.
+//
+// Several bindings can hold the same action (A and ArrowLeft both moving
+// left). `ActiveChords` remembers every binding that is down per player, so an
+// action is only pressed when its first binding goes down and only released
+// when its last one comes up.
+
+use super::action::{Action, InputSource};
+
+/// A trigger source with the modifiers that must be held for it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Chord {
+    /// Kept sorted by name so equal chords compare equal
+    modifiers: Vec<InputSource>,
+    trigger: InputSource,
+}
+
+impl Chord {
+    /// Create a chord without modifiers (a plain binding)
+    pub fn new(trigger: InputSource) -> Self {
+        Self {
+            modifiers: Vec::new(),
+            trigger,
+        }
+    }
+
+    /// Add a modifier that must be held before the trigger
+    pub fn with_modifier(mut self, modifier: InputSource) -> Self {
+        if modifier != self.trigger && !self.modifiers.contains(&modifier) {
+            self.modifiers.push(modifier);
+            self.modifiers.sort_by_key(|source| source.to_string());
+        }
+        self
+    }
+
+    /// Get the source that fires the chord
+    pub fn trigger(&self) -> InputSource {
+        self.trigger
+    }
+
+    /// Get the sources that must be held for the chord
+    pub fn modifiers(&self) -> &[InputSource] {
+        &self.modifiers
+    }
+
+    /// Check if the chord is a plain binding
+    pub fn is_plain(&self) -> bool {
+        self.modifiers.is_empty()
+    }
+
+    /// Check if a source is the trigger or one of the modifiers
+    pub fn uses(&self, source: InputSource) -> bool {
+        self.trigger == source || self.modifiers.contains(&source)
+    }
+
+    /// Parse a chord written by `Display` ("key:ShiftLeft + mouse:Left"); a
+    /// single source parses as a plain binding
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut sources = text
+            .split(" + ")
+            .map(InputSource::parse)
+            .collect::<Option<Vec<_>>>()?;
+        let trigger = sources.pop()?;
+        Some(
+            sources
+                .into_iter()
+                .fold(Self::new(trigger), Self::with_modifier),
+        )
+    }
+}
+
+impl From<InputSource> for Chord {
+    fn from(trigger: InputSource) -> Self {
+        Self::new(trigger)
+    }
+}
+
+impl std::fmt::Display for Chord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for modifier in &self.modifiers {
+            write!(f, "{} + ", modifier)?;
+        }
+        write!(f, "{}", self.trigger)
+    }
+}
+
+/// Bindings currently holding a player's actions down
+#[derive(Debug, Default)]
+pub(crate) struct ActiveChords {
+    active: Vec<(Chord, Action)>,
+}
+
+impl ActiveChords {
+    /// Mark a binding as down
+    /// Returns true if the action was not held by any other binding
+    pub(crate) fn activate(&mut self, chord: Chord, action: Action) -> bool {
+        if self.active.iter().any(|(active, _)| *active == chord) {
+            return false;
+        }
+        let newly_pressed = !self.is_held(action);
+        self.active.push((chord, action));
+        newly_pressed
+    }
+
+    /// Drop every binding a released source was part of
+    /// Returns the actions no binding holds anymore
+    pub(crate) fn deactivate(&mut self, source: InputSource) -> Vec<Action> {
+        let mut released = Vec::new();
+        let (ended, kept) = std::mem::take(&mut self.active)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(chord, _)| chord.uses(source));
+        self.active = kept;
+        for (_, action) in ended {
+            if !self.is_held(action) && !released.contains(&action) {
+                released.push(action);
+            }
+        }
+        released
+    }
+
+    /// Forget every binding
+    pub(crate) fn clear(&mut self) {
+        self.active.clear();
+    }
+
+    fn is_held(&self, action: Action) -> bool {
+        self.active.iter().any(|(_, active)| *active == action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::input::gamepad::{AxisDirection, GamepadAxis};
+    use winit::event::MouseButton;
+    use winit::keyboard::KeyCode;
+
+    #[test]
+    fn test_chord_text_round_trip() {
+        let shift = InputSource::key(KeyCode::ShiftLeft);
+        let stick = InputSource::stick(GamepadAxis::LeftStickX, AxisDirection::Positive);
+        let chord = Chord::new(InputSource::mouse(MouseButton::Left))
+            .with_modifier(stick)
+            .with_modifier(shift);
+
+        let text = chord.to_string();
+        assert_eq!(text, "key:ShiftLeft + stick:LeftStickX+ + mouse:Left");
+        assert_eq!(Chord::parse(&text), Some(chord));
+        assert_eq!(
+            Chord::parse("key:KeyA"),
+            Some(InputSource::key(KeyCode::KeyA).into())
+        );
+        assert_eq!(Chord::parse("key:ShiftLeft + key:Nope"), None);
+    }
+
+    #[test]
+    fn test_action_held_until_last_binding_released() {
+        let a = InputSource::key(KeyCode::KeyA);
+        let left = InputSource::key(KeyCode::ArrowLeft);
+        let mut active = ActiveChords::default();
+
+        assert!(active.activate(a.into(), Action::MoveLeft));
+        assert!(!active.activate(left.into(), Action::MoveLeft));
+        assert!(active.deactivate(a).is_empty());
+        assert_eq!(active.deactivate(left), vec![Action::MoveLeft]);
+    }
+}
@@ -1,7 +1,10 @@
 // Input configuration and remapping system
 
 use super::action::{Action, InputSource};
+use super::chord::Chord;
+use super::file::{BindingsFile, BindingsFileError};
 use std::collections::HashMap;
+use std::path::Path;
 
 /// Input configuration for a single player
 /// Maps input sources (keys/buttons) to game actions
@@ -15,6 +18,9 @@ pub struct InputConfig {
 
     /// Reverse mapping for quick lookups (action -> all sources)
     action_to_sources: HashMap<Action, Vec<InputSource>>,
+
+    /// Bindings that need modifiers held (plain ones live in `bindings`)
+    chords: HashMap<Chord, Action>,
 }
 
 impl InputConfig {
@@ -24,6 +30,7 @@ impl InputConfig {
             player_id,
             bindings: HashMap::new(),
             action_to_sources: HashMap::new(),
+            chords: HashMap::new(),
         }
     }
 
@@ -69,13 +76,32 @@ impl InputConfig {
         }
     }
 
-    /// Unbind all sources for an action
+    /// Bind a chord to an action (a chord without modifiers binds its trigger)
+    pub fn bind_chord(&mut self, chord: Chord, action: Action) {
+        if chord.is_plain() {
+            self.bind(chord.trigger(), action);
+        } else {
+            self.chords.insert(chord, action);
+        }
+    }
+
+    /// Unbind a chord
+    pub fn unbind_chord(&mut self, chord: &Chord) {
+        if chord.is_plain() {
+            self.unbind_source(chord.trigger());
+        } else {
+            self.chords.remove(chord);
+        }
+    }
+
+    /// Unbind all sources and chords for an action
     pub fn unbind_action(&mut self, action: Action) {
         if let Some(sources) = self.action_to_sources.remove(&action) {
             for source in sources {
                 self.bindings.remove(&source);
             }
         }
+        self.chords.retain(|_, bound| *bound != action);
     }
 
     /// Get the action bound to an input source
@@ -91,6 +117,48 @@ impl InputConfig {
             .unwrap_or_default()
     }
 
+    /// Get the action bound to a chord
+    pub fn get_chord_action(&self, chord: &Chord) -> Option<Action> {
+        if chord.is_plain() {
+            self.get_action(chord.trigger())
+        } else {
+            self.chords.get(chord).copied()
+        }
+    }
+
+    /// Get the chords with modifiers bound to an action
+    pub fn get_chords(&self, action: Action) -> Vec<Chord> {
+        let mut chords: Vec<Chord> = self
+            .chords
+            .iter()
+            .filter(|(_, bound)| **bound == action)
+            .map(|(chord, _)| chord.clone())
+            .collect();
+        chords.sort_by_key(|chord| chord.to_string());
+        chords
+    }
+
+    /// Find what a trigger press fires, given which sources are held
+    /// The bound chord with the most modifiers wins; a plain binding comes last
+    pub fn resolve(
+        &self,
+        trigger: InputSource,
+        is_held: impl Fn(InputSource) -> bool,
+    ) -> Option<(Chord, Action)> {
+        let chord = self
+            .chords
+            .iter()
+            .filter(|(chord, _)| chord.trigger() == trigger)
+            .filter(|(chord, _)| chord.modifiers().iter().all(|&source| is_held(source)))
+            .max_by_key(|(chord, _)| chord.modifiers().len());
+        match chord {
+            Some((chord, &action)) => Some((chord.clone(), action)),
+            None => self
+                .get_action(trigger)
+                .map(|action| (Chord::new(trigger), action)),
+        }
+    }
+
     /// Check if an input source is bound to any action
     pub fn is_bound(&self, source: InputSource) -> bool {
         self.bindings.contains_key(&source)
@@ -99,6 +167,7 @@ impl InputConfig {
     /// Check if an action has any bindings
     pub fn has_binding(&self, action: Action) -> bool {
         self.action_to_sources.contains_key(&action)
+            || self.chords.values().any(|bound| *bound == action)
     }
 
     /// Get all bindings as a list
@@ -110,15 +179,15 @@ impl InputConfig {
     pub fn clear(&mut self) {
         self.bindings.clear();
         self.action_to_sources.clear();
+        self.chords.clear();
     }
 
-    /// Reset to default bindings for this player
+    /// Reset to default bindings for this player (keyboard and gamepad)
     pub fn reset_to_defaults(&mut self) {
         self.clear();
-        let defaults = match self.player_id {
-            0 => super::action::default_p1_bindings(),
-            _ => Vec::new(), // Players 2+ (remote) have no local bindings by default
-        };
+        let defaults = super::action::default_bindings(self.player_id)
+            .into_iter()
+            .chain(super::action::default_gamepad_bindings());
         for (source, action) in defaults {
             self.bind(source, action);
         }
@@ -164,6 +233,33 @@ impl InputConfigManager {
         }
     }
 
+    /// Load bindings from a file, starting from the defaults
+    /// A missing file quietly gives the defaults; a corrupt or unreadable one
+    /// gives the defaults with a warning, and bad entries are skipped with one
+    pub fn load_or_default(path: &Path, max_players: usize) -> Self {
+        let mut manager = Self::new(max_players);
+        match BindingsFile::load(path) {
+            Ok(file) => {
+                for warning in file.apply(&mut manager) {
+                    log::warn!("{}: {}", path.display(), warning);
+                }
+            }
+            Err(BindingsFileError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log::warn!("{}; using default bindings", e),
+        }
+        manager
+    }
+
+    /// Save every player's bindings to a file
+    pub fn save(&self, path: &Path) -> Result<(), BindingsFileError> {
+        BindingsFile::from_configs(self).save(path)
+    }
+
+    /// Get the number of players with a configuration
+    pub fn num_players(&self) -> usize {
+        self.configs.len()
+    }
+
     /// Get a player's configuration
     pub fn get_config(&self, player_id: usize) -> Option<&InputConfig> {
         self.configs.get(player_id)
@@ -198,6 +294,28 @@ impl InputConfigManager {
         self.global_config.get_action(source)
     }
 
+    /// Find what a trigger press fires for a player, given which sources are held
+    /// The chord with the most modifiers wins; player bindings win ties with global ones
+    pub fn resolve(
+        &self,
+        player_id: usize,
+        trigger: InputSource,
+        is_held: impl Fn(InputSource) -> bool,
+    ) -> Option<(Chord, Action)> {
+        let player = self
+            .get_config(player_id)
+            .and_then(|config| config.resolve(trigger, &is_held));
+        let global = self.global_config.resolve(trigger, &is_held);
+        match (player, global) {
+            (Some(player), Some(global))
+                if global.0.modifiers().len() > player.0.modifiers().len() =>
+            {
+                Some(global)
+            }
+            (player, global) => player.or(global),
+        }
+    }
+
     /// Reset all configurations to defaults
     pub fn reset_all_to_defaults(&mut self) {
         for config in &mut self.configs {
@@ -338,6 +456,20 @@ mod tests {
         assert_eq!(manager.get_action(0, source), Some(Action::MoveLeft));
     }
 
+    #[test]
+    fn test_config_manager_split_keyboard() {
+        let manager = InputConfigManager::new(4);
+        let arrow_left = InputSource::key(KeyCode::ArrowLeft);
+
+        // Each player only reacts to their own side of the keyboard
+        assert_eq!(manager.get_action(1, arrow_left), Some(Action::MoveLeft));
+        assert_eq!(manager.get_action(0, arrow_left), None);
+        assert_eq!(
+            manager.get_action(3, InputSource::key(KeyCode::Numpad8)),
+            Some(Action::Jump)
+        );
+    }
+
     #[test]
     fn test_config_manager_global_bindings() {
         let manager = InputConfigManager::new(4);
@@ -363,6 +495,67 @@ mod tests {
         assert!(manager.get_config(0).unwrap().has_binding(Action::MoveLeft));
     }
 
+    #[test]
+    fn test_load_falls_back_to_defaults() {
+        let dir = std::env::temp_dir().join(format!("rusted-battle-input-{}", std::process::id()));
+        let path = dir.join("bindings.toml");
+
+        // Missing and corrupt files both give the defaults
+        let missing = InputConfigManager::load_or_default(&path, 4);
+        assert_eq!(
+            missing.get_action(0, InputSource::key(KeyCode::KeyA)),
+            Some(Action::MoveLeft)
+        );
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, "version = [oops").unwrap();
+        let corrupt = InputConfigManager::load_or_default(&path, 4);
+        assert!(corrupt.get_config(3).unwrap().has_binding(Action::Jump));
+
+        // A saved file is read back
+        let mut changed = InputConfigManager::new(4);
+        changed
+            .get_config_mut(0)
+            .unwrap()
+            .bind(InputSource::key(KeyCode::KeyQ), Action::Ability1);
+        changed.save(&path).unwrap();
+        let loaded = InputConfigManager::load_or_default(&path, 4);
+        assert_eq!(
+            loaded.get_action(0, InputSource::key(KeyCode::KeyQ)),
+            Some(Action::Ability1)
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_prefers_longest_chord() {
+        let shift = InputSource::key(KeyCode::ShiftLeft);
+        let ctrl = InputSource::key(KeyCode::ControlLeft);
+        let click = InputSource::mouse(winit::event::MouseButton::Left);
+        let mut manager = InputConfigManager::new(4);
+        let config = manager.get_config_mut(0).unwrap();
+        config.bind_chord(Chord::new(click).with_modifier(shift), Action::Ability2);
+        config.bind_chord(
+            Chord::new(click).with_modifier(shift).with_modifier(ctrl),
+            Action::Ability3,
+        );
+
+        let resolve = |manager: &InputConfigManager, held: &[InputSource]| {
+            manager
+                .resolve(0, click, |source| held.contains(&source))
+                .map(|(_, action)| action)
+        };
+        assert_eq!(resolve(&manager, &[]), Some(Action::Ability1));
+        assert_eq!(resolve(&manager, &[shift]), Some(Action::Ability2));
+        assert_eq!(resolve(&manager, &[ctrl, shift]), Some(Action::Ability3));
+        assert_eq!(resolve(&manager, &[ctrl]), Some(Action::Ability1));
+
+        // Unbinding the action drops its chords too
+        let config = manager.get_config_mut(0).unwrap();
+        config.unbind_action(Action::Ability2);
+        assert!(!config.has_binding(Action::Ability2));
+        assert_eq!(resolve(&manager, &[shift]), Some(Action::Ability1));
+    }
+
     #[test]
     fn test_from_bindings() {
         let bindings = vec![
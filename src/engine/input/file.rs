@@ -0,0 +1,259 @@
+// Key binding file format (TOML)
+//
+// Bindings are stored in the user's config directory (see `bindings_path`)
+// as a human-editable TOML file:
+//
+// ```toml
+// version = 1
+//
+// [[players]]
+// player = 1
+//
+// [players.bindings]
+// move_left = ["key:KeyA", "pad:DPadLeft", "stick:LeftStickX-"]
+// jump = ["key:KeyW", "pad:South"]
+// ability_1 = ["mouse:Left", "pad:West"]
+// ability_2 = ["mouse:Right", "key:ShiftLeft + mouse:Left"]
+//
+// [global]
+// menu = ["key:Escape"]
+// ```
+//
+// Actions and sources are written by name (see `Action::name` and
+// `InputSource::parse`); modifier chords join sources with " + ", trigger
+// last (see `Chord::parse`). Unknown actions and sources are skipped with a
+// warning and unknown fields are ignored, so one bad entry doesn't throw away
+// the rest of the file.
+// A file with a different `version` is rejected and the defaults are used
+// instead. Players missing from the file keep their default bindings.
+
+use super::action::Action;
+use super::chord::Chord;
+use super::config::{InputConfig, InputConfigManager};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Current version of the bindings file format
+pub const BINDINGS_FILE_VERSION: u32 = 1;
+
+/// Bindings file errors
+#[derive(Debug, thiserror::Error)]
+pub enum BindingsFileError {
+    #[error("Failed to read or write bindings file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse bindings file: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("Failed to serialize bindings file: {0}")]
+    Serialize(#[from] toml::ser::Error),
+
+    #[error("Unsupported bindings file version {0} (expected {BINDINGS_FILE_VERSION})")]
+    UnsupportedVersion(u32),
+}
+
+/// Action name -> source names
+pub type BindingTable = BTreeMap<String, Vec<String>>;
+
+/// One player's bindings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerBindings {
+    /// Player number (1-based, as shown in game)
+    pub player: usize,
+    #[serde(default)]
+    pub bindings: BindingTable,
+}
+
+/// Contents of a bindings file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BindingsFile {
+    pub version: u32,
+    #[serde(default)]
+    pub players: Vec<PlayerBindings>,
+    /// Bindings for every player (None = keep the defaults)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global: Option<BindingTable>,
+}
+
+/// Get where the bindings file lives (None if there is no config directory)
+pub fn bindings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("rusted-battle").join("bindings.toml"))
+}
+
+impl BindingsFile {
+    /// Capture the bindings of every player
+    pub fn from_configs(configs: &InputConfigManager) -> Self {
+        let players = (0..configs.num_players())
+            .filter_map(|player_id| configs.get_config(player_id))
+            .map(|config| PlayerBindings {
+                player: config.player_id() + 1,
+                bindings: Self::table(config),
+            })
+            .collect();
+
+        Self {
+            version: BINDINGS_FILE_VERSION,
+            players,
+            global: Some(Self::table(configs.global_config())),
+        }
+    }
+
+    /// Replace the bindings of every player listed in the file
+    /// Returns a warning for each entry that was skipped
+    pub fn apply(&self, configs: &mut InputConfigManager) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for entry in &self.players {
+            let config = entry
+                .player
+                .checked_sub(1)
+                .and_then(|player_id| configs.get_config_mut(player_id));
+            match config {
+                Some(config) => Self::fill(config, &entry.bindings, &mut warnings),
+                None => warnings.push(format!("Skipping unknown player {}", entry.player)),
+            }
+        }
+        if let Some(global) = &self.global {
+            Self::fill(configs.global_config_mut(), global, &mut warnings);
+        }
+        warnings
+    }
+
+    /// Parse bindings from TOML text
+    pub fn from_toml_str(text: &str) -> Result<Self, BindingsFileError> {
+        let file: BindingsFile = toml::from_str(text)?;
+        if file.version != BINDINGS_FILE_VERSION {
+            return Err(BindingsFileError::UnsupportedVersion(file.version));
+        }
+        Ok(file)
+    }
+
+    /// Serialize the bindings to TOML text
+    pub fn to_toml_string(&self) -> Result<String, BindingsFileError> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    /// Load bindings from a file
+    pub fn load(path: &Path) -> Result<Self, BindingsFileError> {
+        let text = std::fs::read_to_string(path)?;
+        Self::from_toml_str(&text)
+    }
+
+    /// Save the bindings to a file, creating parent directories as needed
+    pub fn save(&self, path: &Path) -> Result<(), BindingsFileError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.to_toml_string()?)?;
+        Ok(())
+    }
+
+    fn table(config: &InputConfig) -> BindingTable {
+        Action::ALL
+            .into_iter()
+            .filter(|&action| config.has_binding(action))
+            .map(|action| {
+                let sources = config.get_sources(action).into_iter().map(Chord::from);
+                let chords = sources.chain(config.get_chords(action));
+                let names = chords.map(|chord| chord.to_string()).collect();
+                (action.name().to_string(), names)
+            })
+            .collect()
+    }
+
+    fn fill(config: &mut InputConfig, table: &BindingTable, warnings: &mut Vec<String>) {
+        config.clear();
+        for (name, sources) in table {
+            let Some(action) = Action::from_name(name) else {
+                warnings.push(format!("Skipping unknown action \"{}\"", name));
+                continue;
+            };
+            for text in sources {
+                match Chord::parse(text) {
+                    Some(chord) => config.bind_chord(chord, action),
+                    None => warnings.push(format!("Skipping unknown input \"{}\"", text)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::input::action::InputSource;
+    use winit::keyboard::KeyCode;
+
+    #[test]
+    fn test_round_trip() {
+        let mut configs = InputConfigManager::new(4);
+        let config = configs.get_config_mut(2).unwrap();
+        config.bind(InputSource::key(KeyCode::KeyZ), Action::Jump);
+        let chord = Chord::new(InputSource::key(KeyCode::KeyZ))
+            .with_modifier(InputSource::key(KeyCode::ShiftLeft));
+        config.bind_chord(chord.clone(), Action::Duck);
+
+        let text = BindingsFile::from_configs(&configs)
+            .to_toml_string()
+            .unwrap();
+        let mut loaded = InputConfigManager::new(4);
+        let warnings = BindingsFile::from_toml_str(&text)
+            .unwrap()
+            .apply(&mut loaded);
+
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        for player_id in 0..4 {
+            let mut expected = configs.get_config(player_id).unwrap().get_all_bindings();
+            let mut actual = loaded.get_config(player_id).unwrap().get_all_bindings();
+            expected.sort_by_key(|(source, _)| source.to_string());
+            actual.sort_by_key(|(source, _)| source.to_string());
+            assert_eq!(actual, expected);
+        }
+        assert_eq!(
+            loaded.get_config(2).unwrap().get_chord_action(&chord),
+            Some(Action::Duck)
+        );
+    }
+
+    #[test]
+    fn test_unknown_entries_are_skipped() {
+        let text = r#"
+            version = 1
+            theme = "dark"
+
+            [[players]]
+            player = 2
+            bindings = { jump = ["key:Space", "key:Nope"], teleport = ["key:KeyT"] }
+
+            [[players]]
+            player = 9
+        "#;
+        let mut configs = InputConfigManager::new(4);
+        let warnings = BindingsFile::from_toml_str(text)
+            .unwrap()
+            .apply(&mut configs);
+
+        assert_eq!(warnings.len(), 3, "{:?}", warnings);
+        let player = configs.get_config(1).unwrap();
+        assert_eq!(
+            player.get_action(InputSource::key(KeyCode::Space)),
+            Some(Action::Jump)
+        );
+        assert!(!player.has_binding(Action::MoveLeft));
+        // Players left out of the file keep their defaults
+        assert!(configs.get_config(0).unwrap().has_binding(Action::MoveLeft));
+        assert_eq!(
+            configs.get_action(1, InputSource::key(KeyCode::Escape)),
+            Some(Action::Menu)
+        );
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let result = BindingsFile::from_toml_str("version = 2\n");
+        assert!(matches!(
+            result,
+            Err(BindingsFileError::UnsupportedVersion(2))
+        ));
+    }
+}
@@ -0,0 +1,387 @@
+// Gamepad devices, backends and player assignment
+//
+// The platform layer (gilrs, SDL, a web API...) sits behind `GamepadBackend`,
+// which only reports connections, button changes and axis motion. Everything
+// else lives here and in the `InputManager`:
+// - Buttons and stick directions are `InputSource`s like keys, so they are
+//   bound per player in the same `InputConfig`
+// - Each connected pad is assigned to one player; a pad that reconnects goes
+//   back to its previous player if that player is still free
+// - `FakeGamepads` is a scriptable backend for tests and headless runs
+// - `platform_backend` picks the gilrs backend when built with the `gilrs`
+//   feature (on by default) and falls back to `NoGamepads`
+
+use super::action::InputSource;
+use super::axis::InputAxis;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// How far a stick must be pushed for its direction to count as pressed
+pub const AXIS_PRESS_THRESHOLD: f32 = 0.5;
+
+/// Identifies a connected device (stable for as long as the backend sees it)
+pub type GamepadId = usize;
+
+/// Gamepad buttons, named by position (south = A on Xbox, Cross on PlayStation)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadButton {
+    South,
+    East,
+    West,
+    North,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// Analog stick axes (-1.0 to 1.0, up and right are positive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+}
+
+impl GamepadAxis {
+    /// Get the movement axis this stick axis drives (the left stick moves)
+    pub fn movement_axis(&self) -> Option<InputAxis> {
+        match self {
+            Self::LeftStickX => Some(InputAxis::Horizontal),
+            Self::LeftStickY => Some(InputAxis::Vertical),
+            Self::RightStickX | Self::RightStickY => None,
+        }
+    }
+}
+
+/// One half of an axis, used to bind a stick direction like a button
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AxisDirection {
+    Negative,
+    Positive,
+}
+
+impl AxisDirection {
+    /// Check if an axis value pushes far enough in this direction
+    pub fn is_pressed(&self, value: f32) -> bool {
+        match self {
+            Self::Negative => value <= -AXIS_PRESS_THRESHOLD,
+            Self::Positive => value >= AXIS_PRESS_THRESHOLD,
+        }
+    }
+}
+
+/// Something a gamepad backend reports
+#[derive(Debug, Clone, PartialEq)]
+pub enum GamepadEvent {
+    Connected {
+        id: GamepadId,
+        name: String,
+    },
+    Disconnected {
+        id: GamepadId,
+    },
+    Button {
+        id: GamepadId,
+        button: GamepadButton,
+        pressed: bool,
+    },
+    Axis {
+        id: GamepadId,
+        axis: GamepadAxis,
+        value: f32,
+    },
+}
+
+/// A connection change, as seen by the game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GamepadHotplug {
+    /// A pad was plugged in and given to a player (None = every player has one)
+    Connected {
+        id: GamepadId,
+        name: String,
+        player: Option<usize>,
+    },
+    /// A pad was unplugged from a player
+    Disconnected {
+        id: GamepadId,
+        player: Option<usize>,
+    },
+}
+
+/// Source of gamepad events (the platform layer)
+pub trait GamepadBackend {
+    /// Take every event since the last poll, oldest first
+    fn poll(&mut self) -> Vec<GamepadEvent>;
+}
+
+/// Backend for builds without gamepad support
+#[derive(Debug, Default)]
+pub struct NoGamepads;
+
+impl GamepadBackend for NoGamepads {
+    fn poll(&mut self) -> Vec<GamepadEvent> {
+        Vec::new()
+    }
+}
+
+/// Open the platform's gamepad backend, or `NoGamepads` if it can't be used
+pub fn platform_backend() -> Box<dyn GamepadBackend> {
+    #[cfg(feature = "gilrs")]
+    match super::gilrs_backend::GilrsGamepads::new() {
+        Ok(backend) => return Box::new(backend),
+        Err(e) => log::warn!("Gamepads unavailable: {}", e),
+    }
+    Box::new(NoGamepads)
+}
+
+/// Scripted backend: queue events by hand and they come out on the next poll
+#[derive(Debug, Default)]
+pub struct FakeGamepads {
+    events: VecDeque<GamepadEvent>,
+}
+
+impl FakeGamepads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Plug in a pad
+    pub fn connect(&mut self, id: GamepadId, name: &str) {
+        self.events.push_back(GamepadEvent::Connected {
+            id,
+            name: name.to_string(),
+        });
+    }
+
+    /// Unplug a pad
+    pub fn disconnect(&mut self, id: GamepadId) {
+        self.events.push_back(GamepadEvent::Disconnected { id });
+    }
+
+    /// Press or release a button
+    pub fn set_button(&mut self, id: GamepadId, button: GamepadButton, pressed: bool) {
+        self.events.push_back(GamepadEvent::Button {
+            id,
+            button,
+            pressed,
+        });
+    }
+
+    /// Move a stick axis
+    pub fn set_axis(&mut self, id: GamepadId, axis: GamepadAxis, value: f32) {
+        self.events
+            .push_back(GamepadEvent::Axis { id, axis, value });
+    }
+}
+
+impl GamepadBackend for FakeGamepads {
+    fn poll(&mut self) -> Vec<GamepadEvent> {
+        self.events.drain(..).collect()
+    }
+}
+
+/// State of one connected pad
+#[derive(Debug, Clone, Default)]
+pub struct GamepadState {
+    pub name: String,
+    /// Player the pad controls (None = no free player when it connected)
+    pub player: Option<usize>,
+    /// Buttons and stick directions currently down
+    held: HashSet<InputSource>,
+    /// Latest value of each axis
+    axes: HashMap<GamepadAxis, f32>,
+}
+
+impl GamepadState {
+    /// Get the latest value of an axis (0.0 if it never moved)
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0.0)
+    }
+
+    /// Check if a button or stick direction is down
+    pub fn is_held(&self, source: InputSource) -> bool {
+        self.held.contains(&source)
+    }
+}
+
+/// Source changes caused by one gamepad event: (source, pressed)
+pub(crate) type SourceChanges = Vec<(InputSource, bool)>;
+
+/// Connected pads and which player each one belongs to
+#[derive(Debug, Default)]
+pub struct GamepadRegistry {
+    pads: HashMap<GamepadId, GamepadState>,
+    /// Player each pad last belonged to, so reconnecting restores it
+    previous_players: HashMap<GamepadId, usize>,
+}
+
+impl GamepadRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a connected pad
+    pub fn get(&self, id: GamepadId) -> Option<&GamepadState> {
+        self.pads.get(&id)
+    }
+
+    /// Get the pad a player is using, if any
+    pub fn pad_of(&self, player: usize) -> Option<(GamepadId, &GamepadState)> {
+        self.pads
+            .iter()
+            .find(|(_, pad)| pad.player == Some(player))
+            .map(|(&id, pad)| (id, pad))
+    }
+
+    /// Get the number of connected pads
+    pub fn count(&self) -> usize {
+        self.pads.len()
+    }
+
+    /// Add a pad, giving it to its previous player or the first player without one
+    pub(crate) fn connect(
+        &mut self,
+        id: GamepadId,
+        name: String,
+        max_players: usize,
+    ) -> Option<usize> {
+        let is_free = |player: &usize| self.pads.values().all(|pad| pad.player != Some(*player));
+        let player = self
+            .previous_players
+            .get(&id)
+            .copied()
+            .filter(is_free)
+            .or_else(|| (0..max_players).find(is_free));
+        self.pads.insert(
+            id,
+            GamepadState {
+                name,
+                player,
+                ..GamepadState::default()
+            },
+        );
+        player
+    }
+
+    /// Remove a pad; returns its player and the sources it was holding down
+    pub(crate) fn disconnect(&mut self, id: GamepadId) -> Option<(Option<usize>, SourceChanges)> {
+        let pad = self.pads.remove(&id)?;
+        if let Some(player) = pad.player {
+            self.previous_players.insert(id, player);
+        }
+        let released = pad.held.into_iter().map(|source| (source, false)).collect();
+        Some((pad.player, released))
+    }
+
+    /// Give a pad to a player, swapping with whoever had the player's pad
+    /// Returns the sources to release for the old owners
+    pub(crate) fn assign(&mut self, id: GamepadId, player: usize) -> Vec<(usize, SourceChanges)> {
+        let Some(previous) = self.pads.get(&id).map(|pad| pad.player) else {
+            return Vec::new();
+        };
+        let mut released = Vec::new();
+        let other = self
+            .pads
+            .iter()
+            .find(|(&other, pad)| other != id && pad.player == Some(player))
+            .map(|(&other, _)| other);
+        if let Some(other) = other {
+            let pad = self.pads.get_mut(&other).expect("pad was just found");
+            pad.player = previous;
+            released.push((player, Self::release_all(pad)));
+        }
+        let pad = self.pads.get_mut(&id).expect("pad was just found");
+        if let Some(previous) = previous {
+            released.push((previous, Self::release_all(pad)));
+        }
+        pad.player = Some(player);
+        released
+    }
+
+    /// Apply a button or axis event to a pad's state
+    /// Returns the pad's player and the sources that went down or up
+    pub(crate) fn apply(&mut self, event: &GamepadEvent) -> Option<(usize, SourceChanges)> {
+        let mut changes = Vec::new();
+        let pad = match *event {
+            GamepadEvent::Button {
+                id,
+                button,
+                pressed,
+            } => {
+                let pad = self.pads.get_mut(&id)?;
+                let source = InputSource::GamepadButton(button);
+                if pressed && pad.held.insert(source) || !pressed && pad.held.remove(&source) {
+                    changes.push((source, pressed));
+                }
+                pad
+            }
+            GamepadEvent::Axis { id, axis, value } => {
+                let pad = self.pads.get_mut(&id)?;
+                pad.axes.insert(axis, value);
+                for direction in [AxisDirection::Negative, AxisDirection::Positive] {
+                    let source = InputSource::GamepadAxis(axis, direction);
+                    let pressed = direction.is_pressed(value);
+                    if pressed && pad.held.insert(source) || !pressed && pad.held.remove(&source) {
+                        changes.push((source, pressed));
+                    }
+                }
+                pad
+            }
+            GamepadEvent::Connected { .. } | GamepadEvent::Disconnected { .. } => return None,
+        };
+        Some((pad.player?, changes))
+    }
+
+    fn release_all(pad: &mut GamepadState) -> SourceChanges {
+        pad.axes.clear();
+        pad.held.drain().map(|source| (source, false)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pads_go_to_free_players_and_come_back() {
+        let mut registry = GamepadRegistry::new();
+        assert_eq!(registry.connect(10, "A".to_string(), 2), Some(0));
+        assert_eq!(registry.connect(11, "B".to_string(), 2), Some(1));
+        assert_eq!(registry.connect(12, "C".to_string(), 2), None);
+
+        // Unplugging frees the player; the same pad reclaims it on return
+        registry.disconnect(10);
+        registry.disconnect(12);
+        assert_eq!(registry.connect(12, "C".to_string(), 2), Some(0));
+        registry.disconnect(12);
+        assert_eq!(registry.connect(10, "A".to_string(), 2), Some(0));
+    }
+
+    #[test]
+    fn test_stick_directions_act_as_buttons() {
+        let mut registry = GamepadRegistry::new();
+        registry.connect(3, "Pad".to_string(), 4);
+        let left = InputSource::GamepadAxis(GamepadAxis::LeftStickX, AxisDirection::Negative);
+
+        let axis = |value| GamepadEvent::Axis {
+            id: 3,
+            axis: GamepadAxis::LeftStickX,
+            value,
+        };
+        assert_eq!(registry.apply(&axis(-0.3)), Some((0, vec![])));
+        assert_eq!(registry.apply(&axis(-0.9)), Some((0, vec![(left, true)])));
+        assert_eq!(registry.apply(&axis(-0.95)), Some((0, vec![])));
+        assert_eq!(registry.apply(&axis(0.0)), Some((0, vec![(left, false)])));
+        assert_eq!(registry.get(3).unwrap().axis(GamepadAxis::LeftStickX), 0.0);
+    }
+}
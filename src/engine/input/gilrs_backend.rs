@@ -0,0 +1,149 @@
+// Gamepad backend built on gilrs (evdev on Linux, XInput/WGI on Windows,
+// IOKit on macOS)
+//
+// gilrs names buttons by position like `GamepadButton` does, so most of the
+// work is renaming. Buttons and axes the game has no use for (C/Z, the mode
+// button, analog trigger values, d-pad axes) are dropped. Pads already plugged
+// in when the game starts are reported as connected on the first poll.
+
+use super::gamepad::{GamepadAxis, GamepadBackend, GamepadButton, GamepadEvent, GamepadId};
+use gilrs::{Axis, Button, EventType, Gilrs};
+use std::collections::HashSet;
+
+/// Gamepads read through gilrs
+pub struct GilrsGamepads {
+    gilrs: Gilrs,
+    /// Pads reported as connected, so one isn't announced twice
+    connected: HashSet<GamepadId>,
+}
+
+impl GilrsGamepads {
+    /// Open the platform's gamepad API
+    /// (the error is boxed since gilrs hands back a whole `Gilrs` in it)
+    pub fn new() -> Result<Self, Box<gilrs::Error>> {
+        Ok(Self {
+            gilrs: Gilrs::new().map_err(Box::new)?,
+            connected: HashSet::new(),
+        })
+    }
+
+    fn pad_name(&self, id: gilrs::GamepadId) -> String {
+        self.gilrs.gamepad(id).name().to_string()
+    }
+}
+
+impl GamepadBackend for GilrsGamepads {
+    fn poll(&mut self) -> Vec<GamepadEvent> {
+        let mut events = Vec::new();
+
+        // Pads plugged in before the game started never send `Connected`
+        let present: Vec<gilrs::GamepadId> = self.gilrs.gamepads().map(|(id, _)| id).collect();
+        for id in present {
+            if self.connected.insert(id.into()) {
+                events.push(GamepadEvent::Connected {
+                    id: id.into(),
+                    name: self.pad_name(id),
+                });
+            }
+        }
+
+        while let Some(gilrs::Event { id, event, .. }) = self.gilrs.next_event() {
+            let pad: GamepadId = id.into();
+            let event = match event {
+                EventType::Connected => {
+                    if !self.connected.insert(pad) {
+                        continue;
+                    }
+                    GamepadEvent::Connected {
+                        id: pad,
+                        name: self.pad_name(id),
+                    }
+                }
+                EventType::Disconnected => {
+                    if !self.connected.remove(&pad) {
+                        continue;
+                    }
+                    GamepadEvent::Disconnected { id: pad }
+                }
+                EventType::ButtonPressed(button, _) | EventType::ButtonReleased(button, _) => {
+                    let Some(button) = map_button(button) else {
+                        continue;
+                    };
+                    GamepadEvent::Button {
+                        id: pad,
+                        button,
+                        pressed: matches!(event, EventType::ButtonPressed(..)),
+                    }
+                }
+                EventType::AxisChanged(axis, value, _) => {
+                    let Some(axis) = map_axis(axis) else {
+                        continue;
+                    };
+                    GamepadEvent::Axis {
+                        id: pad,
+                        axis,
+                        value,
+                    }
+                }
+                _ => continue,
+            };
+            events.push(event);
+        }
+        events
+    }
+}
+
+/// Rename a gilrs button (gilrs calls the bumpers triggers and the triggers triggers 2)
+fn map_button(button: Button) -> Option<GamepadButton> {
+    Some(match button {
+        Button::South => GamepadButton::South,
+        Button::East => GamepadButton::East,
+        Button::West => GamepadButton::West,
+        Button::North => GamepadButton::North,
+        Button::LeftTrigger => GamepadButton::LeftBumper,
+        Button::RightTrigger => GamepadButton::RightBumper,
+        Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+        Button::RightTrigger2 => GamepadButton::RightTrigger,
+        Button::Select => GamepadButton::Select,
+        Button::Start => GamepadButton::Start,
+        Button::LeftThumb => GamepadButton::LeftStick,
+        Button::RightThumb => GamepadButton::RightStick,
+        Button::DPadUp => GamepadButton::DPadUp,
+        Button::DPadDown => GamepadButton::DPadDown,
+        Button::DPadLeft => GamepadButton::DPadLeft,
+        Button::DPadRight => GamepadButton::DPadRight,
+        Button::C | Button::Z | Button::Mode | Button::Unknown => return None,
+    })
+}
+
+/// Rename a gilrs stick axis
+fn map_axis(axis: Axis) -> Option<GamepadAxis> {
+    match axis {
+        Axis::LeftStickX => Some(GamepadAxis::LeftStickX),
+        Axis::LeftStickY => Some(GamepadAxis::LeftStickY),
+        Axis::RightStickX => Some(GamepadAxis::RightStickX),
+        Axis::RightStickY => Some(GamepadAxis::RightStickY),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_button_and_axis_names() {
+        assert_eq!(map_button(Button::South), Some(GamepadButton::South));
+        assert_eq!(
+            map_button(Button::LeftTrigger),
+            Some(GamepadButton::LeftBumper)
+        );
+        assert_eq!(
+            map_button(Button::RightTrigger2),
+            Some(GamepadButton::RightTrigger)
+        );
+        assert_eq!(map_button(Button::Mode), None);
+        assert_eq!(map_axis(Axis::LeftStickY), Some(GamepadAxis::LeftStickY));
+        assert_eq!(map_axis(Axis::DPadX), None);
+    }
+}
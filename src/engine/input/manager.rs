@@ -1,10 +1,16 @@
 // Input manager - Main coordination system for all input
 
 use super::action::{Action, InputSource};
+use super::chord::ActiveChords;
 use super::config::InputConfigManager;
+use super::gamepad::{GamepadBackend, GamepadEvent, GamepadHotplug, GamepadId, GamepadRegistry};
 use super::player::PlayerInput;
+use super::rebind::{
+    apply_rebind, find_conflicts, RebindCapture, RebindChoice, RebindOutcome, RebindState,
+};
+use std::collections::HashSet;
 use winit::event::{ElementState, KeyEvent, MouseButton};
-use winit::keyboard::PhysicalKey;
+use winit::keyboard::{KeyCode, PhysicalKey};
 
 /// Main input manager that coordinates all input for all players
 pub struct InputManager {
@@ -14,6 +20,21 @@ pub struct InputManager {
     /// Input state for each player
     players: Vec<PlayerInput>,
 
+    /// Connected gamepads and the player each one belongs to
+    gamepads: GamepadRegistry,
+
+    /// Keys and mouse buttons currently down (shared by every player)
+    held: HashSet<InputSource>,
+
+    /// Bindings holding each player's actions down
+    active: Vec<ActiveChords>,
+
+    /// Whether the next input is captured for rebinding
+    rebind: RebindState,
+
+    /// How the last rebind ended, until the menu takes it
+    rebind_outcome: Option<RebindOutcome>,
+
     /// Maximum number of supported players
     max_players: usize,
 }
@@ -31,6 +52,26 @@ impl InputManager {
         Self {
             config,
             players,
+            gamepads: GamepadRegistry::new(),
+            held: HashSet::new(),
+            active: (0..max_players).map(|_| ActiveChords::default()).collect(),
+            rebind: RebindState::Idle,
+            rebind_outcome: None,
+            max_players,
+        }
+    }
+
+    /// Create an input manager with existing bindings (e.g. loaded from a file)
+    pub fn with_config(config: InputConfigManager) -> Self {
+        let max_players = config.num_players();
+        Self {
+            config,
+            players: (0..max_players).map(PlayerInput::new).collect(),
+            gamepads: GamepadRegistry::new(),
+            held: HashSet::new(),
+            active: (0..max_players).map(|_| ActiveChords::default()).collect(),
+            rebind: RebindState::Idle,
+            rebind_outcome: None,
             max_players,
         }
     }
@@ -40,50 +81,224 @@ impl InputManager {
         // Only process physical key presses
         if let PhysicalKey::Code(key_code) = event.physical_key {
             let source = InputSource::key(key_code);
-
-            // Check each player's bindings
-            for player_id in 0..self.max_players {
-                if let Some(action) = self.config.get_action(player_id, source) {
-                    if let Some(player) = self.players.get_mut(player_id) {
-                        match event.state {
-                            ElementState::Pressed => {
-                                if !event.repeat {
-                                    // Only register if not a key repeat
-                                    player.press(action);
-                                }
-                            }
-                            ElementState::Released => {
-                                player.release(action);
-                            }
-                        }
-                    }
-                }
+            // Only register if not a key repeat
+            if !event.repeat {
+                self.process_shared_source(source, event.state);
             }
         }
     }
 
     /// Process a mouse button event from winit
     pub fn process_mouse_button_event(&mut self, button: MouseButton, state: ElementState) {
-        let source = InputSource::mouse(button);
+        // The mouse goes to whichever players have it bound (Player 1 by default)
+        self.process_shared_source(InputSource::mouse(button), state);
+    }
+
+    /// Press or release a key or mouse button for every player that binds it
+    fn process_shared_source(&mut self, source: InputSource, state: ElementState) {
+        match state {
+            ElementState::Pressed => {
+                // A second press without a release (e.g. after losing focus) is ignored
+                if !self.held.insert(source) || self.capture(source) {
+                    return;
+                }
+                for player_id in 0..self.max_players {
+                    self.press_source(player_id, source);
+                }
+            }
+            ElementState::Released => {
+                self.held.remove(&source);
+                for player_id in 0..self.max_players {
+                    self.release_source(player_id, source);
+                }
+            }
+        }
+    }
 
-        // Mouse input is typically for Player 1 only (local player)
-        // In multiplayer, each player has their own mouse on their own machine
-        let player_id = 0;
+    /// Take every pending event from a gamepad backend
+    /// Returns the pads that were plugged in or unplugged
+    pub fn poll_gamepads(&mut self, backend: &mut dyn GamepadBackend) -> Vec<GamepadHotplug> {
+        backend
+            .poll()
+            .into_iter()
+            .filter_map(|event| self.process_gamepad_event(event))
+            .collect()
+    }
 
-        if let Some(action) = self.config.get_action(player_id, source) {
-            if let Some(player) = self.players.get_mut(player_id) {
-                match state {
-                    ElementState::Pressed => {
-                        player.press(action);
+    /// Process a single gamepad event
+    /// Buttons and sticks go to the player the pad is assigned to
+    pub fn process_gamepad_event(&mut self, event: GamepadEvent) -> Option<GamepadHotplug> {
+        match event {
+            GamepadEvent::Connected { id, name } => {
+                let player = self.gamepads.connect(id, name.clone(), self.max_players);
+                Some(GamepadHotplug::Connected { id, name, player })
+            }
+            GamepadEvent::Disconnected { id } => {
+                let (player, released) = self.gamepads.disconnect(id)?;
+                if let Some(player) = player {
+                    self.release_gamepad(player, released);
+                }
+                Some(GamepadHotplug::Disconnected { id, player })
+            }
+            event => {
+                if let Some((player, changes)) = self.gamepads.apply(&event) {
+                    let pressed = changes.iter().find(|(_, pressed)| *pressed);
+                    if let Some(&(source, _)) = pressed {
+                        if self.capture(source) {
+                            return None;
+                        }
                     }
-                    ElementState::Released => {
-                        player.release(action);
+                    self.apply_source_changes(player, changes);
+                    // The left stick also drives the player's analog movement
+                    if let GamepadEvent::Axis { axis, value, .. } = event {
+                        if let (Some(axis), Some(input)) =
+                            (axis.movement_axis(), self.players.get_mut(player))
+                        {
+                            input.set_analog(axis, value);
+                        }
                     }
                 }
+                None
+            }
+        }
+    }
+
+    /// Give a connected pad to a player
+    /// If the player already had a pad, the two players swap pads
+    pub fn assign_gamepad(&mut self, id: GamepadId, player_id: usize) {
+        if player_id >= self.max_players {
+            return;
+        }
+        for (player, released) in self.gamepads.assign(id, player_id) {
+            self.release_gamepad(player, released);
+        }
+    }
+
+    /// Get the connected gamepads
+    pub fn gamepads(&self) -> &GamepadRegistry {
+        &self.gamepads
+    }
+
+    /// Capture the next input for a player's action instead of dispatching it
+    pub fn start_rebind(&mut self, player_id: usize, action: Action) {
+        if player_id < self.max_players {
+            self.rebind = RebindState::Listening { player_id, action };
+            self.rebind_outcome = None;
+        }
+    }
+
+    /// Stop listening, or drop a capture waiting on a conflict
+    pub fn cancel_rebind(&mut self) {
+        if self.rebind != RebindState::Idle {
+            self.rebind = RebindState::Idle;
+            self.rebind_outcome = Some(RebindOutcome::Cancelled);
+        }
+    }
+
+    /// Settle a capture that conflicts with other bindings
+    pub fn resolve_rebind(&mut self, choice: RebindChoice) {
+        if let RebindState::Conflict(capture) =
+            std::mem::replace(&mut self.rebind, RebindState::Idle)
+        {
+            self.rebind_outcome = Some(apply_rebind(&mut self.config, &capture, choice));
+        }
+    }
+
+    /// Get where rebinding stands
+    pub fn rebind_state(&self) -> &RebindState {
+        &self.rebind
+    }
+
+    /// Take how the last rebind ended (a conflict stays pending until resolved)
+    pub fn take_rebind_outcome(&mut self) -> Option<RebindOutcome> {
+        self.rebind_outcome.take()
+    }
+
+    /// Capture a pressed source if listening for a rebind
+    /// Returns true if the press was captured and must not be dispatched
+    fn capture(&mut self, source: InputSource) -> bool {
+        let RebindState::Listening { player_id, action } = self.rebind else {
+            return false;
+        };
+        if source == InputSource::key(KeyCode::Escape) {
+            self.cancel_rebind();
+            return true;
+        }
+
+        let capture = RebindCapture {
+            player_id,
+            action,
+            source,
+            conflicts: find_conflicts(&self.config, player_id, action, source),
+        };
+        if capture.conflicts.is_empty() {
+            self.rebind = RebindState::Idle;
+            self.rebind_outcome = Some(apply_rebind(
+                &mut self.config,
+                &capture,
+                RebindChoice::Replace,
+            ));
+        } else {
+            self.rebind = RebindState::Conflict(capture.clone());
+            self.rebind_outcome = Some(RebindOutcome::Conflict(capture));
+        }
+        true
+    }
+
+    /// Release everything a pad was holding for a player that lost it
+    fn release_gamepad(&mut self, player_id: usize, released: Vec<(InputSource, bool)>) {
+        self.apply_source_changes(player_id, released);
+        if let Some(player) = self.players.get_mut(player_id) {
+            player.clear_analog();
+        }
+    }
+
+    /// Press or release the actions bound to sources for one player
+    fn apply_source_changes(&mut self, player_id: usize, changes: Vec<(InputSource, bool)>) {
+        for (source, pressed) in changes {
+            if pressed {
+                self.press_source(player_id, source);
+            } else {
+                self.release_source(player_id, source);
             }
         }
     }
 
+    /// Fire the binding a source press completes for a player
+    /// Its action is only pressed if no other binding already holds it
+    fn press_source(&mut self, player_id: usize, source: InputSource) {
+        let pad = self.gamepads.pad_of(player_id).map(|(_, pad)| pad);
+        let is_held = |source: InputSource| {
+            self.held.contains(&source) || pad.is_some_and(|pad| pad.is_held(source))
+        };
+        let Some((chord, action)) = self.config.resolve(player_id, source, is_held) else {
+            return;
+        };
+        let (Some(active), Some(player)) = (
+            self.active.get_mut(player_id),
+            self.players.get_mut(player_id),
+        ) else {
+            return;
+        };
+        if active.activate(chord, action) {
+            player.press(action);
+        }
+    }
+
+    /// End the bindings a released source was part of for a player
+    /// Their actions are only released once no binding holds them
+    fn release_source(&mut self, player_id: usize, source: InputSource) {
+        let (Some(active), Some(player)) = (
+            self.active.get_mut(player_id),
+            self.players.get_mut(player_id),
+        ) else {
+            return;
+        };
+        for action in active.deactivate(source) {
+            player.release(action);
+        }
+    }
+
     /// Update all player input states for a new frame
     /// Call this once per frame after processing all events
     pub fn update(&mut self) {
@@ -136,6 +351,9 @@ impl InputManager {
         for player in &mut self.players {
             player.reset();
         }
+        for active in &mut self.active {
+            active.clear();
+        }
     }
 
     /// Get the number of players
@@ -325,6 +543,169 @@ mod tests {
         assert!(manager.player(0).unwrap().just_released(Action::Ability1));
     }
 
+    #[test]
+    fn test_gamepad_hotplug_and_input() {
+        use crate::engine::input::gamepad::{FakeGamepads, GamepadAxis, GamepadButton};
+
+        let mut manager = InputManager::new(4);
+        let mut pads = FakeGamepads::new();
+        pads.connect(5, "Pad A");
+        pads.connect(9, "Pad B");
+        let hotplugs = manager.poll_gamepads(&mut pads);
+        assert_eq!(
+            hotplugs[1],
+            GamepadHotplug::Connected {
+                id: 9,
+                name: "Pad B".to_string(),
+                player: Some(1)
+            }
+        );
+
+        // Each pad drives its own player
+        pads.set_button(9, GamepadButton::South, true);
+        pads.set_axis(5, GamepadAxis::LeftStickX, 0.8);
+        manager.poll_gamepads(&mut pads);
+        assert!(manager.player(1).unwrap().just_pressed(Action::Jump));
+        assert!(!manager.player(0).unwrap().is_pressed(Action::Jump));
+        assert!(manager.player(0).unwrap().is_pressed(Action::MoveRight));
+        let walk = manager.player(0).unwrap().get_direction().0;
+        assert!(
+            walk > 0.5 && walk < 1.0,
+            "Stick should walk partway, got {}",
+            walk
+        );
+
+        // Unplugging releases whatever the pad was holding
+        pads.disconnect(5);
+        manager.poll_gamepads(&mut pads);
+        assert!(!manager.player(0).unwrap().is_pressed(Action::MoveRight));
+        assert_eq!(manager.player(0).unwrap().get_direction().0, 0.0);
+        assert_eq!(manager.gamepads().count(), 1);
+
+        // Moving a pad to another player releases its buttons for the old one
+        manager.assign_gamepad(9, 3);
+        assert!(!manager.player(1).unwrap().is_pressed(Action::Jump));
+        assert_eq!(manager.gamepads().pad_of(3).map(|(id, _)| id), Some(9));
+    }
+
+    #[test]
+    fn test_rebind_capture() {
+        use crate::engine::input::gamepad::{FakeGamepads, GamepadButton};
+
+        let mut manager = InputManager::new(4);
+        manager.start_rebind(0, Action::Ability1);
+
+        // The next press is captured instead of reaching the player
+        manager.process_mouse_button_event(MouseButton::Back, ElementState::Pressed);
+        assert!(!manager.player(0).unwrap().is_pressed(Action::Ability1));
+        assert!(matches!(
+            manager.take_rebind_outcome(),
+            Some(RebindOutcome::Bound { displaced, .. }) if displaced.is_empty()
+        ));
+        assert_eq!(manager.rebind_state(), &RebindState::Idle);
+        assert_eq!(
+            manager
+                .config()
+                .get_action(0, InputSource::mouse(MouseButton::Back)),
+            Some(Action::Ability1)
+        );
+        assert_eq!(
+            manager
+                .config()
+                .get_action(0, InputSource::mouse(MouseButton::Left)),
+            None
+        );
+
+        // A pad button used by another action waits for a choice
+        let mut pads = FakeGamepads::new();
+        pads.connect(1, "Pad");
+        manager.poll_gamepads(&mut pads);
+        manager.start_rebind(0, Action::Jump);
+        pads.set_button(1, GamepadButton::West, true);
+        manager.poll_gamepads(&mut pads);
+        assert!(!manager.player(0).unwrap().is_pressed(Action::Ability1));
+        assert!(matches!(
+            manager.take_rebind_outcome(),
+            Some(RebindOutcome::Conflict(_))
+        ));
+        manager.resolve_rebind(RebindChoice::Swap);
+        let west = InputSource::gamepad(GamepadButton::West);
+        let south = InputSource::gamepad(GamepadButton::South);
+        assert_eq!(manager.config().get_action(0, west), Some(Action::Jump));
+        assert_eq!(
+            manager.config().get_action(0, south),
+            Some(Action::Ability1)
+        );
+
+        // Nothing is captured once rebinding is over
+        manager.process_mouse_button_event(MouseButton::Back, ElementState::Released);
+        manager.process_mouse_button_event(MouseButton::Back, ElementState::Pressed);
+        assert!(manager.player(0).unwrap().is_pressed(Action::Ability1));
+    }
+
+    #[test]
+    fn test_modifier_chord() {
+        use crate::engine::input::chord::Chord;
+        use crate::engine::input::gamepad::{FakeGamepads, GamepadButton};
+
+        let mut manager = InputManager::new(4);
+        let left_bumper = InputSource::gamepad(GamepadButton::LeftBumper);
+        let west = InputSource::gamepad(GamepadButton::West);
+        manager.config_mut().get_config_mut(0).unwrap().bind_chord(
+            Chord::new(west).with_modifier(left_bumper),
+            Action::Ability2,
+        );
+        let mut pads = FakeGamepads::new();
+        pads.connect(1, "Pad");
+        manager.poll_gamepads(&mut pads);
+
+        // Holding the modifier turns the trigger into the alternate ability
+        pads.set_button(1, GamepadButton::LeftBumper, true);
+        pads.set_button(1, GamepadButton::West, true);
+        manager.poll_gamepads(&mut pads);
+        let player = manager.player(0).unwrap();
+        assert!(player.just_pressed(Action::Ability2));
+        assert!(!player.is_pressed(Action::Ability1));
+
+        // Letting go of the modifier ends the chord
+        manager.update();
+        pads.set_button(1, GamepadButton::LeftBumper, false);
+        manager.poll_gamepads(&mut pads);
+        assert!(manager.player(0).unwrap().just_released(Action::Ability2));
+
+        // Without the modifier the trigger fires its plain binding
+        pads.set_button(1, GamepadButton::West, false);
+        pads.set_button(1, GamepadButton::West, true);
+        manager.poll_gamepads(&mut pads);
+        assert!(manager.player(0).unwrap().just_pressed(Action::Ability1));
+    }
+
+    #[test]
+    fn test_action_stays_pressed_while_any_binding_is_held() {
+        use crate::engine::input::gamepad::{FakeGamepads, GamepadButton};
+
+        let mut manager = InputManager::new(4);
+        let mut pads = FakeGamepads::new();
+        pads.connect(1, "Pad");
+        manager.poll_gamepads(&mut pads);
+
+        // The mouse and the pad both fire Ability1 for Player 1
+        manager.process_mouse_button_event(MouseButton::Left, ElementState::Pressed);
+        manager.update();
+        pads.set_button(1, GamepadButton::West, true);
+        manager.poll_gamepads(&mut pads);
+        assert!(!manager.player(0).unwrap().just_pressed(Action::Ability1));
+
+        manager.process_mouse_button_event(MouseButton::Left, ElementState::Released);
+        let player = manager.player(0).unwrap();
+        assert!(player.is_pressed(Action::Ability1));
+        assert!(!player.just_released(Action::Ability1));
+
+        pads.set_button(1, GamepadButton::West, false);
+        manager.poll_gamepads(&mut pads);
+        assert!(manager.player(0).unwrap().just_released(Action::Ability1));
+    }
+
     #[test]
     fn test_all_mouse_abilities() {
         let mut manager = InputManager::new(4);
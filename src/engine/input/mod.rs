@@ -6,9 +6,15 @@
 // ## Architecture
 //
 // - `action`: Defines game actions and default key bindings
+// - `axis`: Analog movement axes with deadzones and response curves
 // - `buffer`: Input buffering for reliable input detection
 // - `player`: Per-player input state management
 // - `config`: Input configuration and remapping system
+// - `chord`: Modifier chords, and tracking which bindings hold an action down
+// - `file`: Saving and loading bindings as a TOML file in the user's config directory
+// - `gamepad`: Gamepad backends, hotplug and pad-to-player assignment
+// - `gilrs_backend`: The platform gamepad backend (`gilrs` feature)
+// - `rebind`: Capturing the next input to rebind an action, with conflict swaps
 // - `manager`: Main input manager coordinating everything
 //
 // ## Usage Example
@@ -22,6 +28,9 @@
 // // In your event loop, process keyboard events
 // input_manager.process_keyboard_event(&key_event);
 //
+// // Once per frame, pick up gamepad hotplugs, buttons and sticks
+// input_manager.poll_gamepads(&mut gamepad_backend);
+//
 // // At the end of each frame, update the input state
 // input_manager.update();
 //
@@ -37,13 +46,28 @@
 // ```
 
 pub mod action;
+pub mod axis;
 pub mod buffer;
+pub mod chord;
 pub mod config;
+pub mod file;
+pub mod gamepad;
+#[cfg(feature = "gilrs")]
+pub mod gilrs_backend;
 pub mod manager;
 pub mod player;
+pub mod rebind;
 
 // Re-export commonly used types
 pub use action::{Action, InputSource};
+pub use axis::{AxisSettings, InputAxis, ResponseCurve};
+pub use chord::Chord;
 pub use config::{InputConfig, InputConfigManager};
+pub use file::{bindings_path, BindingsFile, BindingsFileError};
+pub use gamepad::{
+    platform_backend, AxisDirection, FakeGamepads, GamepadAxis, GamepadBackend, GamepadButton,
+    GamepadEvent, GamepadHotplug, GamepadId, NoGamepads,
+};
 pub use manager::InputManager;
 pub use player::PlayerInput;
+pub use rebind::{BindingConflict, RebindCapture, RebindChoice, RebindOutcome, RebindState};
@@ -1,8 +1,9 @@
 // Per-player input state management
 
 use super::action::Action;
+use super::axis::{AxisSettings, InputAxis};
 use super::buffer::InputBuffer;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Represents the input state for a single player
 #[derive(Debug)]
@@ -24,6 +25,12 @@ pub struct PlayerInput {
 
     /// Input buffer for delayed/buffered inputs
     buffer: InputBuffer,
+
+    /// Raw stick values driving the movement axes
+    analog: HashMap<InputAxis, f32>,
+
+    /// Deadzones and response curve applied to stick values
+    axis_settings: AxisSettings,
 }
 
 impl PlayerInput {
@@ -36,6 +43,8 @@ impl PlayerInput {
             just_released: HashSet::new(),
             previous_pressed: HashSet::new(),
             buffer: InputBuffer::new(),
+            analog: HashMap::new(),
+            axis_settings: AxisSettings::default(),
         }
     }
 
@@ -93,6 +102,16 @@ impl PlayerInput {
         }
     }
 
+    /// Set the raw stick value of a movement axis
+    pub(crate) fn set_analog(&mut self, axis: InputAxis, raw: f32) {
+        self.analog.insert(axis, raw.clamp(-1.0, 1.0));
+    }
+
+    /// Center both sticks (the gamepad went away)
+    pub(crate) fn clear_analog(&mut self) {
+        self.analog.clear();
+    }
+
     /// Update input state for a new frame
     /// Call this once per frame after processing all events
     pub(crate) fn update(&mut self) {
@@ -114,6 +133,7 @@ impl PlayerInput {
         self.just_released.clear();
         self.previous_pressed.clear();
         self.buffer.clear();
+        self.analog.clear();
     }
 
     /// Get all currently pressed actions
@@ -126,26 +146,47 @@ impl PlayerInput {
         self.just_pressed.iter().copied().collect()
     }
 
-    /// Get directional input as a normalized vector (-1.0 to 1.0)
-    /// Returns (horizontal, vertical)
-    pub fn get_direction(&self) -> (f32, f32) {
-        let mut horizontal = 0.0;
-        let mut vertical = 0.0;
+    /// Get the deadzones and response curve applied to stick values
+    pub fn axis_settings(&self) -> AxisSettings {
+        self.axis_settings
+    }
 
-        if self.is_pressed(Action::MoveLeft) {
-            horizontal -= 1.0;
-        }
-        if self.is_pressed(Action::MoveRight) {
-            horizontal += 1.0;
+    /// Set the deadzones and response curve applied to stick values
+    pub fn set_axis_settings(&mut self, settings: AxisSettings) {
+        self.axis_settings = settings;
+    }
+
+    /// Get a movement axis (-1.0 to 1.0)
+    /// A stick outside its deadzone wins; otherwise the movement actions
+    /// (keys, d-pad) give full deflection
+    pub fn axis(&self, axis: InputAxis) -> f32 {
+        let raw = self.analog.get(&axis).copied().unwrap_or(0.0);
+        let analog = self.axis_settings.apply(raw);
+        if analog != 0.0 {
+            return analog;
         }
-        if self.is_pressed(Action::Duck) {
-            vertical -= 1.0;
+
+        let (negative, positive) = match axis {
+            InputAxis::Horizontal => (Action::MoveLeft, Action::MoveRight),
+            InputAxis::Vertical => (Action::Duck, Action::Jump),
+        };
+        let mut value = 0.0;
+        if self.is_pressed(negative) {
+            value -= 1.0;
         }
-        if self.is_pressed(Action::Jump) {
-            vertical += 1.0;
+        if self.is_pressed(positive) {
+            value += 1.0;
         }
+        value
+    }
 
-        (horizontal, vertical)
+    /// Get directional input (-1.0 to 1.0 on each axis, analog when a stick is used)
+    /// Returns (horizontal, vertical)
+    pub fn get_direction(&self) -> (f32, f32) {
+        (
+            self.axis(InputAxis::Horizontal),
+            self.axis(InputAxis::Vertical),
+        )
     }
 }
 
@@ -275,6 +316,25 @@ mod tests {
         assert_eq!(v, -1.0);
     }
 
+    #[test]
+    fn test_analog_axis() {
+        let mut input = PlayerInput::new(0);
+        input.set_axis_settings(AxisSettings::default().with_deadzone(0.2, 1.0));
+
+        // Inside the deadzone the keys still drive the axis
+        input.set_analog(InputAxis::Horizontal, 0.1);
+        input.press(Action::MoveLeft);
+        assert_eq!(input.axis(InputAxis::Horizontal), -1.0);
+
+        // Outside it the stick wins, scaled past the deadzone
+        input.set_analog(InputAxis::Horizontal, 0.6);
+        assert!((input.axis(InputAxis::Horizontal) - 0.5).abs() < 1e-6);
+        assert_eq!(input.axis(InputAxis::Vertical), 0.0);
+
+        input.clear_analog();
+        assert_eq!(input.get_direction(), (-1.0, 0.0));
+    }
+
     #[test]
     fn test_multiple_presses_same_action() {
         let mut input = PlayerInput::new(0);
@@ -0,0 +1,251 @@
+// Rebinding: capture the next input for a player's action
+//
+// A controls menu calls `InputManager::start_rebind`. The next key, mouse
+// button or gamepad button/stick press is then captured instead of being
+// dispatched (Escape cancels). If nobody else uses the input it is bound right
+// away; otherwise the capture waits for the menu to pick a `RebindChoice`.
+//
+// Rebinding replaces the action's sources of the same kind: a key replaces the
+// keyboard/mouse bindings and keeps the gamepad ones, and the other way round.
+// Keyboard and mouse are shared, so they conflict across players; gamepad
+// inputs only conflict within a player, since every player has their own pad.
+
+use super::action::{Action, InputSource};
+use super::config::{InputConfig, InputConfigManager};
+
+/// A binding that already uses a captured input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BindingConflict {
+    /// Player whose binding it is (None = a global binding)
+    pub player_id: Option<usize>,
+    pub action: Action,
+}
+
+/// An input captured for an action that others already use
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebindCapture {
+    pub player_id: usize,
+    pub action: Action,
+    pub source: InputSource,
+    pub conflicts: Vec<BindingConflict>,
+}
+
+/// How to settle a conflicting capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebindChoice {
+    /// Conflicting bindings get the action's old input in exchange
+    Swap,
+    /// Conflicting bindings lose the input
+    Replace,
+    /// Keep everything as it was
+    Cancel,
+}
+
+/// Where rebinding stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebindState {
+    Idle,
+    /// Waiting for the next input
+    Listening {
+        player_id: usize,
+        action: Action,
+    },
+    /// Waiting for the menu to settle a conflict (see `resolve_rebind`)
+    Conflict(RebindCapture),
+}
+
+/// What a rebind ended with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebindOutcome {
+    Bound {
+        player_id: usize,
+        action: Action,
+        source: InputSource,
+        /// Conflicting bindings and the input each was given (None = left unbound)
+        displaced: Vec<(BindingConflict, Option<InputSource>)>,
+    },
+    /// The capture clashes with other bindings; pick a `RebindChoice`
+    Conflict(RebindCapture),
+    Cancelled,
+}
+
+/// Check if two sources are the same kind (keyboard/mouse or gamepad)
+fn same_kind(a: InputSource, b: InputSource) -> bool {
+    a.is_gamepad() == b.is_gamepad()
+}
+
+/// Find the bindings other than `player_id`'s `action` that use `source`
+pub fn find_conflicts(
+    configs: &InputConfigManager,
+    player_id: usize,
+    action: Action,
+    source: InputSource,
+) -> Vec<BindingConflict> {
+    let mut conflicts = Vec::new();
+    let players: Vec<usize> = if source.is_gamepad() {
+        vec![player_id]
+    } else {
+        (0..configs.num_players()).collect()
+    };
+    for other in players {
+        let bound = configs
+            .get_config(other)
+            .and_then(|config| config.get_action(source));
+        if let Some(bound) = bound.filter(|&bound| other != player_id || bound != action) {
+            conflicts.push(BindingConflict {
+                player_id: Some(other),
+                action: bound,
+            });
+        }
+    }
+    if let Some(bound) = configs.global_config().get_action(source) {
+        conflicts.push(BindingConflict {
+            player_id: None,
+            action: bound,
+        });
+    }
+    conflicts
+}
+
+/// Bind a captured input, settling conflicts as chosen
+/// Returns what happened (never `RebindOutcome::Conflict`)
+pub fn apply_rebind(
+    configs: &mut InputConfigManager,
+    capture: &RebindCapture,
+    choice: RebindChoice,
+) -> RebindOutcome {
+    let source = capture.source;
+    let Some(config) = configs.get_config_mut(capture.player_id) else {
+        return RebindOutcome::Cancelled;
+    };
+    if choice == RebindChoice::Cancel {
+        return RebindOutcome::Cancelled;
+    }
+
+    // The action's current input of the same kind, handed over on a swap
+    let old_sources: Vec<InputSource> = config
+        .get_sources(capture.action)
+        .into_iter()
+        .filter(|&old| same_kind(old, source) && old != source)
+        .collect();
+    let handed_over = match choice {
+        RebindChoice::Swap => old_sources.first().copied(),
+        _ => None,
+    };
+
+    let mut displaced = Vec::new();
+    for &conflict in &capture.conflicts {
+        let config = config_for(configs, conflict.player_id);
+        if let Some(config) = config {
+            config.unbind_source(source);
+            if let Some(old) = handed_over {
+                config.bind(old, conflict.action);
+            }
+            displaced.push((conflict, handed_over));
+        }
+    }
+
+    if let Some(config) = configs.get_config_mut(capture.player_id) {
+        for old in old_sources {
+            // A swap may have just given this input to another of our actions
+            if config.get_action(old) == Some(capture.action) {
+                config.unbind_source(old);
+            }
+        }
+        config.bind(source, capture.action);
+    }
+
+    RebindOutcome::Bound {
+        player_id: capture.player_id,
+        action: capture.action,
+        source,
+        displaced,
+    }
+}
+
+fn config_for(
+    configs: &mut InputConfigManager,
+    player_id: Option<usize>,
+) -> Option<&mut InputConfig> {
+    match player_id {
+        Some(player_id) => configs.get_config_mut(player_id),
+        None => Some(configs.global_config_mut()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::input::gamepad::GamepadButton;
+    use winit::keyboard::KeyCode;
+
+    fn capture(
+        configs: &InputConfigManager,
+        player_id: usize,
+        action: Action,
+        source: InputSource,
+    ) -> RebindCapture {
+        RebindCapture {
+            player_id,
+            action,
+            source,
+            conflicts: find_conflicts(configs, player_id, action, source),
+        }
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let configs = InputConfigManager::new(4);
+        let arrow_up = InputSource::key(KeyCode::ArrowUp);
+        assert_eq!(
+            find_conflicts(&configs, 0, Action::Jump, arrow_up),
+            vec![BindingConflict {
+                player_id: Some(1),
+                action: Action::Jump
+            }]
+        );
+        // Rebinding an action to the input it already has is no conflict
+        assert!(find_conflicts(&configs, 1, Action::Jump, arrow_up).is_empty());
+        // Every player has their own pad, so only their own bindings clash
+        let south = InputSource::gamepad(GamepadButton::South);
+        assert_eq!(
+            find_conflicts(&configs, 2, Action::Ability1, south),
+            vec![BindingConflict {
+                player_id: Some(2),
+                action: Action::Jump
+            }]
+        );
+    }
+
+    #[test]
+    fn test_swap_and_replace() {
+        let w = InputSource::key(KeyCode::KeyW);
+        let s = InputSource::key(KeyCode::KeyS);
+        let south = InputSource::gamepad(GamepadButton::South);
+
+        // Swapping Jump onto S gives Duck the old W
+        let mut configs = InputConfigManager::new(4);
+        let swap = capture(&configs, 0, Action::Jump, s);
+        apply_rebind(&mut configs, &swap, RebindChoice::Swap);
+        let player = configs.get_config(0).unwrap();
+        assert_eq!(player.get_action(s), Some(Action::Jump));
+        assert_eq!(player.get_action(w), Some(Action::Duck));
+        assert_eq!(player.get_action(south), Some(Action::Jump), "Pad kept");
+
+        // Replacing leaves Duck without a key
+        let mut configs = InputConfigManager::new(4);
+        let replace = capture(&configs, 0, Action::Jump, s);
+        let outcome = apply_rebind(&mut configs, &replace, RebindChoice::Replace);
+        let player = configs.get_config(0).unwrap();
+        assert_eq!(player.get_action(s), Some(Action::Jump));
+        assert_eq!(player.get_action(w), None);
+        assert!(!player
+            .get_sources(Action::Duck)
+            .iter()
+            .any(|source| !source.is_gamepad()));
+        assert!(matches!(
+            outcome,
+            RebindOutcome::Bound { displaced, .. } if displaced.len() == 1
+        ));
+    }
+}
@@ -153,6 +153,8 @@ pub struct ColliderBuilder2D {
     density: Option<Real>,
     mass: Option<Real>,
     active_events: ActiveEvents,
+    contact_force_threshold: Real,
+    translation: Vector<Real>,
 }
 
 impl ColliderBuilder2D {
@@ -167,6 +169,8 @@ impl ColliderBuilder2D {
             density: Some(1.0),
             mass: None,
             active_events: ActiveEvents::COLLISION_EVENTS,
+            contact_force_threshold: 0.0,
+            translation: Vector::zeros(),
         }
     }
 
@@ -181,6 +185,8 @@ impl ColliderBuilder2D {
             density: Some(1.0),
             mass: None,
             active_events: ActiveEvents::COLLISION_EVENTS,
+            contact_force_threshold: 0.0,
+            translation: Vector::zeros(),
         }
     }
 
@@ -197,6 +203,8 @@ impl ColliderBuilder2D {
             density: Some(1.0),
             mass: None,
             active_events: ActiveEvents::COLLISION_EVENTS,
+            contact_force_threshold: 0.0,
+            translation: Vector::zeros(),
         }
     }
 
@@ -213,9 +221,36 @@ impl ColliderBuilder2D {
             density: Some(1.0),
             mass: None,
             active_events: ActiveEvents::COLLISION_EVENTS,
+            contact_force_threshold: 0.0,
+            translation: Vector::zeros(),
         })
     }
 
+    /// Create a collider from line segments (`indices` pairs up vertices;
+    /// None connects them in order)
+    pub fn polyline(points: &[[Real; 2]], indices: Option<Vec<[u32; 2]>>) -> Self {
+        let points: Vec<_> = points.iter().map(|p| point![p[0], p[1]]).collect();
+
+        Self {
+            shape: SharedShape::polyline(points, indices),
+            collision_groups: CollisionGroups::Default,
+            is_sensor: false,
+            friction: 0.5,
+            restitution: 0.0,
+            density: Some(1.0),
+            mass: None,
+            active_events: ActiveEvents::COLLISION_EVENTS,
+            contact_force_threshold: 0.0,
+            translation: Vector::zeros(),
+        }
+    }
+
+    /// Offset the collider from its parent body
+    pub fn translation(mut self, x: Real, y: Real) -> Self {
+        self.translation = vector![x, y];
+        self
+    }
+
     /// Set the collision groups for filtering
     pub fn collision_groups(mut self, groups: CollisionGroups) -> Self {
         self.collision_groups = groups;
@@ -260,6 +295,14 @@ impl ColliderBuilder2D {
         self
     }
 
+    /// Only report contact forces stronger than `threshold`
+    /// (implies `contact_force_events`)
+    pub fn contact_force_threshold(mut self, threshold: Real) -> Self {
+        self.active_events |= ActiveEvents::CONTACT_FORCE_EVENTS;
+        self.contact_force_threshold = threshold;
+        self
+    }
+
     /// Build the collider
     pub fn build(self) -> Collider {
         let mut builder = rapier2d::prelude::ColliderBuilder::new(self.shape)
@@ -267,7 +310,9 @@ impl ColliderBuilder2D {
             .sensor(self.is_sensor)
             .friction(self.friction)
             .restitution(self.restitution)
-            .active_events(self.active_events);
+            .active_events(self.active_events)
+            .contact_force_event_threshold(self.contact_force_threshold)
+            .translation(self.translation);
 
         // Set mass or density
         if let Some(mass) = self.mass {
@@ -321,6 +366,20 @@ pub mod presets {
             .build()
     }
 
+    /// Create a platform collider that reports impacts above `impact_threshold`
+    pub fn destructible_platform_collider(
+        width: Real,
+        height: Real,
+        impact_threshold: Real,
+    ) -> Collider {
+        ColliderBuilder2D::box_shape(width / 2.0, height / 2.0)
+            .collision_groups(CollisionGroups::Platform)
+            .friction(0.3)
+            .restitution(0.0)
+            .contact_force_threshold(impact_threshold)
+            .build()
+    }
+
     /// Create a projectile body (dynamic)
     pub fn projectile_body(x: Real, y: Real, vel_x: Real, vel_y: Real) -> RigidBody {
         BodyBuilder::new_dynamic()
@@ -341,6 +400,24 @@ pub mod presets {
             .build()
     }
 
+    /// Create a debris body (dynamic, tumbles freely)
+    pub fn debris_body(x: Real, y: Real, vel_x: Real, vel_y: Real, angvel: Real) -> RigidBody {
+        BodyBuilder::new_dynamic()
+            .position(x, y)
+            .linvel(vel_x, vel_y)
+            .angvel(angvel)
+            .build()
+    }
+
+    /// Create a debris collider (box shape, doesn't block players)
+    pub fn debris_collider(width: Real, height: Real) -> Collider {
+        ColliderBuilder2D::box_shape(width / 2.0, height / 2.0)
+            .collision_groups(CollisionGroups::Debris)
+            .friction(0.6)
+            .restitution(0.1)
+            .build()
+    }
+
     /// Create a sensor collider (detects but doesn't block)
     pub fn sensor_collider(width: Real, height: Real) -> Collider {
         ColliderBuilder2D::box_shape(width / 2.0, height / 2.0)
@@ -348,6 +425,23 @@ pub mod presets {
             .sensor(true)
             .build()
     }
+
+    /// Create a pickup collider (circle sensor that only detects players)
+    pub fn pickup_collider(radius: Real) -> Collider {
+        ColliderBuilder2D::circle(radius)
+            .collision_groups(CollisionGroups::Pickup)
+            .sensor(true)
+            .build()
+    }
+
+    /// Create a hazard volume collider (spikes, lava, saws)
+    /// Hazards are sensors that only detect players
+    pub fn hazard_collider(width: Real, height: Real) -> Collider {
+        ColliderBuilder2D::box_shape(width / 2.0, height / 2.0)
+            .collision_groups(CollisionGroups::Hazard)
+            .sensor(true)
+            .build()
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,13 @@
 use rapier2d::prelude::*;
 use std::sync::{Arc, Mutex};
 
+/// Most teams the physics filter can tell apart
+pub const MAX_TEAMS: usize = 8;
+
+/// Team membership bits sit above the group bits
+const TEAM_SHIFT: u32 = 16;
+const ALL_TEAM_BITS: u32 = ((1 << MAX_TEAMS) - 1) << TEAM_SHIFT;
+
 /// Collision groups for filtering what objects can collide with each other
 ///
 /// This is essential for game logic - we need different collision behaviors
@@ -30,6 +37,9 @@ pub enum CollisionGroups {
 
     /// Sensors (trigger zones, etc.) - don't cause physical collision
     Sensor = 0b1000_0000,
+
+    /// Debris from destroyed arena pieces (falls away without blocking players)
+    Debris = 0b1_0000_0000,
 }
 
 impl CollisionGroups {
@@ -39,10 +49,11 @@ impl CollisionGroups {
 
         // Define what each group can interact with
         let filter = match self {
-            // Players collide with platforms, hazards, pickups, and ability effects
-            // But not with other players (we'll handle that separately if needed)
+            // Players collide with platforms, projectiles, hazards, pickups, and ability effects
+            // But not with other players, unless enabled (see `with_player_collision`)
             CollisionGroups::Player => Group::from_bits_truncate(
                 CollisionGroups::Platform as u32
+                    | CollisionGroups::Projectile as u32
                     | CollisionGroups::Hazard as u32
                     | CollisionGroups::Pickup as u32
                     | CollisionGroups::AbilityEffect as u32
@@ -61,7 +72,8 @@ impl CollisionGroups {
                 CollisionGroups::Player as u32
                     | CollisionGroups::Projectile as u32
                     | CollisionGroups::Platform as u32
-                    | CollisionGroups::Hazard as u32,
+                    | CollisionGroups::Hazard as u32
+                    | CollisionGroups::Debris as u32,
             ),
 
             // Hazards collide with players only
@@ -78,6 +90,11 @@ impl CollisionGroups {
             // Sensors interact with everything but don't cause physical collision
             CollisionGroups::Sensor => Group::ALL,
 
+            // Debris only lands on platforms and piles up on other debris
+            CollisionGroups::Debris => Group::from_bits_truncate(
+                CollisionGroups::Platform as u32 | CollisionGroups::Debris as u32,
+            ),
+
             // Default interacts with everything
             CollisionGroups::Default => Group::ALL,
         };
@@ -85,6 +102,38 @@ impl CollisionGroups {
         InteractionGroups::new(memberships, filter)
     }
 
+    /// Convert to rapier2d's InteractionGroups for a collider owned by a team
+    /// Projectiles and ability effects pass through players of their own team
+    /// (and players without a team); everything else behaves as without a team
+    pub fn to_team_interaction_groups(self, team: usize) -> InteractionGroups {
+        let groups = self.to_interaction_groups();
+        let team_bit = Group::from_bits_truncate(1 << (TEAM_SHIFT + (team % MAX_TEAMS) as u32));
+        let memberships = groups.memberships | team_bit;
+
+        let filter = match self {
+            CollisionGroups::Projectile | CollisionGroups::AbilityEffect => {
+                let player = Group::from_bits_truncate(CollisionGroups::Player as u32);
+                let other_teams = Group::from_bits_truncate(ALL_TEAM_BITS) - team_bit;
+                (groups.filter - player) | other_teams
+            }
+            _ => groups.filter,
+        };
+
+        InteractionGroups::new(memberships, filter)
+    }
+
+    /// Add other players to (or remove them from) a player collider's filter
+    /// Players only push each other when both have it; teams are kept
+    pub fn with_player_collision(groups: InteractionGroups, solid: bool) -> InteractionGroups {
+        let player = Group::from_bits_truncate(CollisionGroups::Player as u32);
+        let filter = if solid {
+            groups.filter | player
+        } else {
+            groups.filter - player
+        };
+        InteractionGroups::new(groups.memberships, filter)
+    }
+
     /// Create a sensor version (no physical collision, just detection)
     pub fn as_sensor(self) -> InteractionGroups {
         let groups = self.to_interaction_groups();
@@ -107,6 +156,14 @@ pub enum CollisionEvent {
         collider1: ColliderHandle,
         collider2: ColliderHandle,
     },
+
+    /// Two colliders pushed against each other with a force above the
+    /// colliders' contact force threshold (heavy impacts)
+    ContactForce {
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+        force: Real,
+    },
 }
 
 /// Queue for storing collision events during physics step
@@ -180,10 +237,14 @@ impl EventHandler for CollisionEventQueue {
         _dt: Real,
         _bodies: &RigidBodySet,
         _colliders: &ColliderSet,
-        _contact_pair: &ContactPair,
-        _total_force_magnitude: Real,
+        contact_pair: &ContactPair,
+        total_force_magnitude: Real,
     ) {
-        // We don't need force events for now, but could add them later
+        self.push(CollisionEvent::ContactForce {
+            collider1: contact_pair.collider1,
+            collider2: contact_pair.collider2,
+            force: total_force_magnitude,
+        });
     }
 }
 
@@ -203,6 +264,7 @@ mod tests {
             CollisionGroups::Pickup,
             CollisionGroups::AbilityEffect,
             CollisionGroups::Sensor,
+            CollisionGroups::Debris,
         ];
 
         for (i, group1) in groups.iter().enumerate() {
@@ -230,6 +292,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_debris_doesnt_collide_with_player() {
+        let debris = CollisionGroups::Debris.to_interaction_groups();
+        let player = CollisionGroups::Player.to_interaction_groups();
+        let platform = CollisionGroups::Platform.to_interaction_groups();
+
+        assert!(!debris.test(player), "Debris should not block players");
+        assert!(debris.test(platform), "Debris should land on platforms");
+    }
+
+    #[test]
+    fn test_team_projectiles_pass_through_teammates() {
+        let red_player = CollisionGroups::Player.to_team_interaction_groups(0);
+        let blue_player = CollisionGroups::Player.to_team_interaction_groups(1);
+        let red_shot = CollisionGroups::Projectile.to_team_interaction_groups(0);
+        let platform = CollisionGroups::Platform.to_interaction_groups();
+
+        assert!(!red_shot.test(red_player), "Shots pass through teammates");
+        assert!(red_shot.test(blue_player), "Shots hit the other team");
+        assert!(red_shot.test(platform));
+        assert!(red_player.test(platform));
+        assert!(
+            !red_player.test(blue_player),
+            "Teams don't make players collide"
+        );
+    }
+
+    #[test]
+    fn test_solid_players_collide() {
+        let red = CollisionGroups::with_player_collision(
+            CollisionGroups::Player.to_team_interaction_groups(0),
+            true,
+        );
+        let blue = CollisionGroups::with_player_collision(
+            CollisionGroups::Player.to_team_interaction_groups(1),
+            true,
+        );
+        let ghost = CollisionGroups::Player.to_interaction_groups();
+
+        assert!(red.test(blue));
+        assert!(!red.test(ghost), "Both players must opt in");
+        assert!(!CollisionGroups::Projectile
+            .to_team_interaction_groups(0)
+            .test(red));
+        assert_eq!(
+            CollisionGroups::with_player_collision(red, false),
+            CollisionGroups::Player.to_team_interaction_groups(0)
+        );
+    }
+
     #[test]
     fn test_projectile_collides_with_player() {
         let projectile_groups = CollisionGroups::Projectile.to_interaction_groups();
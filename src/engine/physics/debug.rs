@@ -201,7 +201,11 @@ impl DebugRenderer {
         let identity = Isometry::identity();
         for (_handle, collider) in colliders.iter() {
             let parent_body = collider.parent().and_then(|h| rigid_bodies.get(h));
-            let body_pos = parent_body.map(|b| b.position()).unwrap_or(&identity);
+            let collider_pos = if parent_body.is_some() {
+                collider.position()
+            } else {
+                &identity
+            };
 
             // Get color based on body type
             let color = if let Some(body) = parent_body {
@@ -216,7 +220,7 @@ impl DebugRenderer {
             };
 
             // Draw collider shape
-            self.draw_collider_shape(collider, body_pos, color);
+            self.draw_collider_shape(collider, collider_pos, color);
         }
 
         // Update buffers if we have geometry
@@ -270,6 +274,11 @@ impl DebugRenderer {
                     self.draw_capsule(transform, capsule, color);
                 }
             }
+            ShapeType::Polyline => {
+                if let Some(polyline) = collider.shape().as_polyline() {
+                    self.draw_polyline(transform, polyline, color);
+                }
+            }
             _ => {
                 // For other shapes, draw a simple cross
                 self.draw_cross(transform, 0.5, color);
@@ -374,6 +383,24 @@ impl DebugRenderer {
         }
     }
 
+    /// Draw a polyline
+    fn draw_polyline(&mut self, transform: &Isometry<Real>, polyline: &Polyline, color: [f32; 4]) {
+        let start_idx = self.vertices.len() as u16;
+
+        for point in polyline.vertices() {
+            let world_point = transform * point;
+            self.vertices.push(DebugVertex {
+                position: [world_point.x, world_point.y],
+                color,
+            });
+        }
+
+        for [a, b] in polyline.indices() {
+            self.indices.push(start_idx + *a as u16);
+            self.indices.push(start_idx + *b as u16);
+        }
+    }
+
     /// Draw a cross (for unsupported shapes)
     fn draw_cross(&mut self, transform: &Isometry<Real>, size: Real, color: [f32; 4]) {
         let start_idx = self.vertices.len() as u16;
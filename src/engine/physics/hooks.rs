@@ -0,0 +1,171 @@
+use rapier2d::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Maximum angle between a contact normal and "up" for a one-way platform
+/// contact to be accepted (radians)
+const ONE_WAY_ALLOWED_ANGLE: Real = std::f32::consts::FRAC_PI_4;
+
+/// Physics hooks implementing one-way platforms, drop-through and conveyors
+///
+/// One-way platforms only block bodies landing on them from above. Bodies
+/// flagged as dropping through ignore one-way platforms entirely until the
+/// flag is cleared again. Conveyor surfaces drag whatever touches them along
+/// the surface, like a belt running clockwise at the given speed.
+#[derive(Debug, Default)]
+pub struct PlatformHooks {
+    /// Colliders that behave as one-way platforms
+    one_way_colliders: HashSet<ColliderHandle>,
+    /// Bodies currently dropping through one-way platforms
+    dropping_bodies: HashSet<RigidBodyHandle>,
+    /// Surface speed of conveyor colliders (positive moves the top surface right)
+    conveyor_speeds: HashMap<ColliderHandle, Real>,
+}
+
+impl PlatformHooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark or unmark a collider as a one-way platform
+    pub fn set_one_way(&mut self, collider: ColliderHandle, one_way: bool) {
+        if one_way {
+            self.one_way_colliders.insert(collider);
+        } else {
+            self.one_way_colliders.remove(&collider);
+        }
+    }
+
+    /// Check if a collider is a one-way platform
+    pub fn is_one_way(&self, collider: ColliderHandle) -> bool {
+        self.one_way_colliders.contains(&collider)
+    }
+
+    /// Start or stop a body dropping through one-way platforms
+    pub fn set_dropping(&mut self, body: RigidBodyHandle, dropping: bool) {
+        if dropping {
+            self.dropping_bodies.insert(body);
+        } else {
+            self.dropping_bodies.remove(&body);
+        }
+    }
+
+    /// Check if a body is dropping through one-way platforms
+    pub fn is_dropping(&self, body: RigidBodyHandle) -> bool {
+        self.dropping_bodies.contains(&body)
+    }
+
+    /// Forget a removed body
+    pub fn remove_body(&mut self, body: RigidBodyHandle) {
+        self.dropping_bodies.remove(&body);
+    }
+
+    /// Set a collider's conveyor speed (0.0 turns the conveyor off)
+    pub fn set_conveyor_speed(&mut self, collider: ColliderHandle, speed: Real) {
+        if speed == 0.0 {
+            self.conveyor_speeds.remove(&collider);
+        } else {
+            self.conveyor_speeds.insert(collider, speed);
+        }
+    }
+
+    /// Get a collider's conveyor speed (0.0 if it isn't a conveyor)
+    pub fn conveyor_speed(&self, collider: ColliderHandle) -> Real {
+        self.conveyor_speeds.get(&collider).copied().unwrap_or(0.0)
+    }
+
+    /// Forget a removed collider
+    pub fn remove_collider(&mut self, collider: ColliderHandle) {
+        self.one_way_colliders.remove(&collider);
+        self.conveyor_speeds.remove(&collider);
+    }
+
+    /// Check if `platform` is a one-way platform that `body` is dropping through
+    fn is_dropping_through(&self, platform: ColliderHandle, body: Option<RigidBodyHandle>) -> bool {
+        self.is_one_way(platform) && body.is_some_and(|b| self.is_dropping(b))
+    }
+}
+
+impl PhysicsHooks for PlatformHooks {
+    fn filter_contact_pair(&self, context: &PairFilterContext) -> Option<SolverFlags> {
+        // Keep rapier's default behavior of ignoring pairs without a dynamic body
+        let is_dynamic = |handle: Option<RigidBodyHandle>| {
+            handle
+                .and_then(|h| context.bodies.get(h))
+                .is_some_and(|body| body.is_dynamic())
+        };
+        if !is_dynamic(context.rigid_body1) && !is_dynamic(context.rigid_body2) {
+            return None;
+        }
+
+        // Bodies dropping through don't touch one-way platforms at all
+        if self.is_dropping_through(context.collider1, context.rigid_body2)
+            || self.is_dropping_through(context.collider2, context.rigid_body1)
+        {
+            return None;
+        }
+
+        Some(SolverFlags::COMPUTE_IMPULSES)
+    }
+
+    fn modify_solver_contacts(&self, context: &mut ContactModificationContext) {
+        // The allowed normal is expressed in collider1's local space and points
+        // out of collider1, so it flips depending on which side the platform is
+        let allowed_local_n1 = if self.is_one_way(context.collider1) {
+            Some(vector![0.0, 1.0])
+        } else if self.is_one_way(context.collider2) {
+            Some(vector![0.0, -1.0])
+        } else {
+            None
+        };
+        if let Some(allowed_local_n1) = allowed_local_n1 {
+            context.update_as_oneway_platform(&allowed_local_n1, ONE_WAY_ALLOWED_ANGLE);
+        }
+
+        // Conveyors: the surface moves clockwise around the collider, so its
+        // direction is the outward normal rotated a quarter turn clockwise.
+        // `normal` points out of collider1, and the tangent velocity is
+        // collider1's surface velocity relative to collider2's.
+        let normal = *context.normal;
+        let belt = |n: Vector<Real>| vector![n.y, -n.x];
+        let tangent_velocity = self.conveyor_speed(context.collider1) * belt(normal)
+            - self.conveyor_speed(context.collider2) * belt(-normal);
+        if tangent_velocity != Vector::zeros() {
+            for contact in context.solver_contacts.iter_mut() {
+                contact.tangent_velocity = tangent_velocity;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_way_registration() {
+        let mut colliders = ColliderSet::new();
+        let handle = colliders.insert(ColliderBuilder::cuboid(1.0, 1.0).build());
+
+        let mut hooks = PlatformHooks::new();
+        assert!(!hooks.is_one_way(handle));
+
+        hooks.set_one_way(handle, true);
+        assert!(hooks.is_one_way(handle));
+
+        hooks.remove_collider(handle);
+        assert!(!hooks.is_one_way(handle));
+    }
+
+    #[test]
+    fn test_dropping_registration() {
+        let mut bodies = RigidBodySet::new();
+        let handle = bodies.insert(RigidBodyBuilder::dynamic().build());
+
+        let mut hooks = PlatformHooks::new();
+        hooks.set_dropping(handle, true);
+        assert!(hooks.is_dropping(handle));
+
+        hooks.set_dropping(handle, false);
+        assert!(!hooks.is_dropping(handle));
+    }
+}
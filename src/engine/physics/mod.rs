@@ -3,6 +3,7 @@
 pub mod body;
 mod collision;
 mod debug;
+mod hooks;
 mod world;
 
 pub use body::RigidBodyHandle;
@@ -1,7 +1,10 @@
 use rapier2d::prelude::*;
 use std::collections::HashMap;
 
-use super::collision::{CollisionEvent as GameCollisionEvent, CollisionEventQueue};
+use super::collision::{
+    CollisionEvent as GameCollisionEvent, CollisionEventQueue, CollisionGroups,
+};
+use super::hooks::PlatformHooks;
 
 /// Handle to identify rigid bodies
 pub type RigidBodyHandle = rapier2d::prelude::RigidBodyHandle;
@@ -50,6 +53,9 @@ pub struct PhysicsWorld {
     /// Collision event handler
     collision_event_queue: CollisionEventQueue,
 
+    /// Physics hooks for one-way platforms
+    platform_hooks: PlatformHooks,
+
     /// User data mapping from handles to game entity IDs
     body_to_entity: HashMap<RigidBodyHandle, u64>,
 }
@@ -80,6 +86,7 @@ impl PhysicsWorld {
             rigid_body_set: RigidBodySet::new(),
             collider_set: ColliderSet::new(),
             collision_event_queue: CollisionEventQueue::new(),
+            platform_hooks: PlatformHooks::new(),
             body_to_entity: HashMap::new(),
         }
     }
@@ -105,7 +112,7 @@ impl PhysicsWorld {
             &mut self.multibody_joint_set,
             &mut self.ccd_solver,
             Some(&mut self.query_pipeline),
-            &(),
+            &self.platform_hooks,
             event_handler,
         );
     }
@@ -127,6 +134,13 @@ impl PhysicsWorld {
 
     /// Remove a rigid body and all its attached colliders
     pub fn remove_rigid_body(&mut self, handle: RigidBodyHandle) {
+        if let Some(body) = self.rigid_body_set.get(handle) {
+            for collider in body.colliders() {
+                self.platform_hooks.remove_collider(*collider);
+            }
+        }
+        self.platform_hooks.remove_body(handle);
+
         self.rigid_body_set.remove(
             handle,
             &mut self.island_manager,
@@ -140,6 +154,7 @@ impl PhysicsWorld {
 
     /// Remove a collider from the physics world
     pub fn remove_collider(&mut self, handle: ColliderHandle) {
+        self.platform_hooks.remove_collider(handle);
         self.collider_set.remove(
             handle,
             &mut self.island_manager,
@@ -168,6 +183,108 @@ impl PhysicsWorld {
         self.collider_set.get_mut(handle)
     }
 
+    /// Put a collider of a collision group on a team (None = no team)
+    /// See `CollisionGroups::to_team_interaction_groups` for what changes
+    pub fn set_collider_team(
+        &mut self,
+        handle: ColliderHandle,
+        group: CollisionGroups,
+        team: Option<usize>,
+    ) {
+        if let Some(collider) = self.collider_set.get_mut(handle) {
+            let groups = match team {
+                Some(team) => group.to_team_interaction_groups(team),
+                None => group.to_interaction_groups(),
+            };
+            collider.set_collision_groups(groups);
+        }
+    }
+
+    /// Let a player collider collide with other players (or pass through them again)
+    /// Keeps the collider's team; call after `set_collider_team`
+    pub fn set_player_collision(&mut self, handle: ColliderHandle, solid: bool) {
+        if let Some(collider) = self.collider_set.get_mut(handle) {
+            let groups = CollisionGroups::with_player_collision(collider.collision_groups(), solid);
+            collider.set_collision_groups(groups);
+        }
+    }
+
+    /// Turn a collider into a one-way platform (or back into a solid one)
+    ///
+    /// One-way platforms can be jumped through from below and landed on from above.
+    pub fn set_one_way_platform(&mut self, handle: ColliderHandle, one_way: bool) {
+        if self.collider_set.contains(handle) {
+            self.platform_hooks.set_one_way(handle, one_way);
+            self.update_active_hooks(handle);
+        }
+    }
+
+    /// Turn a collider's surface into a conveyor moving at `speed` (0.0 = off)
+    ///
+    /// The surface runs clockwise around the collider, so positive speeds move
+    /// anything standing on top to the right.
+    pub fn set_conveyor_speed(&mut self, handle: ColliderHandle, speed: Real) {
+        if self.collider_set.contains(handle) {
+            self.platform_hooks.set_conveyor_speed(handle, speed);
+            self.update_active_hooks(handle);
+        }
+    }
+
+    /// Get a collider's conveyor speed (0.0 if it isn't a conveyor)
+    pub fn conveyor_speed(&self, handle: ColliderHandle) -> Real {
+        self.platform_hooks.conveyor_speed(handle)
+    }
+
+    /// Enable exactly the physics hooks a collider's surface features need
+    fn update_active_hooks(&mut self, handle: ColliderHandle) {
+        let Some(collider) = self.collider_set.get_mut(handle) else {
+            return;
+        };
+
+        let one_way = self.platform_hooks.is_one_way(handle);
+        let conveyor = self.platform_hooks.conveyor_speed(handle) != 0.0;
+        let mut hooks = collider.active_hooks()
+            - (ActiveHooks::FILTER_CONTACT_PAIRS | ActiveHooks::MODIFY_SOLVER_CONTACTS);
+        if one_way {
+            hooks |= ActiveHooks::FILTER_CONTACT_PAIRS;
+        }
+        if one_way || conveyor {
+            hooks |= ActiveHooks::MODIFY_SOLVER_CONTACTS;
+        }
+        collider.set_active_hooks(hooks);
+    }
+
+    /// Check if a collider is a one-way platform
+    pub fn is_one_way_platform(&self, handle: ColliderHandle) -> bool {
+        self.platform_hooks.is_one_way(handle)
+    }
+
+    /// Let a body fall through one-way platforms (or stop doing so)
+    pub fn set_drop_through(&mut self, handle: RigidBodyHandle, dropping: bool) {
+        if self.platform_hooks.is_dropping(handle) == dropping {
+            return;
+        }
+        self.platform_hooks.set_dropping(handle, dropping);
+
+        // Contacts of resting bodies are cached across steps, so flag the body's
+        // colliders as changed to make the narrow phase re-run the pair filter
+        let Some(body) = self.rigid_body_set.get(handle) else {
+            return;
+        };
+        for collider_handle in body.colliders() {
+            if let Some(collider) = self.collider_set.get_mut(*collider_handle) {
+                let groups = collider.collision_groups();
+                collider.set_collision_groups(InteractionGroups::none());
+                collider.set_collision_groups(groups);
+            }
+        }
+    }
+
+    /// Check if a body is currently dropping through one-way platforms
+    pub fn is_dropping_through(&self, handle: RigidBodyHandle) -> bool {
+        self.platform_hooks.is_dropping(handle)
+    }
+
     /// Associate a game entity ID with a rigid body
     pub fn set_entity_mapping(&mut self, body_handle: RigidBodyHandle, entity_id: u64) {
         self.body_to_entity.insert(body_handle, entity_id);
@@ -198,6 +315,70 @@ impl PhysicsWorld {
         )
     }
 
+    /// Get the world positions of all colliders that belong to a collision group
+    pub fn collider_positions_in_group(&self, group: CollisionGroups) -> Vec<Vector<Real>> {
+        let bit = Group::from_bits_truncate(group as u32);
+        self.collider_set
+            .iter()
+            .filter(|(_, collider)| collider.collision_groups().memberships.contains(bit))
+            .map(|(_, collider)| collider.translation())
+            .copied()
+            .collect()
+    }
+
+    /// Remove every rigid body with a collider in a collision group
+    /// Returns the number of bodies removed
+    pub fn remove_bodies_in_group(&mut self, group: CollisionGroups) -> usize {
+        let bit = Group::from_bits_truncate(group as u32);
+        let mut bodies: Vec<RigidBodyHandle> = self
+            .collider_set
+            .iter()
+            .filter(|(_, collider)| collider.collision_groups().memberships.contains(bit))
+            .filter_map(|(_, collider)| collider.parent())
+            .collect();
+        bodies.sort_unstable_by_key(|handle| handle.into_raw_parts());
+        bodies.dedup();
+
+        for &handle in &bodies {
+            self.remove_rigid_body(handle);
+        }
+        bodies.len()
+    }
+
+    /// Get all colliders currently overlapping a sensor collider
+    pub fn intersecting_colliders(&self, handle: ColliderHandle) -> Vec<ColliderHandle> {
+        self.narrow_phase
+            .intersection_pairs_with(handle)
+            .filter(|(_, _, intersecting)| *intersecting)
+            .map(|(a, b, _)| if a == handle { b } else { a })
+            .collect()
+    }
+
+    /// Get the outward normal of a collider's surface at the point closest to `point`
+    /// Points inside the collider get the normal of the nearest face
+    pub fn surface_normal(
+        &self,
+        handle: ColliderHandle,
+        point: Vector<Real>,
+    ) -> Option<Vector<Real>> {
+        let collider = self.collider_set.get(handle)?;
+        let point = point![point.x, point.y];
+        let projection = collider
+            .shape()
+            .project_point(collider.position(), &point, false);
+
+        let offset = point - projection.point;
+        let normal = if projection.is_inside {
+            -offset
+        } else {
+            offset
+        };
+        normal
+            .try_normalize(1.0e-6)
+            // Exactly on the surface: push away from the collider's center instead
+            .or_else(|| (point.coords - collider.translation()).try_normalize(1.0e-6))
+    }
+
     // Note: Shape casting will be added in a future version
     // The API changed in rapier 0.19 and needs further investigation
 
@@ -0,0 +1,222 @@
+// Parallax background layers
+//
+// Layers are drawn behind everything else, back to front. Each layer moves
+// and scales with the camera according to its parallax factor:
+// - 0.0 is fixed to the screen (infinitely far away)
+// - 1.0 moves and zooms exactly like the world
+// Values in between move and zoom proportionally less than the world.
+
+use super::camera::Camera;
+use super::sprite::Sprite;
+use super::texture::TextureHandle;
+use glam::{Vec2, Vec4};
+
+/// Zoom the background art is authored for (matches the renderer's default)
+pub const DEFAULT_REFERENCE_ZOOM: f32 = 50.0;
+
+/// Most quads a single layer may produce per frame
+const MAX_LAYER_QUADS: usize = 512;
+
+/// A single background layer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackgroundLayer {
+    /// Image to draw
+    pub texture: TextureHandle,
+    /// How much the layer follows the camera (0.0 = fixed, 1.0 = like the world)
+    pub parallax: f32,
+    /// Size of one copy of the image in world units at the reference zoom
+    pub size: Vec2,
+    /// Position of the first copy's center in layer space
+    pub offset: Vec2,
+    /// Repeat the image horizontally / vertically
+    pub repeat_x: bool,
+    pub repeat_y: bool,
+    /// Color multiplied with the image
+    pub tint: Vec4,
+}
+
+impl BackgroundLayer {
+    /// Create a layer that repeats horizontally
+    pub fn new(texture: TextureHandle, parallax: f32, size: Vec2) -> Self {
+        Self {
+            texture,
+            parallax,
+            size,
+            offset: Vec2::ZERO,
+            repeat_x: true,
+            repeat_y: false,
+            tint: Vec4::ONE,
+        }
+    }
+
+    /// Set the position of the first copy
+    pub fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Set which axes the image repeats along
+    pub fn with_repeat(mut self, repeat_x: bool, repeat_y: bool) -> Self {
+        self.repeat_x = repeat_x;
+        self.repeat_y = repeat_y;
+        self
+    }
+
+    /// Set the tint color
+    pub fn with_tint(mut self, tint: Vec4) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// How big one layer-space unit appears in world units
+    fn scale(&self, camera: &Camera, reference_zoom: f32) -> f32 {
+        (reference_zoom / camera.zoom).powf(1.0 - self.parallax.clamp(0.0, 1.0))
+    }
+
+    /// Convert a layer-space point to where it appears in the world
+    pub fn world_position(&self, point: Vec2, camera: &Camera, reference_zoom: f32) -> Vec2 {
+        let scale = self.scale(camera, reference_zoom);
+        camera.position + (point - camera.position * self.parallax) * scale
+    }
+
+    /// Build the quads that cover the camera's view for this frame
+    pub fn quads(&self, camera: &Camera, reference_zoom: f32) -> Vec<Sprite> {
+        if self.size.x <= 0.0 || self.size.y <= 0.0 {
+            return Vec::new();
+        }
+
+        // Visible area, converted into layer space
+        let scale = self.scale(camera, reference_zoom);
+        let view = camera.viewport_bounds();
+        let half_view = (view.max - view.min) / (2.0 * scale);
+        let layer_center = camera.position * self.parallax;
+        let min = layer_center - half_view;
+        let max = layer_center + half_view;
+
+        let range = |repeat: bool, min: f32, max: f32, offset: f32, size: f32| {
+            if repeat {
+                let first = ((min - offset) / size + 0.5).floor() as i32;
+                let last = ((max - offset) / size + 0.5).ceil() as i32;
+                first..last
+            } else {
+                0..1
+            }
+        };
+        let xs = range(self.repeat_x, min.x, max.x, self.offset.x, self.size.x);
+        let ys = range(self.repeat_y, min.y, max.y, self.offset.y, self.size.y);
+
+        let mut quads = Vec::new();
+        for y in ys {
+            for x in xs.clone() {
+                if quads.len() >= MAX_LAYER_QUADS {
+                    return quads;
+                }
+                let center = self.offset + Vec2::new(x as f32, y as f32) * self.size;
+                let sprite = Sprite::new(
+                    self.world_position(center, camera, reference_zoom),
+                    self.size * scale,
+                )
+                .with_color(self.tint);
+                quads.push(sprite);
+            }
+        }
+        quads
+    }
+}
+
+/// All background layers of an arena, plus the clear color behind them
+#[derive(Debug, Clone, PartialEq)]
+pub struct Background {
+    /// Color the frame is cleared to before drawing layers
+    pub clear_color: Vec4,
+    /// Layers, back to front
+    pub layers: Vec<BackgroundLayer>,
+    /// Zoom the layer sizes are authored for
+    pub reference_zoom: f32,
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Self {
+            clear_color: Vec4::new(0.1, 0.1, 0.15, 1.0),
+            layers: Vec::new(),
+            reference_zoom: DEFAULT_REFERENCE_ZOOM,
+        }
+    }
+}
+
+impl Background {
+    /// Add a layer in front of the existing ones
+    pub fn with_layer(mut self, layer: BackgroundLayer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera(position: Vec2, zoom: f32) -> Camera {
+        let mut camera = Camera::new(position, 1280.0, 720.0);
+        camera.set_zoom(zoom);
+        camera
+    }
+
+    fn layer(parallax: f32) -> BackgroundLayer {
+        BackgroundLayer::new(TextureHandle(0), parallax, Vec2::new(10.0, 5.0))
+    }
+
+    #[test]
+    fn test_parallax_movement() {
+        let start = camera(Vec2::ZERO, 50.0);
+        let moved = camera(Vec2::new(10.0, 0.0), 50.0);
+        let point = Vec2::new(2.0, 1.0);
+
+        // World layers stay put, screen layers follow the camera,
+        // half-parallax layers move half as far
+        let world = layer(1.0);
+        assert_eq!(world.world_position(point, &moved, 50.0), point);
+        let fixed = layer(0.0);
+        assert_eq!(
+            fixed.world_position(point, &moved, 50.0) - fixed.world_position(point, &start, 50.0),
+            Vec2::new(10.0, 0.0)
+        );
+        let half = layer(0.5);
+        assert_eq!(
+            half.world_position(point, &moved, 50.0) - half.world_position(point, &start, 50.0),
+            Vec2::new(5.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_fixed_layer_ignores_zoom() {
+        let fixed = layer(0.0);
+        let near = fixed.quads(&camera(Vec2::ZERO, 100.0), 50.0);
+        let far = fixed.quads(&camera(Vec2::ZERO, 25.0), 50.0);
+
+        // Same size on screen: world size times zoom is constant
+        assert_eq!(near[0].size * 100.0, far[0].size * 25.0);
+    }
+
+    #[test]
+    fn test_tiles_cover_view() {
+        let camera = camera(Vec2::new(37.0, 0.0), 50.0);
+        let view = camera.viewport_bounds();
+        let quads = layer(0.3).quads(&camera, 50.0);
+
+        let left = quads
+            .iter()
+            .map(|q| q.position.x - q.size.x / 2.0)
+            .fold(f32::MAX, f32::min);
+        let right = quads
+            .iter()
+            .map(|q| q.position.x + q.size.x / 2.0)
+            .fold(f32::MIN, f32::max);
+        assert!(left <= view.min.x && right >= view.max.x);
+
+        // Without repeating there is a single copy
+        let single = layer(0.3).with_repeat(false, false).quads(&camera, 50.0);
+        assert_eq!(single.len(), 1);
+    }
+}
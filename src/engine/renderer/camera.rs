@@ -16,6 +16,8 @@ pub struct Camera {
     viewport_height: f32,
     /// View-projection matrix
     view_proj: Mat4,
+    /// World area the camera is allowed to show (None = unbounded)
+    bounds: Option<Viewport>,
 }
 
 impl Camera {
@@ -27,6 +29,7 @@ impl Camera {
             viewport_width,
             viewport_height,
             view_proj: Mat4::IDENTITY,
+            bounds: None,
         };
         camera.update_view_proj();
         camera
@@ -34,6 +37,8 @@ impl Camera {
 
     /// Update the view-projection matrix
     fn update_view_proj(&mut self) {
+        self.clamp_to_bounds();
+
         // Calculate orthographic projection bounds based on zoom
         let half_width = (self.viewport_width / 2.0) / self.zoom;
         let half_height = (self.viewport_height / 2.0) / self.zoom;
@@ -51,6 +56,41 @@ impl Camera {
         self.view_proj = projection;
     }
 
+    /// Keep the visible area inside the bounds
+    /// If the view is larger than the bounds on an axis, it is centered instead
+    fn clamp_to_bounds(&mut self) {
+        let Some(bounds) = self.bounds else {
+            return;
+        };
+
+        let half_size = Vec2::new(self.viewport_width, self.viewport_height) / (2.0 * self.zoom);
+        let min = bounds.min + half_size;
+        let max = bounds.max - half_size;
+        let center = (bounds.min + bounds.max) / 2.0;
+
+        self.position.x = if min.x <= max.x {
+            self.position.x.clamp(min.x, max.x)
+        } else {
+            center.x
+        };
+        self.position.y = if min.y <= max.y {
+            self.position.y.clamp(min.y, max.y)
+        } else {
+            center.y
+        };
+    }
+
+    /// Limit the world area the camera may show
+    pub fn set_bounds(&mut self, bounds: Option<Viewport>) {
+        self.bounds = bounds;
+        self.update_view_proj();
+    }
+
+    /// Get the world area the camera may show
+    pub fn bounds(&self) -> Option<Viewport> {
+        self.bounds
+    }
+
     /// Set camera position
     pub fn set_position(&mut self, position: Vec2) {
         self.position = position;
@@ -158,3 +198,38 @@ impl CameraUniform {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camera_clamped_to_bounds() {
+        // 20x10 world units visible
+        let mut camera = Camera::new(Vec2::ZERO, 200.0, 100.0);
+        camera.set_zoom(10.0);
+        camera.set_bounds(Some(Viewport {
+            min: Vec2::new(-20.0, -5.0),
+            max: Vec2::new(20.0, 50.0),
+        }));
+
+        camera.set_position(Vec2::new(100.0, -100.0));
+        assert_eq!(camera.position, Vec2::new(10.0, 0.0));
+
+        let view = camera.viewport_bounds();
+        assert_eq!(view.max.x, 20.0);
+        assert_eq!(view.min.y, -5.0);
+    }
+
+    #[test]
+    fn test_camera_centered_in_small_bounds() {
+        let mut camera = Camera::new(Vec2::ZERO, 200.0, 100.0);
+        camera.set_zoom(10.0);
+        camera.set_bounds(Some(Viewport {
+            min: Vec2::new(0.0, 0.0),
+            max: Vec2::new(4.0, 4.0),
+        }));
+
+        assert_eq!(camera.position, Vec2::new(2.0, 2.0));
+    }
+}
@@ -1,10 +1,12 @@
 // Rendering system using wgpu
 
+mod background;
 mod camera;
 mod sprite;
 pub mod texture;
 mod vertex;
 
+pub use background::{Background, BackgroundLayer};
 pub use camera::{Camera, CameraUniform};
 pub use sprite::{Sprite, SpriteRenderer, SpriteUV};
 pub use texture::{TextureHandle, TextureManager};
@@ -34,6 +36,7 @@ pub struct Renderer {
     sprite_renderer: SpriteRenderer,
     texture_manager: TextureManager,
     camera: Camera,
+    background: Background,
     physics_debug_renderer: PhysicsDebugRenderer,
 }
 
@@ -128,6 +131,7 @@ impl Renderer {
             sprite_renderer,
             texture_manager,
             camera,
+            background: Background::default(),
             physics_debug_renderer,
         })
     }
@@ -172,9 +176,17 @@ impl Renderer {
             self.camera.view_proj_matrix().to_cols_array_2d(),
         );
 
+        // Queue background layers for the final camera position
+        for layer in &self.background.layers {
+            let quads = layer.quads(&self.camera, self.background.reference_zoom);
+            self.sprite_renderer
+                .add_background_batch(layer.texture, &quads);
+        }
+
         // Prepare sprites (upload vertex data)
         self.sprite_renderer.prepare(&self.queue);
 
+        let clear_color = self.background.clear_color;
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Main Render Pass"),
@@ -183,10 +195,10 @@ impl Renderer {
                     resolve_target: None,
                     ops: wgpu::Operations {
                         load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.1,
-                            b: 0.15,
-                            a: 1.0,
+                            r: clear_color.x as f64,
+                            g: clear_color.y as f64,
+                            b: clear_color.z as f64,
+                            a: clear_color.w as f64,
                         }),
                         store: wgpu::StoreOp::Store,
                     },
@@ -251,6 +263,11 @@ impl Renderer {
         &mut self.camera
     }
 
+    /// Replace the background (clear color and parallax layers)
+    pub fn set_background(&mut self, background: Background) {
+        self.background = background;
+    }
+
     /// Get a mutable reference to the physics debug renderer
     pub fn physics_debug_renderer_mut(&mut self) -> &mut PhysicsDebugRenderer {
         &mut self.physics_debug_renderer
@@ -266,6 +283,17 @@ impl Renderer {
         self.sprite_renderer.add_sprite(sprite);
     }
 
+    /// Add a batch of tiles sharing one texture to render this frame
+    pub fn add_tile_batch(&mut self, texture: TextureHandle, tiles: &[Sprite]) {
+        self.sprite_renderer.add_tile_batch(texture, tiles);
+    }
+
+    /// Create a 1x1 solid color texture
+    pub fn create_color_texture(&mut self, color: [u8; 4], label: &str) -> Result<TextureHandle> {
+        self.texture_manager
+            .create_color_texture(&self.device, &self.queue, color, label)
+    }
+
     /// Load a texture from file path
     pub fn load_texture(&mut self, path: &std::path::Path) -> Result<TextureHandle> {
         self.texture_manager
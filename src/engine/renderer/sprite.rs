@@ -191,9 +191,21 @@ impl Sprite {
 
 /// Maximum number of sprites that can be batched in a single draw call
 const MAX_SPRITES: usize = 1000;
+/// Maximum number of tile quads across all tile batches in a frame
+const MAX_TILES: usize = 4096;
 const VERTICES_PER_SPRITE: usize = 4;
 const INDICES_PER_SPRITE: usize = 6;
 
+/// A run of tile quads that share one texture and are drawn in a single call
+#[derive(Debug, Clone, Copy)]
+struct TileBatch {
+    texture: super::TextureHandle,
+    /// Index of the first quad in the tile vertex buffer
+    start: usize,
+    /// Number of quads in the batch
+    count: usize,
+}
+
 /// Sprite renderer with batching support
 pub struct SpriteRenderer {
     render_pipeline: wgpu::RenderPipeline,
@@ -205,6 +217,12 @@ pub struct SpriteRenderer {
     sprites: Vec<Sprite>,
     // For dynamic vertex updates
     vertex_data: Vec<Vertex>,
+    // Tile batches (drawn before sprites, one draw call per batch)
+    tile_vertex_buffer: wgpu::Buffer,
+    tile_batches: Vec<TileBatch>,
+    // Background batches share the tile buffer but are drawn before any tiles
+    background_batches: Vec<TileBatch>,
+    tile_vertex_data: Vec<Vertex>,
 }
 
 impl SpriteRenderer {
@@ -309,9 +327,18 @@ impl SpriteRenderer {
             mapped_at_creation: false,
         });
 
-        // Create index buffer with pre-computed indices for all sprites
-        let mut indices = Vec::with_capacity(MAX_SPRITES * INDICES_PER_SPRITE);
-        for i in 0..MAX_SPRITES {
+        // Create dynamic vertex buffer for tile batches
+        let tile_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Tile Vertex Buffer"),
+            size: (MAX_TILES * VERTICES_PER_SPRITE * std::mem::size_of::<Vertex>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Create index buffer with pre-computed indices for all sprites and tiles
+        let max_quads = MAX_SPRITES.max(MAX_TILES);
+        let mut indices = Vec::with_capacity(max_quads * INDICES_PER_SPRITE);
+        for i in 0..max_quads {
             let base = (i * VERTICES_PER_SPRITE) as u16;
             indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
         }
@@ -351,6 +378,10 @@ impl SpriteRenderer {
             texture_bind_group_layout,
             sprites: Vec::new(),
             vertex_data: Vec::with_capacity(MAX_SPRITES * VERTICES_PER_SPRITE),
+            tile_vertex_buffer,
+            tile_batches: Vec::new(),
+            background_batches: Vec::new(),
+            tile_vertex_data: Vec::with_capacity(MAX_TILES * VERTICES_PER_SPRITE),
         })
     }
 
@@ -361,10 +392,64 @@ impl SpriteRenderer {
         }
     }
 
-    /// Clear all sprites (call at end of frame)
+    /// Add tiles that share one texture (e.g. a tilemap atlas) to render this frame
+    /// All tiles in the batch are drawn with a single draw call, before sprites
+    pub fn add_tile_batch(&mut self, texture: super::TextureHandle, tiles: &[Sprite]) {
+        Self::push_batch(
+            &mut self.tile_batches,
+            &mut self.tile_vertex_data,
+            texture,
+            tiles,
+        );
+    }
+
+    /// Add background quads that share one texture to render this frame
+    /// Background batches are drawn before all tiles and sprites, in call order
+    pub fn add_background_batch(&mut self, texture: super::TextureHandle, quads: &[Sprite]) {
+        Self::push_batch(
+            &mut self.background_batches,
+            &mut self.tile_vertex_data,
+            texture,
+            quads,
+        );
+    }
+
+    fn push_batch(
+        batches: &mut Vec<TileBatch>,
+        vertex_data: &mut Vec<Vertex>,
+        texture: super::TextureHandle,
+        tiles: &[Sprite],
+    ) {
+        let start = vertex_data.len() / VERTICES_PER_SPRITE;
+        let count = tiles.len().min(MAX_TILES - start);
+        if count == 0 {
+            return;
+        }
+
+        for tile in &tiles[..count] {
+            vertex_data.extend_from_slice(&tile.vertices());
+        }
+
+        // Extend the previous batch if it uses the same texture
+        match batches.last_mut() {
+            Some(last) if last.texture == texture && last.start + last.count == start => {
+                last.count += count
+            }
+            _ => batches.push(TileBatch {
+                texture,
+                start,
+                count,
+            }),
+        }
+    }
+
+    /// Clear all sprites and tiles (call at end of frame)
     pub fn clear(&mut self) {
         self.sprites.clear();
         self.vertex_data.clear();
+        self.tile_batches.clear();
+        self.background_batches.clear();
+        self.tile_vertex_data.clear();
     }
 
     /// Get the texture bind group layout (for creating texture bind groups)
@@ -388,6 +473,14 @@ impl SpriteRenderer {
                 bytemuck::cast_slice(&self.vertex_data),
             );
         }
+
+        if !self.tile_vertex_data.is_empty() {
+            queue.write_buffer(
+                &self.tile_vertex_buffer,
+                0,
+                bytemuck::cast_slice(&self.tile_vertex_data),
+            );
+        }
     }
 
     /// Render all sprites
@@ -397,7 +490,10 @@ impl SpriteRenderer {
         _camera: &Camera,
         texture_manager: &'a TextureManager,
     ) -> Result<()> {
-        if self.sprites.is_empty() {
+        if self.sprites.is_empty()
+            && self.tile_batches.is_empty()
+            && self.background_batches.is_empty()
+        {
             return Ok(());
         }
 
@@ -405,10 +501,28 @@ impl SpriteRenderer {
         // log::info!("Rendering {} sprites", self.sprites.len());
 
         render_pass.set_pipeline(&self.render_pipeline);
-        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
         render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
         render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
 
+        // Draw the background, then tiles, so sprites appear on top
+        if !self.tile_vertex_data.is_empty() {
+            render_pass.set_vertex_buffer(0, self.tile_vertex_buffer.slice(..));
+
+            for batch in self.background_batches.iter().chain(&self.tile_batches) {
+                let Some(bind_group) = texture_manager.get_bind_group(batch.texture) else {
+                    log::warn!("No bind group for tile texture {:?}", batch.texture);
+                    continue;
+                };
+                render_pass.set_bind_group(1, bind_group, &[]);
+
+                let index_start = (batch.start * INDICES_PER_SPRITE) as u32;
+                let index_count = (batch.count * INDICES_PER_SPRITE) as u32;
+                render_pass.draw_indexed(index_start..(index_start + index_count), 0, 0..1);
+            }
+        }
+
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+
         // Draw sprites batched by texture
         // For now, simple approach: draw each sprite with its texture
         let mut current_sprite_index = 0;
@@ -452,6 +566,11 @@ impl SpriteRenderer {
         self.sprites.len()
     }
 
+    /// Get the number of tile quads queued for rendering
+    pub fn tile_count(&self) -> usize {
+        self.tile_vertex_data.len() / VERTICES_PER_SPRITE
+    }
+
     /// Get a reference to the camera buffer
     pub fn camera_buffer(&self) -> &wgpu::Buffer {
         &self.camera_buffer
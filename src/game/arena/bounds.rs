@@ -0,0 +1,78 @@
+// Arena bounds (blast zones)
+
+use glam::Vec2;
+
+/// An axis-aligned rectangle in world units
+///
+/// Used as the arena's blast zone: characters leaving it are knocked out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArenaBounds {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl ArenaBounds {
+    /// Create bounds from two corners
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        Self {
+            min: min.min(max),
+            max: min.max(max),
+        }
+    }
+
+    /// Create bounds from a center point and full size
+    pub fn from_center(center: Vec2, size: Vec2) -> Self {
+        Self::new(center - size / 2.0, center + size / 2.0)
+    }
+
+    /// Check if a point is inside the bounds
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    /// Get the center of the bounds
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Get the full size of the bounds
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    /// Interpolate between two bounds (t = 0.0 gives self, 1.0 gives other)
+    pub fn lerp(&self, other: &ArenaBounds, t: f32) -> ArenaBounds {
+        let t = t.clamp(0.0, 1.0);
+        ArenaBounds {
+            min: self.min.lerp(other.min, t),
+            max: self.max.lerp(other.max, t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds_contains() {
+        let bounds = ArenaBounds::new(Vec2::new(10.0, 5.0), Vec2::new(-10.0, -5.0));
+        assert_eq!(bounds.min, Vec2::new(-10.0, -5.0));
+        assert!(bounds.contains(Vec2::ZERO));
+        assert!(bounds.contains(Vec2::new(10.0, 5.0)));
+        assert!(!bounds.contains(Vec2::new(0.0, -6.0)));
+    }
+
+    #[test]
+    fn test_bounds_lerp() {
+        let outer = ArenaBounds::from_center(Vec2::ZERO, Vec2::new(40.0, 20.0));
+        let inner = ArenaBounds::from_center(Vec2::ZERO, Vec2::new(20.0, 10.0));
+
+        let half = outer.lerp(&inner, 0.5);
+        assert_eq!(half.size(), Vec2::new(30.0, 15.0));
+        assert_eq!(outer.lerp(&inner, 2.0), inner);
+    }
+}
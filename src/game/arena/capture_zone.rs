@@ -0,0 +1,172 @@
+// King-of-the-hill capture zone
+//
+// The zone is a sensor that hops between arena-defined positions on a timer
+// and tracks which players are standing inside it. Who scores is decided by
+// the match (see `MatchController::update_hill`), since that depends on teams.
+
+use super::bounds::ArenaBounds;
+use super::file::CaptureZoneDef;
+use crate::engine::physics::{
+    body::presets, ColliderHandle, PhysicsWorld, RigidBodyHandle, Vector,
+};
+use crate::game::characters::CharacterManager;
+use glam::Vec2;
+
+/// A capture zone moving between fixed positions
+#[derive(Debug)]
+pub struct CaptureZone {
+    size: Vec2,
+    positions: Vec<Vec2>,
+    /// Index of the position the zone is at
+    current: usize,
+    move_interval: f32,
+    /// Time until the zone moves (seconds)
+    move_timer: f32,
+    /// Players inside the zone as of the last update, in ascending order
+    occupants: Vec<usize>,
+    pub body_handle: RigidBodyHandle,
+    pub collider_handle: ColliderHandle,
+}
+
+impl CaptureZone {
+    /// Create the zone at the definition's first position
+    pub fn new(physics: &mut PhysicsWorld, def: &CaptureZoneDef) -> Self {
+        let positions: Vec<Vec2> = if def.positions.is_empty() {
+            vec![Vec2::ZERO]
+        } else {
+            def.positions.iter().map(|&p| Vec2::from(p)).collect()
+        };
+        let size = Vec2::from(def.size);
+        let body_handle =
+            physics.add_rigid_body(presets::platform_body(positions[0].x, positions[0].y));
+        let collider_handle =
+            physics.add_collider(presets::sensor_collider(size.x, size.y), body_handle);
+
+        Self {
+            size,
+            positions,
+            current: 0,
+            move_interval: def.move_interval.max(0.1),
+            move_timer: def.move_interval.max(0.1),
+            occupants: Vec::new(),
+            body_handle,
+            collider_handle,
+        }
+    }
+
+    /// Get the zone's current center
+    pub fn position(&self) -> Vec2 {
+        self.positions[self.current]
+    }
+
+    /// Get the area currently covered by the zone
+    pub fn bounds(&self) -> ArenaBounds {
+        ArenaBounds::from_center(self.position(), self.size)
+    }
+
+    /// Get the players inside the zone, in ascending order
+    pub fn occupants(&self) -> &[usize] {
+        &self.occupants
+    }
+
+    /// Get the time until the zone moves (seconds)
+    pub fn time_until_move(&self) -> f32 {
+        self.move_timer
+    }
+
+    /// Move on a timer and refresh who is inside
+    /// Call after stepping physics so overlaps are up to date
+    /// Returns the new position if the zone moved this frame
+    pub fn update(
+        &mut self,
+        physics: &mut PhysicsWorld,
+        characters: &CharacterManager,
+        dt: f32,
+    ) -> Option<Vec2> {
+        self.move_timer -= dt;
+        if self.move_timer <= 0.0 && self.positions.len() > 1 {
+            self.move_timer += self.move_interval;
+            self.move_to(physics, (self.current + 1) % self.positions.len());
+            // Overlaps at the new position are only known after the next step
+            self.occupants.clear();
+            return Some(self.position());
+        }
+        self.move_timer = self.move_timer.max(0.0);
+
+        self.occupants = physics
+            .intersecting_colliders(self.collider_handle)
+            .into_iter()
+            .filter_map(|other| {
+                characters
+                    .all()
+                    .iter()
+                    .find(|c| c.collider_handle == other && c.is_alive())
+                    .and_then(|c| c.player_index)
+            })
+            .collect();
+        self.occupants.sort_unstable();
+        self.occupants.dedup();
+        None
+    }
+
+    /// Put the zone back at its first position with a full timer
+    pub fn reset(&mut self, physics: &mut PhysicsWorld) {
+        self.move_to(physics, 0);
+        self.move_timer = self.move_interval;
+        self.occupants.clear();
+    }
+
+    fn move_to(&mut self, physics: &mut PhysicsWorld, index: usize) {
+        self.current = index;
+        let position = self.position();
+        if let Some(body) = physics.get_rigid_body_mut(self.body_handle) {
+            body.set_translation(Vector::new(position.x, position.y), true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::characters::CharacterStats;
+
+    #[test]
+    fn test_zone_moves_and_tracks_occupants() {
+        let mut physics = PhysicsWorld::new();
+        let floor = physics.add_rigid_body(presets::platform_body(0.0, -5.0));
+        physics.add_collider(presets::platform_collider(60.0, 1.0), floor);
+
+        let mut characters = CharacterManager::new();
+        characters.spawn_character(
+            "Climber",
+            Some(2),
+            CharacterStats::standard(),
+            &mut physics,
+            8.0,
+            -3.5,
+        );
+
+        let def = CaptureZoneDef {
+            size: [4.0, 3.0],
+            positions: vec![[0.0, -3.5], [8.0, -3.5]],
+            move_interval: 1.0,
+        };
+        let mut zone = CaptureZone::new(&mut physics, &def);
+        let dt = 1.0 / 60.0;
+
+        physics.step();
+        assert_eq!(zone.update(&mut physics, &characters, dt), None);
+        assert!(zone.occupants().is_empty());
+
+        // Once the timer runs out the zone lands on the character
+        let moved = zone.update(&mut physics, &characters, 1.0);
+        assert_eq!(moved, Some(Vec2::new(8.0, -3.5)));
+        physics.step();
+        zone.update(&mut physics, &characters, dt);
+        assert_eq!(zone.occupants(), &[2]);
+
+        zone.reset(&mut physics);
+        assert_eq!(zone.position(), Vec2::new(0.0, -3.5));
+        assert!(zone.occupants().is_empty());
+    }
+}
@@ -0,0 +1,561 @@
+// Destructible platforms that fracture into debris
+
+use super::file::PlatformDef;
+use crate::engine::physics::{
+    body::presets, ColliderHandle, CollisionEvent, PhysicsWorld, RigidBodyHandle,
+};
+use glam::Vec2;
+use log::info;
+
+/// Unique identifier for destructible platforms
+pub type DestructibleId = u32;
+
+/// Default contact force needed before an impact damages a platform
+const DEFAULT_IMPACT_THRESHOLD: f32 = 400.0;
+
+/// Default damage dealt per unit of force above the impact threshold
+const DEFAULT_IMPACT_DAMAGE_SCALE: f32 = 0.05;
+
+/// Speed below which a debris piece counts as resting (units/second)
+const DEBRIS_REST_SPEED: f32 = 0.2;
+
+/// How long debris has to rest before it is cleaned up (seconds)
+const DEBRIS_SETTLE_TIME: f32 = 1.5;
+
+/// Debris is removed after this long no matter what (seconds)
+const DEBRIS_MAX_LIFETIME: f32 = 10.0;
+
+/// Default height below which debris is considered out of bounds
+const DEFAULT_KILL_Y: f32 = -30.0;
+
+/// A static platform with hit points that breaks apart at 0 HP
+#[derive(Debug)]
+pub struct DestructiblePlatform {
+    /// Unique identifier
+    pub id: DestructibleId,
+    /// Handle to the platform's body (None once destroyed)
+    pub body_handle: Option<RigidBodyHandle>,
+    /// Handle to the platform's collider (None once destroyed)
+    pub collider_handle: Option<ColliderHandle>,
+    /// Center of the platform in world units
+    pub position: Vec2,
+    /// Platform size in world units
+    pub size: Vec2,
+    /// Current hit points
+    pub health: i32,
+    /// Maximum hit points
+    pub max_health: i32,
+    /// Contact force needed before an impact deals damage
+    pub impact_threshold: f32,
+    /// Damage per unit of force above the threshold
+    pub impact_damage_scale: f32,
+    /// Number of debris pieces across and up (columns, rows)
+    pub debris_grid: (u32, u32),
+}
+
+impl DestructiblePlatform {
+    /// Check if the platform has been destroyed
+    pub fn is_destroyed(&self) -> bool {
+        self.health <= 0
+    }
+
+    /// Get the remaining health as a fraction (0.0 to 1.0)
+    pub fn health_fraction(&self) -> f32 {
+        if self.max_health <= 0 {
+            return 0.0;
+        }
+        (self.health.max(0) as f32 / self.max_health as f32).clamp(0.0, 1.0)
+    }
+
+    /// Distance from a point to the closest point of the platform's box
+    fn distance_to(&self, point: Vec2) -> f32 {
+        let half = self.size / 2.0;
+        let offset = (point - self.position).abs() - half;
+        offset.max(Vec2::ZERO).length()
+    }
+}
+
+/// A dynamic piece of a destroyed platform
+#[derive(Debug)]
+pub struct DebrisPiece {
+    /// Handle to the debris body
+    pub body_handle: RigidBodyHandle,
+    /// Piece size in world units
+    pub size: Vec2,
+    /// Time since the piece was spawned
+    age: f32,
+    /// Time the piece has been resting
+    rest_time: f32,
+}
+
+/// Owns destructible platforms and the debris they leave behind
+#[derive(Debug)]
+pub struct DestructibleManager {
+    platforms: Vec<DestructiblePlatform>,
+    debris: Vec<DebrisPiece>,
+    next_id: DestructibleId,
+    /// Height below which debris is removed
+    kill_y: f32,
+    /// Platforms built from the arena file, and the definitions they came from
+    arena_platforms: Vec<DestructibleId>,
+    arena_defs: Vec<PlatformDef>,
+}
+
+impl DestructibleManager {
+    pub fn new() -> Self {
+        Self {
+            platforms: Vec::new(),
+            debris: Vec::new(),
+            next_id: 0,
+            kill_y: DEFAULT_KILL_Y,
+            arena_platforms: Vec::new(),
+            arena_defs: Vec::new(),
+        }
+    }
+
+    /// Set the height below which debris is removed
+    pub fn with_kill_y(mut self, kill_y: f32) -> Self {
+        self.kill_y = kill_y;
+        self
+    }
+
+    /// Add a destructible platform centered at (x, y)
+    pub fn spawn_platform(
+        &mut self,
+        physics: &mut PhysicsWorld,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        health: i32,
+    ) -> DestructibleId {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let body_handle = physics.add_rigid_body(presets::platform_body(x, y));
+        let collider =
+            presets::destructible_platform_collider(width, height, DEFAULT_IMPACT_THRESHOLD);
+        let collider_handle = physics.add_collider(collider, body_handle);
+
+        // Roughly square pieces, at least two across
+        let columns = ((width / height.max(0.1)).round() as u32).clamp(2, 8);
+
+        self.platforms.push(DestructiblePlatform {
+            id,
+            body_handle: Some(body_handle),
+            collider_handle: Some(collider_handle),
+            position: Vec2::new(x, y),
+            size: Vec2::new(width, height),
+            health,
+            max_health: health,
+            impact_threshold: DEFAULT_IMPACT_THRESHOLD,
+            impact_damage_scale: DEFAULT_IMPACT_DAMAGE_SCALE,
+            debris_grid: (columns, 1),
+        });
+
+        info!(
+            "Spawned destructible platform {} at ({}, {}) with {} HP",
+            id, x, y, health
+        );
+        id
+    }
+
+    /// Keep the arena file's destructible platforms (those with `health`) in
+    /// sync with its definitions
+    /// Only rebuilds when the definitions changed (e.g. in the editor), so
+    /// broken platforms stay broken until `clear` at the end of the round
+    pub fn sync_arena(&mut self, physics: &mut PhysicsWorld, defs: &[PlatformDef]) {
+        let defs: Vec<PlatformDef> = defs
+            .iter()
+            .filter(|d| d.health.is_some())
+            .copied()
+            .collect();
+        if self.arena_defs == defs {
+            return;
+        }
+
+        for id in std::mem::take(&mut self.arena_platforms) {
+            self.remove(physics, id);
+        }
+        self.arena_platforms = defs
+            .iter()
+            .map(|def| {
+                let [x, y] = def.position;
+                let [width, height] = def.size;
+                let health = def.health.unwrap_or_default();
+                let id = self.spawn_platform(physics, x, y, width, height, health);
+                if let Some(collider) = self.get(id).and_then(|p| p.collider_handle) {
+                    physics.set_one_way_platform(collider, def.one_way);
+                    physics.set_conveyor_speed(collider, def.conveyor_speed);
+                }
+                id
+            })
+            .collect();
+        self.arena_defs = defs;
+    }
+
+    /// Remove a platform (its debris is left to settle)
+    pub fn remove(&mut self, physics: &mut PhysicsWorld, id: DestructibleId) {
+        let Some(index) = self.platforms.iter().position(|p| p.id == id) else {
+            return;
+        };
+        let platform = self.platforms.remove(index);
+        if let Some(handle) = platform.body_handle {
+            physics.remove_rigid_body(handle);
+        }
+    }
+
+    /// Get a platform by ID
+    pub fn get(&self, id: DestructibleId) -> Option<&DestructiblePlatform> {
+        self.platforms.iter().find(|p| p.id == id)
+    }
+
+    /// Get a mutable platform by ID
+    pub fn get_mut(&mut self, id: DestructibleId) -> Option<&mut DestructiblePlatform> {
+        self.platforms.iter_mut().find(|p| p.id == id)
+    }
+
+    /// Get all platforms, including destroyed ones
+    pub fn platforms(&self) -> &[DestructiblePlatform] {
+        &self.platforms
+    }
+
+    /// Get all live debris pieces
+    pub fn debris(&self) -> &[DebrisPiece] {
+        &self.debris
+    }
+
+    /// Deal damage to a platform, fracturing it at 0 HP
+    /// Returns true if this damage destroyed the platform
+    pub fn damage(&mut self, physics: &mut PhysicsWorld, id: DestructibleId, amount: i32) -> bool {
+        let Some(index) = self.platforms.iter().position(|p| p.id == id) else {
+            return false;
+        };
+
+        let platform = &mut self.platforms[index];
+        if platform.is_destroyed() || amount <= 0 {
+            return false;
+        }
+
+        platform.health = (platform.health - amount).max(0);
+        if platform.is_destroyed() {
+            self.fracture(physics, index, None);
+            return true;
+        }
+        false
+    }
+
+    /// Damage every platform within `radius` of `center`
+    /// Damage falls off linearly with distance to the platform's edge
+    /// Returns the IDs of platforms destroyed by the explosion
+    pub fn apply_explosion(
+        &mut self,
+        physics: &mut PhysicsWorld,
+        center: Vec2,
+        radius: f32,
+        damage: i32,
+    ) -> Vec<DestructibleId> {
+        let mut destroyed = Vec::new();
+        if radius <= 0.0 {
+            return destroyed;
+        }
+
+        for index in 0..self.platforms.len() {
+            let platform = &mut self.platforms[index];
+            if platform.is_destroyed() {
+                continue;
+            }
+
+            let distance = platform.distance_to(center);
+            if distance > radius {
+                continue;
+            }
+
+            let falloff = 1.0 - distance / radius;
+            let amount = (damage as f32 * falloff).round() as i32;
+            if amount <= 0 {
+                continue;
+            }
+
+            platform.health = (platform.health - amount).max(0);
+            if platform.is_destroyed() {
+                self.fracture(physics, index, Some(center));
+                destroyed.push(self.platforms[index].id);
+            }
+        }
+
+        destroyed
+    }
+
+    /// Apply heavy-impact damage from this step's contact force events
+    /// Returns the IDs of platforms destroyed by impacts
+    pub fn handle_collision_events(
+        &mut self,
+        physics: &mut PhysicsWorld,
+        events: &[CollisionEvent],
+    ) -> Vec<DestructibleId> {
+        let mut destroyed = Vec::new();
+
+        for event in events {
+            let CollisionEvent::ContactForce {
+                collider1,
+                collider2,
+                force,
+            } = *event
+            else {
+                continue;
+            };
+
+            let Some(index) = self.platforms.iter().position(|p| {
+                p.collider_handle
+                    .is_some_and(|c| c == collider1 || c == collider2)
+            }) else {
+                continue;
+            };
+
+            let platform = &mut self.platforms[index];
+            if platform.is_destroyed() || force <= platform.impact_threshold {
+                continue;
+            }
+
+            let excess = force - platform.impact_threshold;
+            let amount = ((excess * platform.impact_damage_scale).round() as i32).max(1);
+            platform.health = (platform.health - amount).max(0);
+
+            if platform.is_destroyed() {
+                self.fracture(physics, index, None);
+                destroyed.push(self.platforms[index].id);
+            }
+        }
+
+        destroyed
+    }
+
+    /// Remove debris that has settled, fallen out of bounds, or lived too long
+    pub fn update(&mut self, physics: &mut PhysicsWorld, dt: f32) {
+        let kill_y = self.kill_y;
+
+        self.debris.retain_mut(|piece| {
+            let Some(body) = physics.get_rigid_body(piece.body_handle) else {
+                return false;
+            };
+
+            piece.age += dt;
+            if body.linvel().norm() < DEBRIS_REST_SPEED && body.angvel().abs() < DEBRIS_REST_SPEED {
+                piece.rest_time += dt;
+            } else {
+                piece.rest_time = 0.0;
+            }
+
+            let expired = piece.rest_time >= DEBRIS_SETTLE_TIME
+                || piece.age >= DEBRIS_MAX_LIFETIME
+                || body.translation().y < kill_y;
+
+            if expired {
+                physics.remove_rigid_body(piece.body_handle);
+            }
+            !expired
+        });
+    }
+
+    /// Remove all platforms and debris from the physics world
+    pub fn clear(&mut self, physics: &mut PhysicsWorld) {
+        for platform in &mut self.platforms {
+            if let Some(handle) = platform.body_handle.take() {
+                physics.remove_rigid_body(handle);
+            }
+            platform.collider_handle = None;
+        }
+        for piece in self.debris.drain(..) {
+            physics.remove_rigid_body(piece.body_handle);
+        }
+        self.platforms.clear();
+        self.arena_platforms.clear();
+        self.arena_defs.clear();
+    }
+
+    /// Replace a platform's static body with a grid of dynamic debris pieces
+    /// Pieces fly away from `origin` (defaults to the platform's center)
+    fn fracture(&mut self, physics: &mut PhysicsWorld, index: usize, origin: Option<Vec2>) {
+        let platform = &mut self.platforms[index];
+        let Some(body_handle) = platform.body_handle.take() else {
+            return;
+        };
+        platform.collider_handle = None;
+        physics.remove_rigid_body(body_handle);
+
+        let (columns, rows) = platform.debris_grid;
+        let (columns, rows) = (columns.max(1), rows.max(1));
+        let piece_size = Vec2::new(
+            platform.size.x / columns as f32,
+            platform.size.y / rows as f32,
+        );
+        let bottom_left = platform.position - platform.size / 2.0;
+        let origin = origin.unwrap_or(platform.position);
+
+        for row in 0..rows {
+            for column in 0..columns {
+                let center = bottom_left
+                    + Vec2::new(
+                        (column as f32 + 0.5) * piece_size.x,
+                        (row as f32 + 0.5) * piece_size.y,
+                    );
+
+                // Push pieces outward and slightly up, spinning away from the origin
+                let away = (center - origin).normalize_or_zero();
+                let velocity = away * 3.0 + Vec2::new(0.0, 2.0);
+                let spin = if away.x < 0.0 { 3.0 } else { -3.0 };
+
+                let body = presets::debris_body(center.x, center.y, velocity.x, velocity.y, spin);
+                let handle = physics.add_rigid_body(body);
+                physics.add_collider(presets::debris_collider(piece_size.x, piece_size.y), handle);
+
+                self.debris.push(DebrisPiece {
+                    body_handle: handle,
+                    size: piece_size,
+                    age: 0.0,
+                    rest_time: 0.0,
+                });
+            }
+        }
+
+        info!(
+            "Destructible platform {} fractured into {} pieces",
+            platform.id,
+            columns * rows
+        );
+    }
+}
+
+impl Default for DestructibleManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damage_fractures_at_zero_hp() {
+        let mut physics = PhysicsWorld::new();
+        let mut manager = DestructibleManager::new();
+        let id = manager.spawn_platform(&mut physics, 0.0, 0.0, 4.0, 1.0, 50);
+
+        assert!(!manager.damage(&mut physics, id, 20));
+        assert_eq!(manager.get(id).unwrap().health, 30);
+        assert!(manager.debris().is_empty());
+
+        assert!(manager.damage(&mut physics, id, 40));
+        let platform = manager.get(id).unwrap();
+        assert!(platform.is_destroyed());
+        assert!(platform.body_handle.is_none());
+        assert_eq!(manager.debris().len(), 4);
+
+        // Further damage does nothing
+        assert!(!manager.damage(&mut physics, id, 10));
+    }
+
+    #[test]
+    fn test_explosion_falloff() {
+        let mut physics = PhysicsWorld::new();
+        let mut manager = DestructibleManager::new();
+        let near = manager.spawn_platform(&mut physics, 0.0, 0.0, 2.0, 1.0, 100);
+        let far = manager.spawn_platform(&mut physics, 10.0, 0.0, 2.0, 1.0, 100);
+
+        // Edge of the near platform is 1 unit from the center: 75% damage
+        manager.apply_explosion(&mut physics, Vec2::new(-2.0, 0.0), 4.0, 40);
+        assert_eq!(manager.get(near).unwrap().health, 70);
+        assert_eq!(manager.get(far).unwrap().health, 100);
+
+        let destroyed = manager.apply_explosion(&mut physics, Vec2::ZERO, 4.0, 100);
+        assert_eq!(destroyed, vec![near]);
+    }
+
+    #[test]
+    fn test_heavy_impact_damages_platform() {
+        let mut physics = PhysicsWorld::new();
+        let mut manager = DestructibleManager::new();
+        let id = manager.spawn_platform(&mut physics, 0.0, 0.0, 4.0, 1.0, 100);
+        let collider = manager.get(id).unwrap().collider_handle.unwrap();
+        let other = ColliderHandle::invalid();
+
+        let light = CollisionEvent::ContactForce {
+            collider1: other,
+            collider2: collider,
+            force: DEFAULT_IMPACT_THRESHOLD * 0.5,
+        };
+        manager.handle_collision_events(&mut physics, &[light]);
+        assert_eq!(manager.get(id).unwrap().health, 100);
+
+        let heavy = CollisionEvent::ContactForce {
+            collider1: collider,
+            collider2: other,
+            force: DEFAULT_IMPACT_THRESHOLD + 200.0,
+        };
+        manager.handle_collision_events(&mut physics, &[heavy]);
+        assert_eq!(manager.get(id).unwrap().health, 90);
+    }
+
+    #[test]
+    fn test_debris_cleaned_up_out_of_bounds() {
+        let mut physics = PhysicsWorld::new();
+        let mut manager = DestructibleManager::new().with_kill_y(-5.0);
+        let id = manager.spawn_platform(&mut physics, 0.0, 0.0, 4.0, 1.0, 10);
+        manager.damage(&mut physics, id, 10);
+        assert!(!manager.debris().is_empty());
+
+        // Nothing to land on, so the debris falls out of the arena
+        for _ in 0..120 {
+            manager.update(&mut physics, 1.0 / 60.0);
+            physics.step();
+        }
+
+        assert!(manager.debris().is_empty());
+    }
+
+    #[test]
+    fn test_sync_arena_builds_platforms_with_health() {
+        let mut physics = PhysicsWorld::new();
+        let mut manager = DestructibleManager::new();
+        let mut defs = vec![
+            PlatformDef {
+                position: [0.0, 0.0],
+                size: [4.0, 1.0],
+                one_way: false,
+                health: Some(50),
+                conveyor_speed: 0.0,
+            },
+            PlatformDef {
+                position: [8.0, 0.0],
+                size: [4.0, 1.0],
+                one_way: false,
+                health: None,
+                conveyor_speed: 0.0,
+            },
+        ];
+
+        // Only the platform with health is destructible
+        manager.sync_arena(&mut physics, &defs);
+        assert_eq!(manager.platforms().len(), 1);
+        let id = manager.platforms()[0].id;
+        assert_eq!(manager.get(id).unwrap().health, 50);
+
+        // Broken platforms stay broken while the definitions are unchanged
+        manager.damage(&mut physics, id, 50);
+        manager.sync_arena(&mut physics, &defs);
+        assert!(manager.get(id).unwrap().is_destroyed());
+
+        // Clearing for a new round builds them again
+        manager.clear(&mut physics);
+        manager.sync_arena(&mut physics, &defs);
+        assert_eq!(manager.platforms()[0].health, 50);
+
+        // Editing the definitions replaces the old platforms
+        defs[0].health = Some(80);
+        manager.sync_arena(&mut physics, &defs);
+        assert_eq!(manager.platforms().len(), 1);
+        assert_eq!(manager.platforms()[0].health, 80);
+    }
+}
@@ -0,0 +1,575 @@
+// In-game arena editor
+//
+// The editor owns an `ArenaFile` document and keeps a physics body for every
+// item in it, so edits show up in the physics debug view and can be played
+// on immediately.
+
+use super::file::{ArenaFile, HazardDef, HazardKind, PlatformDef, SpawnDef};
+use crate::engine::physics::{body::presets, PhysicsWorld, RigidBodyHandle};
+use glam::Vec2;
+use log::info;
+
+/// Size of the grab area around item corners used for resizing (world units)
+const HANDLE_SIZE: f32 = 0.4;
+
+/// Smallest size an item can be resized to (world units)
+const MIN_ITEM_SIZE: f32 = 0.25;
+
+/// Size of the marker shown for spawn points (world units)
+const SPAWN_MARKER_SIZE: Vec2 = Vec2::new(0.8, 1.8);
+
+/// Hit points of newly placed destructible platforms
+const DEFAULT_PLATFORM_HEALTH: i32 = 100;
+
+/// What a left click on empty space creates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EditorTool {
+    #[default]
+    Platform,
+    OneWayPlatform,
+    DestructiblePlatform,
+    Spawn,
+    Hazard(HazardKind),
+}
+
+impl EditorTool {
+    /// Tools in the order they are bound to number keys
+    pub const ALL: [EditorTool; 7] = [
+        EditorTool::Platform,
+        EditorTool::OneWayPlatform,
+        EditorTool::Spawn,
+        EditorTool::Hazard(HazardKind::Spikes),
+        EditorTool::Hazard(HazardKind::Lava),
+        EditorTool::Hazard(HazardKind::Saw),
+        EditorTool::DestructiblePlatform,
+    ];
+}
+
+/// An item in the arena document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorItem {
+    Platform(usize),
+    Spawn(usize),
+    Hazard(usize),
+}
+
+/// What the current mouse drag does
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DragMode {
+    /// Move the item, keeping the grab point under the cursor
+    Move { offset: Vec2 },
+    /// Resize the item, keeping the opposite corner fixed
+    Resize { anchor: Vec2 },
+}
+
+/// Mouse-driven editor for arena files
+#[derive(Debug)]
+pub struct ArenaEditor {
+    /// Whether edit mode is on
+    enabled: bool,
+    /// The arena being edited
+    document: ArenaFile,
+    /// What clicking on empty space creates
+    pub tool: EditorTool,
+    /// Grid that positions and sizes snap to (0.0 = no snapping)
+    pub grid: f32,
+    /// Physics bodies, parallel to the document's item lists
+    platform_bodies: Vec<RigidBodyHandle>,
+    spawn_bodies: Vec<RigidBodyHandle>,
+    hazard_bodies: Vec<RigidBodyHandle>,
+    selection: Option<EditorItem>,
+    drag: Option<DragMode>,
+}
+
+impl ArenaEditor {
+    /// Create an editor for a document and build its physics bodies
+    pub fn new(document: ArenaFile, physics: &mut PhysicsWorld) -> Self {
+        let mut editor = Self {
+            enabled: false,
+            document,
+            tool: EditorTool::default(),
+            grid: 0.25,
+            platform_bodies: Vec::new(),
+            spawn_bodies: Vec::new(),
+            hazard_bodies: Vec::new(),
+            selection: None,
+            drag: None,
+        };
+        editor.rebuild(physics);
+        editor
+    }
+
+    /// Check if edit mode is on
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Turn edit mode on or off
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        self.drag = None;
+        if !self.enabled {
+            self.selection = None;
+        }
+        info!(
+            "Arena editor {}",
+            if self.enabled { "enabled" } else { "disabled" }
+        );
+    }
+
+    /// Get the arena being edited
+    pub fn document(&self) -> &ArenaFile {
+        &self.document
+    }
+
+    /// Get the selected item
+    pub fn selection(&self) -> Option<EditorItem> {
+        self.selection
+    }
+
+    /// Get the center and size of the selected item
+    pub fn selection_rect(&self) -> Option<(Vec2, Vec2)> {
+        self.selection.and_then(|item| self.item_rect(item))
+    }
+
+    /// Select the tool bound to a number key (1-based)
+    pub fn select_tool(&mut self, number: usize) {
+        if let Some(tool) = number.checked_sub(1).and_then(|i| EditorTool::ALL.get(i)) {
+            self.tool = *tool;
+            info!("Editor tool: {:?}", tool);
+        }
+    }
+
+    /// Handle a left mouse press at a world position
+    /// Grabs the item under the cursor, or places a new one with the current tool
+    pub fn mouse_pressed(&mut self, physics: &mut PhysicsWorld, world_pos: Vec2) {
+        if !self.enabled {
+            return;
+        }
+
+        let item = match self.item_at(world_pos) {
+            Some(item) => item,
+            None => self.place(physics, world_pos),
+        };
+        self.selection = Some(item);
+
+        let Some((center, size)) = self.item_rect(item) else {
+            return;
+        };
+
+        // Corners of resizable items act as resize handles
+        let resizable = !matches!(item, EditorItem::Spawn(_));
+        let half = size / 2.0;
+        let corner = Vec2::new(
+            if world_pos.x < center.x { -1.0 } else { 1.0 },
+            if world_pos.y < center.y { -1.0 } else { 1.0 },
+        );
+        let near_corner = (center + corner * half - world_pos).abs();
+
+        self.drag = if resizable && near_corner.x <= HANDLE_SIZE && near_corner.y <= HANDLE_SIZE {
+            Some(DragMode::Resize {
+                anchor: center - corner * half,
+            })
+        } else {
+            Some(DragMode::Move {
+                offset: center - world_pos,
+            })
+        };
+    }
+
+    /// Handle the mouse moving to a world position (drags the grabbed item)
+    pub fn mouse_moved(&mut self, physics: &mut PhysicsWorld, world_pos: Vec2) {
+        let (Some(item), Some(drag)) = (self.selection, self.drag) else {
+            return;
+        };
+        let Some((center, size)) = self.item_rect(item) else {
+            return;
+        };
+
+        let (new_center, new_size) = match drag {
+            DragMode::Move { offset } => (self.snap(world_pos + offset), size),
+            DragMode::Resize { anchor } => {
+                let corner = self.snap(world_pos);
+                let size = (corner - anchor).abs().max(Vec2::splat(MIN_ITEM_SIZE));
+                let direction = (corner - anchor).signum();
+                (anchor + direction * size / 2.0, size)
+            }
+        };
+
+        if new_center != center || new_size != size {
+            self.set_item_rect(item, new_center, new_size);
+            self.rebuild_item(physics, item);
+        }
+    }
+
+    /// Handle the left mouse button being released
+    pub fn mouse_released(&mut self) {
+        self.drag = None;
+    }
+
+    /// Delete the item under the cursor
+    pub fn delete_at(&mut self, physics: &mut PhysicsWorld, world_pos: Vec2) {
+        if !self.enabled {
+            return;
+        }
+        if let Some(item) = self.item_at(world_pos) {
+            self.delete(physics, item);
+        }
+    }
+
+    /// Delete the selected item
+    pub fn delete_selected(&mut self, physics: &mut PhysicsWorld) {
+        if !self.enabled {
+            return;
+        }
+        if let Some(item) = self.selection {
+            self.delete(physics, item);
+        }
+    }
+
+    /// Change the hit points of the selected platform
+    /// Platforms at 0 HP or below become indestructible again
+    pub fn adjust_selected_health(&mut self, physics: &mut PhysicsWorld, delta: i32) {
+        if !self.enabled {
+            return;
+        }
+        let Some(item @ EditorItem::Platform(i)) = self.selection else {
+            return;
+        };
+        let Some(platform) = self.document.platforms.get_mut(i) else {
+            return;
+        };
+
+        let health = platform.health.unwrap_or(0) + delta;
+        platform.health = (health > 0).then_some(health);
+        match platform.health {
+            Some(health) => info!("Platform health: {} HP", health),
+            None => info!("Platform is indestructible"),
+        }
+        self.rebuild_item(physics, item);
+    }
+
+    /// Save the document to an arena file
+    pub fn save(&self, path: &std::path::Path) -> Result<(), super::file::ArenaFileError> {
+        self.document.save(path)?;
+        info!("Saved arena '{}' to {}", self.document.name, path.display());
+        Ok(())
+    }
+
+    /// Find the topmost item at a world position
+    /// Spawns and hazards are checked before platforms since they sit on top
+    pub fn item_at(&self, world_pos: Vec2) -> Option<EditorItem> {
+        let hit = |item: EditorItem| {
+            self.item_rect(item).is_some_and(|(center, size)| {
+                let delta = (world_pos - center).abs();
+                delta.x <= size.x / 2.0 + HANDLE_SIZE / 2.0
+                    && delta.y <= size.y / 2.0 + HANDLE_SIZE / 2.0
+            })
+        };
+
+        let spawns = (0..self.document.spawns.len()).rev().map(EditorItem::Spawn);
+        let hazards = (0..self.document.hazards.len())
+            .rev()
+            .map(EditorItem::Hazard);
+        let platforms = (0..self.document.platforms.len())
+            .rev()
+            .map(EditorItem::Platform);
+
+        spawns
+            .chain(hazards)
+            .chain(platforms)
+            .find(|item| hit(*item))
+    }
+
+    /// Get the center and size of an item
+    pub fn item_rect(&self, item: EditorItem) -> Option<(Vec2, Vec2)> {
+        match item {
+            EditorItem::Platform(i) => self
+                .document
+                .platforms
+                .get(i)
+                .map(|p| (Vec2::from(p.position), Vec2::from(p.size))),
+            EditorItem::Spawn(i) => self
+                .document
+                .spawns
+                .get(i)
+                .map(|s| (Vec2::from(s.position), SPAWN_MARKER_SIZE)),
+            EditorItem::Hazard(i) => self
+                .document
+                .hazards
+                .get(i)
+                .map(|h| (Vec2::from(h.position), Vec2::from(h.size))),
+        }
+    }
+
+    /// Snap a position to the grid
+    fn snap(&self, position: Vec2) -> Vec2 {
+        if self.grid <= 0.0 {
+            return position;
+        }
+        (position / self.grid).round() * self.grid
+    }
+
+    /// Add a new item with the current tool
+    fn place(&mut self, physics: &mut PhysicsWorld, world_pos: Vec2) -> EditorItem {
+        let position = self.snap(world_pos).to_array();
+
+        let item = match self.tool {
+            EditorTool::Platform
+            | EditorTool::OneWayPlatform
+            | EditorTool::DestructiblePlatform => {
+                self.document.platforms.push(PlatformDef {
+                    position,
+                    size: [4.0, 0.5],
+                    one_way: self.tool == EditorTool::OneWayPlatform,
+                    health: (self.tool == EditorTool::DestructiblePlatform)
+                        .then_some(DEFAULT_PLATFORM_HEALTH),
+                    conveyor_speed: 0.0,
+                });
+                EditorItem::Platform(self.document.platforms.len() - 1)
+            }
+            EditorTool::Spawn => {
+                self.document.spawns.push(SpawnDef { position });
+                EditorItem::Spawn(self.document.spawns.len() - 1)
+            }
+            EditorTool::Hazard(kind) => {
+                self.document.hazards.push(HazardDef {
+                    kind,
+                    position,
+                    size: [2.0, 0.5],
+                });
+                EditorItem::Hazard(self.document.hazards.len() - 1)
+            }
+        };
+
+        let handle = self.create_body(physics, item);
+        match item {
+            EditorItem::Platform(_) => self.platform_bodies.push(handle),
+            EditorItem::Spawn(_) => self.spawn_bodies.push(handle),
+            EditorItem::Hazard(_) => self.hazard_bodies.push(handle),
+        }
+
+        info!("Placed {:?}", item);
+        item
+    }
+
+    /// Remove an item from the document and the physics world
+    fn delete(&mut self, physics: &mut PhysicsWorld, item: EditorItem) {
+        let handle = match item {
+            EditorItem::Platform(i) if i < self.document.platforms.len() => {
+                self.document.platforms.remove(i);
+                self.platform_bodies.remove(i)
+            }
+            EditorItem::Spawn(i) if i < self.document.spawns.len() => {
+                self.document.spawns.remove(i);
+                self.spawn_bodies.remove(i)
+            }
+            EditorItem::Hazard(i) if i < self.document.hazards.len() => {
+                self.document.hazards.remove(i);
+                self.hazard_bodies.remove(i)
+            }
+            _ => return,
+        };
+
+        physics.remove_rigid_body(handle);
+        self.selection = None;
+        self.drag = None;
+        info!("Deleted {:?}", item);
+    }
+
+    /// Update an item's position and size in the document
+    fn set_item_rect(&mut self, item: EditorItem, center: Vec2, size: Vec2) {
+        match item {
+            EditorItem::Platform(i) => {
+                if let Some(platform) = self.document.platforms.get_mut(i) {
+                    platform.position = center.to_array();
+                    platform.size = size.to_array();
+                }
+            }
+            EditorItem::Spawn(i) => {
+                if let Some(spawn) = self.document.spawns.get_mut(i) {
+                    spawn.position = center.to_array();
+                }
+            }
+            EditorItem::Hazard(i) => {
+                if let Some(hazard) = self.document.hazards.get_mut(i) {
+                    hazard.position = center.to_array();
+                    hazard.size = size.to_array();
+                }
+            }
+        }
+    }
+
+    /// Create the physics body that represents an item
+    fn create_body(&self, physics: &mut PhysicsWorld, item: EditorItem) -> RigidBodyHandle {
+        let (center, size) = self.item_rect(item).unwrap_or((Vec2::ZERO, Vec2::ONE));
+        let handle = physics.add_rigid_body(presets::platform_body(center.x, center.y));
+
+        let destructible = |i| {
+            self.document
+                .platforms
+                .get(i)
+                .is_some_and(|p: &PlatformDef| p.health.is_some())
+        };
+        match item {
+            EditorItem::Platform(i) if !destructible(i) => {
+                let collider =
+                    physics.add_collider(presets::platform_collider(size.x, size.y), handle);
+                if let Some(platform) = self.document.platforms.get(i) {
+                    physics.set_one_way_platform(collider, platform.one_way);
+                    physics.set_conveyor_speed(collider, platform.conveyor_speed);
+                }
+            }
+            // Hazards and destructible platforms are live in their managers;
+            // the editor only needs a marker
+            EditorItem::Platform(_) | EditorItem::Spawn(_) | EditorItem::Hazard(_) => {
+                physics.add_collider(presets::sensor_collider(size.x, size.y), handle);
+            }
+        }
+
+        handle
+    }
+
+    /// Replace the physics body of a single item
+    fn rebuild_item(&mut self, physics: &mut PhysicsWorld, item: EditorItem) {
+        let new_handle = self.create_body(physics, item);
+        let slot = match item {
+            EditorItem::Platform(i) => self.platform_bodies.get_mut(i),
+            EditorItem::Spawn(i) => self.spawn_bodies.get_mut(i),
+            EditorItem::Hazard(i) => self.hazard_bodies.get_mut(i),
+        };
+
+        match slot {
+            Some(handle) => {
+                let old = std::mem::replace(handle, new_handle);
+                physics.remove_rigid_body(old);
+            }
+            None => physics.remove_rigid_body(new_handle),
+        }
+    }
+
+    /// Recreate the physics bodies for the whole document
+    fn rebuild(&mut self, physics: &mut PhysicsWorld) {
+        for handle in self
+            .platform_bodies
+            .drain(..)
+            .chain(self.spawn_bodies.drain(..))
+            .chain(self.hazard_bodies.drain(..))
+        {
+            physics.remove_rigid_body(handle);
+        }
+
+        self.platform_bodies = (0..self.document.platforms.len())
+            .map(|i| self.create_body(physics, EditorItem::Platform(i)))
+            .collect();
+        self.spawn_bodies = (0..self.document.spawns.len())
+            .map(|i| self.create_body(physics, EditorItem::Spawn(i)))
+            .collect();
+        self.hazard_bodies = (0..self.document.hazards.len())
+            .map(|i| self.create_body(physics, EditorItem::Hazard(i)))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled_editor(physics: &mut PhysicsWorld) -> ArenaEditor {
+        let mut editor = ArenaEditor::new(ArenaFile::new("Test"), physics);
+        editor.toggle();
+        editor
+    }
+
+    #[test]
+    fn test_disabled_editor_ignores_mouse() {
+        let mut physics = PhysicsWorld::new();
+        let mut editor = ArenaEditor::new(ArenaFile::new("Test"), &mut physics);
+
+        editor.mouse_pressed(&mut physics, Vec2::ZERO);
+        assert!(editor.document().platforms.is_empty());
+    }
+
+    #[test]
+    fn test_place_and_drag_platform() {
+        let mut physics = PhysicsWorld::new();
+        let mut editor = enabled_editor(&mut physics);
+
+        // Click on empty space places a platform and grabs it
+        editor.mouse_pressed(&mut physics, Vec2::new(1.1, 2.0));
+        assert_eq!(editor.document().platforms.len(), 1);
+        assert_eq!(editor.document().platforms[0].position, [1.0, 2.0]);
+
+        editor.mouse_moved(&mut physics, Vec2::new(3.1, 5.0));
+        editor.mouse_released();
+        assert_eq!(editor.document().platforms[0].position, [3.0, 5.0]);
+
+        // The live body follows the document
+        let body = physics.get_rigid_body(editor.platform_bodies[0]).unwrap();
+        assert_eq!(body.translation().x, 3.0);
+    }
+
+    #[test]
+    fn test_resize_from_corner() {
+        let mut physics = PhysicsWorld::new();
+        let mut editor = enabled_editor(&mut physics);
+        editor.mouse_pressed(&mut physics, Vec2::ZERO);
+        editor.mouse_released();
+
+        // Grab the top-right corner of the 4x0.5 platform and pull it out
+        editor.mouse_pressed(&mut physics, Vec2::new(2.0, 0.25));
+        editor.mouse_moved(&mut physics, Vec2::new(4.0, 1.75));
+        editor.mouse_released();
+
+        let platform = editor.document().platforms[0];
+        assert_eq!(platform.size, [6.0, 2.0]);
+        assert_eq!(platform.position, [1.0, 0.75]);
+    }
+
+    #[test]
+    fn test_tools_and_delete() {
+        let mut physics = PhysicsWorld::new();
+        let mut editor = enabled_editor(&mut physics);
+
+        editor.select_tool(3);
+        editor.mouse_pressed(&mut physics, Vec2::new(0.0, 5.0));
+        editor.mouse_released();
+        editor.select_tool(5);
+        editor.mouse_pressed(&mut physics, Vec2::new(5.0, 0.0));
+        editor.mouse_released();
+
+        assert_eq!(editor.document().spawns.len(), 1);
+        assert_eq!(editor.document().hazards[0].kind, HazardKind::Lava);
+
+        editor.delete_at(&mut physics, Vec2::new(0.0, 5.0));
+        assert!(editor.document().spawns.is_empty());
+        assert!(editor.spawn_bodies.is_empty());
+
+        editor.delete_selected(&mut physics);
+        assert_eq!(editor.document().hazards.len(), 1);
+        editor.mouse_pressed(&mut physics, Vec2::new(5.0, 0.0));
+        editor.delete_selected(&mut physics);
+        assert!(editor.document().hazards.is_empty());
+    }
+
+    #[test]
+    fn test_destructible_platform_health() {
+        let mut physics = PhysicsWorld::new();
+        let mut editor = enabled_editor(&mut physics);
+
+        editor.select_tool(7);
+        editor.mouse_pressed(&mut physics, Vec2::ZERO);
+        editor.mouse_released();
+        assert_eq!(editor.document().platforms[0].health, Some(100));
+
+        editor.adjust_selected_health(&mut physics, -75);
+        assert_eq!(editor.document().platforms[0].health, Some(25));
+
+        // Dropping to 0 HP makes the platform indestructible, and raising it again
+        // makes it destructible
+        editor.adjust_selected_health(&mut physics, -25);
+        assert_eq!(editor.document().platforms[0].health, None);
+        editor.adjust_selected_health(&mut physics, 25);
+        assert_eq!(editor.document().platforms[0].health, Some(25));
+    }
+}
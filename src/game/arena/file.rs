@@ -0,0 +1,596 @@
+// Arena file format (TOML)
+//
+// Arenas are stored as human-editable TOML files:
+//
+// ```toml
+// version = 1
+// name = "Custom"
+//
+// [blast_zone]
+// min = [-22.0, -14.0]
+// max = [22.0, 18.0]
+//
+// [[platforms]]
+// position = [0.0, -5.0]
+// size = [20.0, 1.0]
+// conveyor_speed = 2.0
+//
+// [[platforms]]
+// position = [-6.0, 4.0]
+// size = [4.0, 0.5]
+// health = 50
+//
+// [[spawns]]
+// position = [0.0, 2.0]
+//
+// [[hazards]]
+// kind = "spikes"
+// position = [4.0, -4.25]
+// size = [2.0, 0.5]
+//
+// [[force_zones]]
+// position = [-6.0, 0.0]
+// size = [2.0, 8.0]
+// force = [0.0, 30.0]
+//
+// [[pickup_spots]]
+// position = [0.0, 3.0]
+//
+// [pickup_table]
+// interval = 12.0
+// entries = [{ kind = "health", weight = 2.0 }, { kind = "extra_jump" }]
+//
+// [round_hazard]
+// kind = "rising_water"
+// start_time = 90.0
+// water_levels = [-12.0, -3.0]
+//
+// [capture_zone]
+// size = [4.0, 3.0]
+// positions = [[0.0, -3.0], [-8.0, 1.0], [8.0, 1.0]]
+// move_interval = 20.0
+//
+// background_color = [0.05, 0.05, 0.1, 1.0]
+//
+// [[background_layers]]
+// image = "textures/backgrounds/mountains.png"
+// parallax = 0.2
+// size = [30.0, 12.0]
+// tint = [0.6, 0.6, 0.8, 1.0]
+// ```
+
+use super::bounds::ArenaBounds;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Current version of the arena file format
+pub const ARENA_FILE_VERSION: u32 = 1;
+
+/// Arena file errors
+#[derive(Debug, thiserror::Error)]
+pub enum ArenaFileError {
+    #[error("Failed to read or write arena file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse arena file: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("Failed to serialize arena file: {0}")]
+    Serialize(#[from] toml::ser::Error),
+
+    #[error("Unsupported arena file version {0} (expected {ARENA_FILE_VERSION})")]
+    UnsupportedVersion(u32),
+}
+
+/// A static platform
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlatformDef {
+    /// Center in world units
+    pub position: [f32; 2],
+    /// Full size in world units
+    pub size: [f32; 2],
+    /// Only blocks from above
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub one_way: bool,
+    /// Hit points for destructible platforms (None = indestructible)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<i32>,
+    /// Conveyor surface speed (positive moves riders on top to the right)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub conveyor_speed: f32,
+}
+
+/// A spawn point
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpawnDef {
+    /// Position in world units
+    pub position: [f32; 2],
+}
+
+/// Kinds of hazard volumes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HazardKind {
+    #[default]
+    Spikes,
+    Lava,
+    Saw,
+}
+
+/// A hazard volume
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HazardDef {
+    /// What kind of hazard this is
+    #[serde(default)]
+    pub kind: HazardKind,
+    /// Center in world units
+    pub position: [f32; 2],
+    /// Full size in world units
+    pub size: [f32; 2],
+}
+
+/// A region that pushes bodies inside it (updrafts, crosswinds, fans)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ForceZoneDef {
+    /// Center in world units
+    pub position: [f32; 2],
+    /// Full size in world units
+    pub size: [f32; 2],
+    /// Acceleration applied inside the zone (units/second²)
+    pub force: [f32; 2],
+}
+
+/// Kinds of collectible pickups
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PickupKind {
+    /// Refills some health
+    Health,
+    /// Temporary movement speed boost
+    Speed,
+    /// One extra jump, usable once
+    ExtraJump,
+    /// Swaps one ability slot for a different ability
+    /// Has no effect until abilities exist, so it is left out of `ALL`
+    AbilitySwap,
+}
+
+impl PickupKind {
+    /// All pickup kinds that do something when collected
+    pub const ALL: [PickupKind; 3] = [PickupKind::Health, PickupKind::Speed, PickupKind::ExtraJump];
+}
+
+/// A spot where pickups can appear
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PickupSpotDef {
+    /// Position in world units
+    pub position: [f32; 2],
+}
+
+/// One entry in a pickup spawn table
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PickupEntryDef {
+    pub kind: PickupKind,
+    /// Relative chance of this kind being picked
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+}
+
+/// What pickups an arena spawns, and how often
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickupTableDef {
+    /// Time between spawns (seconds)
+    #[serde(default = "default_pickup_interval")]
+    pub interval: f32,
+    /// Most pickups that can be waiting at once
+    #[serde(default = "default_max_active")]
+    pub max_active: usize,
+    pub entries: Vec<PickupEntryDef>,
+}
+
+impl Default for PickupTableDef {
+    fn default() -> Self {
+        Self {
+            interval: default_pickup_interval(),
+            max_active: default_max_active(),
+            entries: PickupKind::ALL
+                .iter()
+                .map(|&kind| PickupEntryDef {
+                    kind,
+                    weight: default_weight(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The king-of-the-hill capture zone and the spots it moves between
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureZoneDef {
+    /// Size of the zone (width, height)
+    pub size: [f32; 2],
+    /// Centers the zone visits, in order
+    pub positions: Vec<[f32; 2]>,
+    /// Time the zone stays in one place (seconds)
+    #[serde(default = "default_move_interval")]
+    pub move_interval: f32,
+}
+
+/// Kinds of hazards that close in on long rounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundHazardMode {
+    /// Rounds can go on forever
+    #[default]
+    Off,
+    /// A kill plane rises from the bottom of the arena
+    RisingWater,
+    /// The blast zones shrink towards the middle
+    ClosingBlastZones,
+}
+
+impl RoundHazardMode {
+    /// Get the next mode in the order Off -> RisingWater -> ClosingBlastZones
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Off => Self::RisingWater,
+            Self::RisingWater => Self::ClosingBlastZones,
+            Self::ClosingBlastZones => Self::Off,
+        }
+    }
+
+    /// Get a short description for menus and logs
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::RisingWater => "rising water",
+            Self::ClosingBlastZones => "closing blast zones",
+        }
+    }
+}
+
+/// The hazard that closes in on long rounds
+/// Settings for both kinds may be given, so switching kinds keeps them
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RoundHazardDef {
+    /// What closes in
+    #[serde(default)]
+    pub kind: RoundHazardMode,
+    /// Round time before the hazard starts moving (seconds)
+    #[serde(default = "default_round_hazard_start")]
+    pub start_time: f32,
+    /// Water level at the start and the end (None = rise from near the bottom
+    /// of the blast zone)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub water_levels: Option<[f32; 2]>,
+    /// Blast zone once fully closed (None = half the size of the normal one)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_bounds: Option<BoundsDef>,
+}
+
+impl Default for RoundHazardDef {
+    fn default() -> Self {
+        Self {
+            kind: RoundHazardMode::Off,
+            start_time: default_round_hazard_start(),
+            water_levels: None,
+            final_bounds: None,
+        }
+    }
+}
+
+/// A parallax background layer, drawn back to front in file order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackgroundLayerDef {
+    /// Image path relative to the assets directory (None = solid tint)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// How much the layer follows the camera (0.0 = fixed, 1.0 = like the world)
+    pub parallax: f32,
+    /// Size of one copy of the image in world units
+    pub size: [f32; 2],
+    /// Center of the first copy
+    #[serde(default)]
+    pub offset: [f32; 2],
+    #[serde(default = "default_true")]
+    pub repeat_x: bool,
+    #[serde(default)]
+    pub repeat_y: bool,
+    /// Color multiplied with the image (RGBA)
+    #[serde(default = "default_tint")]
+    pub tint: [f32; 4],
+}
+
+/// An axis-aligned rectangle (min and max corners)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoundsDef {
+    pub min: [f32; 2],
+    pub max: [f32; 2],
+}
+
+impl From<BoundsDef> for ArenaBounds {
+    fn from(def: BoundsDef) -> Self {
+        ArenaBounds::new(Vec2::from(def.min), Vec2::from(def.max))
+    }
+}
+
+impl From<ArenaBounds> for BoundsDef {
+    fn from(bounds: ArenaBounds) -> Self {
+        Self {
+            min: bounds.min.to_array(),
+            max: bounds.max.to_array(),
+        }
+    }
+}
+
+/// Contents of an arena file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArenaFile {
+    /// File format version
+    pub version: u32,
+    /// Display name
+    #[serde(default)]
+    pub name: String,
+    /// Blast zone (None = use the game's default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blast_zone: Option<BoundsDef>,
+    #[serde(default)]
+    pub platforms: Vec<PlatformDef>,
+    #[serde(default)]
+    pub spawns: Vec<SpawnDef>,
+    #[serde(default)]
+    pub hazards: Vec<HazardDef>,
+    #[serde(default)]
+    pub force_zones: Vec<ForceZoneDef>,
+    #[serde(default)]
+    pub pickup_spots: Vec<PickupSpotDef>,
+    /// Pickup spawn table (None = the game's default table)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pickup_table: Option<PickupTableDef>,
+    /// King-of-the-hill capture zone (None = the game's default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_zone: Option<CaptureZoneDef>,
+    /// Hazard that closes in on long rounds (None = off)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub round_hazard: Option<RoundHazardDef>,
+    /// Color behind all background layers (None = the game's default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<[f32; 4]>,
+    #[serde(default)]
+    pub background_layers: Vec<BackgroundLayerDef>,
+}
+
+impl Default for ArenaFile {
+    fn default() -> Self {
+        Self::new("Untitled")
+    }
+}
+
+impl ArenaFile {
+    /// Create an empty arena
+    pub fn new(name: &str) -> Self {
+        Self {
+            version: ARENA_FILE_VERSION,
+            name: name.to_string(),
+            blast_zone: None,
+            platforms: Vec::new(),
+            spawns: Vec::new(),
+            hazards: Vec::new(),
+            force_zones: Vec::new(),
+            pickup_spots: Vec::new(),
+            pickup_table: None,
+            capture_zone: None,
+            round_hazard: None,
+            background_color: None,
+            background_layers: Vec::new(),
+        }
+    }
+
+    /// Parse an arena from TOML text
+    pub fn from_toml_str(text: &str) -> Result<Self, ArenaFileError> {
+        let file: ArenaFile = toml::from_str(text)?;
+        if file.version != ARENA_FILE_VERSION {
+            return Err(ArenaFileError::UnsupportedVersion(file.version));
+        }
+        Ok(file)
+    }
+
+    /// Serialize the arena to TOML text
+    pub fn to_toml_string(&self) -> Result<String, ArenaFileError> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    /// Load an arena from a file
+    pub fn load(path: &Path) -> Result<Self, ArenaFileError> {
+        let text = std::fs::read_to_string(path)?;
+        Self::from_toml_str(&text)
+    }
+
+    /// Save the arena to a file, creating parent directories as needed
+    pub fn save(&self, path: &Path) -> Result<(), ArenaFileError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.to_toml_string()?)?;
+        Ok(())
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_tint() -> [f32; 4] {
+    [1.0; 4]
+}
+
+fn is_zero(value: &f32) -> bool {
+    *value == 0.0
+}
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+fn default_pickup_interval() -> f32 {
+    15.0
+}
+
+fn default_max_active() -> usize {
+    2
+}
+
+fn default_move_interval() -> f32 {
+    20.0
+}
+
+fn default_round_hazard_start() -> f32 {
+    90.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut arena = ArenaFile::new("Test");
+        arena.platforms.push(PlatformDef {
+            position: [0.0, -5.0],
+            size: [20.0, 1.0],
+            one_way: false,
+            health: None,
+            conveyor_speed: 0.0,
+        });
+        arena.platforms.push(PlatformDef {
+            position: [3.0, 1.0],
+            size: [4.0, 0.5],
+            one_way: true,
+            health: Some(50),
+            conveyor_speed: -2.5,
+        });
+        arena.spawns.push(SpawnDef {
+            position: [0.0, 2.0],
+        });
+        arena.hazards.push(HazardDef {
+            kind: HazardKind::Lava,
+            position: [5.0, -4.0],
+            size: [2.0, 0.5],
+        });
+
+        arena.force_zones.push(ForceZoneDef {
+            position: [-6.0, 0.0],
+            size: [2.0, 8.0],
+            force: [0.0, 30.0],
+        });
+        arena.pickup_spots.push(PickupSpotDef {
+            position: [0.0, 3.0],
+        });
+        arena.pickup_table = Some(PickupTableDef::default());
+        arena.capture_zone = Some(CaptureZoneDef {
+            size: [4.0, 3.0],
+            positions: vec![[0.0, -3.0], [8.0, 1.0]],
+            move_interval: 15.0,
+        });
+        arena.round_hazard = Some(RoundHazardDef {
+            kind: RoundHazardMode::ClosingBlastZones,
+            start_time: 60.0,
+            water_levels: None,
+            final_bounds: Some(BoundsDef {
+                min: [-8.0, -4.0],
+                max: [8.0, 8.0],
+            }),
+        });
+        arena.background_color = Some([0.05, 0.05, 0.1, 1.0]);
+        arena.background_layers.push(BackgroundLayerDef {
+            image: Some("textures/backgrounds/hills.png".to_string()),
+            parallax: 0.4,
+            size: [20.0, 8.0],
+            offset: [0.0, -2.0],
+            repeat_x: true,
+            repeat_y: false,
+            tint: [0.5, 0.6, 0.7, 1.0],
+        });
+
+        let text = arena.to_toml_string().unwrap();
+        assert!(text.contains("kind = \"lava\""));
+        assert!(text.contains("kind = \"extra_jump\""));
+        assert!(text.contains("kind = \"closing_blast_zones\""));
+        assert_eq!(ArenaFile::from_toml_str(&text).unwrap(), arena);
+    }
+
+    #[test]
+    fn test_minimal_file_uses_defaults() {
+        let arena = ArenaFile::from_toml_str(
+            "version = 1\n[[platforms]]\nposition = [0.0, 0.0]\nsize = [2.0, 1.0]\n",
+        )
+        .unwrap();
+
+        assert_eq!(arena.platforms.len(), 1);
+        assert!(!arena.platforms[0].one_way);
+        assert!(arena.spawns.is_empty());
+        assert_eq!(arena.round_hazard, None);
+    }
+
+    #[test]
+    fn test_round_hazard_defaults() {
+        let arena =
+            ArenaFile::from_toml_str("version = 1\n[round_hazard]\nkind = \"rising_water\"\n")
+                .unwrap();
+
+        let hazard = arena.round_hazard.unwrap();
+        assert_eq!(hazard.kind, RoundHazardMode::RisingWater);
+        assert_eq!(hazard.start_time, 90.0);
+        assert_eq!(hazard.water_levels, None);
+    }
+
+    #[test]
+    fn test_pickup_table_defaults() {
+        let arena = ArenaFile::from_toml_str(
+            "version = 1\n[pickup_table]\nentries = [{ kind = \"speed\" }]\n",
+        )
+        .unwrap();
+
+        let table = arena.pickup_table.unwrap();
+        assert_eq!(table.interval, 15.0);
+        assert_eq!(table.max_active, 2);
+        assert_eq!(table.entries[0].kind, PickupKind::Speed);
+        assert_eq!(table.entries[0].weight, 1.0);
+
+        // The built-in table only spawns pickups that have an effect
+        let default = PickupTableDef::default();
+        assert!(default
+            .entries
+            .iter()
+            .all(|entry| entry.kind != PickupKind::AbilitySwap));
+    }
+
+    #[test]
+    fn test_background_layer_defaults() {
+        let arena = ArenaFile::from_toml_str(
+            "version = 1\n[[background_layers]]\nparallax = 0.1\nsize = [10.0, 5.0]\n",
+        )
+        .unwrap();
+
+        let layer = &arena.background_layers[0];
+        assert_eq!(layer.image, None);
+        assert!(layer.repeat_x && !layer.repeat_y);
+        assert_eq!(layer.offset, [0.0, 0.0]);
+        assert_eq!(layer.tint, [1.0; 4]);
+        assert_eq!(arena.background_color, None);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let result = ArenaFile::from_toml_str("version = 99\n");
+        assert!(matches!(
+            result,
+            Err(ArenaFileError::UnsupportedVersion(99))
+        ));
+    }
+}
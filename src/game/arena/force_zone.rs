@@ -0,0 +1,280 @@
+// Force zones: regions that push bodies inside them (updrafts, crosswinds, fans)
+//
+// Zones are sensors that apply a constant acceleration to every dynamic body
+// overlapping them. Characters get the horizontal part as drift on top of
+// their movement (see `Character::apply_environment_force`), so holding a
+// direction fights the wind instead of cancelling it.
+
+use super::bounds::ArenaBounds;
+use super::file::ForceZoneDef;
+use crate::engine::physics::{
+    body::presets, ColliderHandle, PhysicsWorld, RigidBodyHandle, Vector,
+};
+use crate::game::characters::CharacterManager;
+use glam::Vec2;
+use std::collections::HashSet;
+
+/// A region applying a constant force
+#[derive(Debug)]
+pub struct ForceZone {
+    /// Area covered by the zone
+    pub bounds: ArenaBounds,
+    /// Acceleration applied inside the zone (units/second²)
+    pub force: Vec2,
+    pub body_handle: RigidBodyHandle,
+    pub collider_handle: ColliderHandle,
+    /// Whether the zone was built from the arena file
+    from_arena: bool,
+}
+
+/// Owns all force zones and applies them each step
+#[derive(Debug, Default)]
+pub struct ForceZoneManager {
+    zones: Vec<ForceZone>,
+    /// Definitions the current zones were built from
+    arena_defs: Vec<ForceZoneDef>,
+}
+
+impl ForceZoneManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a force zone
+    pub fn spawn_zone(&mut self, physics: &mut PhysicsWorld, bounds: ArenaBounds, force: Vec2) {
+        self.insert(physics, bounds, force, false);
+    }
+
+    fn insert(
+        &mut self,
+        physics: &mut PhysicsWorld,
+        bounds: ArenaBounds,
+        force: Vec2,
+        from_arena: bool,
+    ) {
+        let center = bounds.center();
+        let size = bounds.size();
+        let body_handle = physics.add_rigid_body(presets::platform_body(center.x, center.y));
+        let collider_handle =
+            physics.add_collider(presets::sensor_collider(size.x, size.y), body_handle);
+        self.zones.push(ForceZone {
+            bounds,
+            force,
+            body_handle,
+            collider_handle,
+            from_arena,
+        });
+    }
+
+    /// Rebuild the zones from an arena file if its definitions changed
+    pub fn sync_arena(&mut self, physics: &mut PhysicsWorld, defs: &[ForceZoneDef]) {
+        if self.arena_defs == defs {
+            return;
+        }
+
+        self.zones.retain(|zone| {
+            if zone.from_arena {
+                physics.remove_rigid_body(zone.body_handle);
+            }
+            !zone.from_arena
+        });
+        for def in defs {
+            let bounds = ArenaBounds::from_center(Vec2::from(def.position), Vec2::from(def.size));
+            self.insert(physics, bounds, Vec2::from(def.force), true);
+        }
+        self.arena_defs = defs.to_vec();
+    }
+
+    /// Get all force zones
+    pub fn zones(&self) -> &[ForceZone] {
+        &self.zones
+    }
+
+    /// Push every dynamic body inside a zone
+    /// Call before updating characters so their movement includes this step's drift
+    pub fn update(&self, physics: &mut PhysicsWorld, characters: &mut CharacterManager, dt: f32) {
+        for zone in &self.zones {
+            let force = Vector::new(zone.force.x, zone.force.y);
+
+            // A body with several colliders in the zone is only pushed once
+            let bodies: HashSet<RigidBodyHandle> = physics
+                .intersecting_colliders(zone.collider_handle)
+                .into_iter()
+                .filter_map(|collider| physics.get_collider(collider)?.parent())
+                .collect();
+
+            for body_handle in bodies {
+                if let Some(character) = characters
+                    .all_mut()
+                    .iter_mut()
+                    .find(|c| c.body_handle == body_handle)
+                {
+                    character.apply_environment_force(physics, force, dt);
+                    continue;
+                }
+
+                if let Some(body) = physics.get_rigid_body_mut(body_handle) {
+                    if body.is_dynamic() {
+                        let velocity = *body.linvel() + force * dt;
+                        body.set_linvel(velocity, true);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Remove all force zones
+    pub fn clear(&mut self, physics: &mut PhysicsWorld) {
+        for zone in self.zones.drain(..) {
+            physics.remove_rigid_body(zone.body_handle);
+        }
+        self.arena_defs.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::characters::{CharacterId, CharacterStats};
+
+    fn test_world() -> (PhysicsWorld, CharacterManager, CharacterId, ColliderHandle) {
+        let mut physics = PhysicsWorld::new();
+        let floor = physics.add_rigid_body(presets::platform_body(0.0, -5.0));
+        let floor_collider = physics.add_collider(presets::platform_collider(60.0, 1.0), floor);
+
+        let mut characters = CharacterManager::new();
+        let id = characters.spawn_character(
+            "Drifter",
+            Some(0),
+            CharacterStats::standard(),
+            &mut physics,
+            0.0,
+            -3.5,
+        );
+        (physics, characters, id, floor_collider)
+    }
+
+    fn run(
+        physics: &mut PhysicsWorld,
+        characters: &mut CharacterManager,
+        zones: &ForceZoneManager,
+        frames: usize,
+    ) {
+        let dt = 1.0 / 60.0;
+        for _ in 0..frames {
+            zones.update(physics, characters, dt);
+            characters.update(physics, dt);
+            physics.step();
+        }
+    }
+
+    fn x_position(physics: &PhysicsWorld, characters: &CharacterManager, id: CharacterId) -> f32 {
+        characters.get(id).unwrap().position(physics).unwrap().0
+    }
+
+    #[test]
+    fn test_updraft_lifts_character() {
+        let (mut physics, mut characters, id, _) = test_world();
+        let mut zones = ForceZoneManager::new();
+        zones.spawn_zone(
+            &mut physics,
+            ArenaBounds::new(Vec2::new(-2.0, -5.0), Vec2::new(2.0, 10.0)),
+            Vec2::new(0.0, 25.0),
+        );
+
+        run(&mut physics, &mut characters, &zones, 60);
+        let (_, y) = characters.get(id).unwrap().position(&physics).unwrap();
+        assert!(y > 0.0, "Updraft should lift the character, y = {}", y);
+    }
+
+    #[test]
+    fn test_crosswind_composes_with_movement() {
+        let (mut physics, mut characters, id, _) = test_world();
+        let mut zones = ForceZoneManager::new();
+        zones.spawn_zone(
+            &mut physics,
+            ArenaBounds::new(Vec2::new(-30.0, -5.0), Vec2::new(30.0, 5.0)),
+            Vec2::new(8.0, 0.0),
+        );
+
+        // Standing still, the wind pushes the character along
+        run(&mut physics, &mut characters, &zones, 60);
+        let drifted = x_position(&physics, &characters, id);
+        assert!(
+            drifted > 1.0,
+            "Wind should push the character, x = {}",
+            drifted
+        );
+
+        // Walking into the wind is slower than walking with it
+        let start = x_position(&physics, &characters, id);
+        for _ in 0..30 {
+            characters.get_mut(id).unwrap().input_horizontal = -1.0;
+            run(&mut physics, &mut characters, &zones, 1);
+        }
+        let against = start - x_position(&physics, &characters, id);
+
+        let start = x_position(&physics, &characters, id);
+        for _ in 0..30 {
+            characters.get_mut(id).unwrap().input_horizontal = 1.0;
+            run(&mut physics, &mut characters, &zones, 1);
+        }
+        let with = x_position(&physics, &characters, id) - start;
+
+        assert!(against > 0.0 && with > against + 1.0);
+    }
+
+    #[test]
+    fn test_conveyor_carries_characters_and_bodies() {
+        let (mut physics, mut characters, id, floor) = test_world();
+        physics.set_conveyor_speed(floor, 3.0);
+
+        let crate_body = physics.add_rigid_body(presets::debris_body(-5.0, -4.0, 0.0, 0.0, 0.0));
+        physics.add_collider(presets::debris_collider(0.8, 0.8), crate_body);
+
+        run(&mut physics, &mut characters, &ForceZoneManager::new(), 60);
+
+        let x = x_position(&physics, &characters, id);
+        assert!(
+            (x - 3.0).abs() < 0.5,
+            "Rider should move with the belt, x = {}",
+            x
+        );
+        let crate_x = physics.get_rigid_body(crate_body).unwrap().translation().x;
+        assert!(
+            crate_x > -4.0,
+            "Crate should move with the belt, x = {}",
+            crate_x
+        );
+
+        // Walking against the belt at full speed still makes progress
+        characters.get_mut(id).unwrap().input_horizontal = -1.0;
+        run(&mut physics, &mut characters, &ForceZoneManager::new(), 30);
+        assert!(x_position(&physics, &characters, id) < x);
+    }
+
+    #[test]
+    fn test_sync_arena() {
+        let mut physics = PhysicsWorld::new();
+        let mut zones = ForceZoneManager::new();
+        let defs = [ForceZoneDef {
+            position: [0.0, 0.0],
+            size: [2.0, 4.0],
+            force: [0.0, 20.0],
+        }];
+
+        zones.spawn_zone(
+            &mut physics,
+            ArenaBounds::new(Vec2::new(5.0, 0.0), Vec2::new(6.0, 1.0)),
+            Vec2::new(10.0, 0.0),
+        );
+        zones.sync_arena(&mut physics, &defs);
+        assert_eq!(zones.zones().len(), 2);
+        assert_eq!(zones.zones()[1].bounds.size(), Vec2::new(2.0, 4.0));
+
+        // Zones added in code survive re-syncing
+        zones.sync_arena(&mut physics, &[]);
+        assert_eq!(zones.zones().len(), 1);
+        assert_eq!(zones.zones()[0].force, Vec2::new(10.0, 0.0));
+    }
+}
@@ -0,0 +1,465 @@
+// Hazard volumes (spikes, lava, saws)
+//
+// Hazards are sensor colliders in `CollisionGroups::Hazard`. They can sit on
+// their own fixed body or be attached to any existing body (e.g. a moving
+// platform), in which case they travel with it.
+
+use super::bounds::ArenaBounds;
+use super::file::{HazardDef, HazardKind};
+use crate::engine::physics::{
+    body::presets, ColliderHandle, PhysicsWorld, RigidBodyHandle, Vector,
+};
+use crate::game::characters::{CharacterId, CharacterManager, DamageSource, Hit};
+use glam::Vec2;
+use std::collections::HashMap;
+
+/// Unique identifier for hazards
+pub type HazardId = u32;
+
+/// How long before a knockback hazard can hit the same character again (seconds)
+const KNOCKBACK_REHIT_TIME: f32 = 0.5;
+
+/// What a hazard does to characters touching it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HazardEffect {
+    /// Kill on contact
+    InstantKill,
+    /// Steady damage while touching, without hit stun
+    DamageOverTime { damage_per_second: f32 },
+    /// A hit that launches the character away from the hazard surface
+    DamageKnockback { damage: i32, knockback: f32 },
+}
+
+impl HazardEffect {
+    /// Get the default effect for a kind of hazard
+    pub fn for_kind(kind: HazardKind) -> Self {
+        match kind {
+            HazardKind::Spikes => HazardEffect::DamageKnockback {
+                damage: 15,
+                knockback: 14.0,
+            },
+            HazardKind::Lava => HazardEffect::DamageOverTime {
+                damage_per_second: 40.0,
+            },
+            HazardKind::Saw => HazardEffect::InstantKill,
+        }
+    }
+}
+
+/// A hazard volume
+#[derive(Debug)]
+pub struct Hazard {
+    /// Unique identifier
+    pub id: HazardId,
+    /// What kind of hazard this is (used for display)
+    pub kind: HazardKind,
+    /// What touching it does
+    pub effect: HazardEffect,
+    /// Body the hazard is attached to
+    pub body_handle: RigidBodyHandle,
+    /// The hazard's sensor collider
+    pub collider_handle: ColliderHandle,
+    /// Whether the hazard created its body (and removes it with the hazard)
+    owns_body: bool,
+}
+
+impl Hazard {
+    /// Get the hazard's current world-space bounding box
+    pub fn bounds(&self, physics: &PhysicsWorld) -> Option<ArenaBounds> {
+        physics.get_collider(self.collider_handle).map(|collider| {
+            let aabb = collider.compute_aabb();
+            ArenaBounds::new(
+                Vec2::new(aabb.mins.x, aabb.mins.y),
+                Vec2::new(aabb.maxs.x, aabb.maxs.y),
+            )
+        })
+    }
+}
+
+/// A character killed by a hazard
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HazardKill {
+    /// The hazard that did it
+    pub hazard: HazardId,
+    pub kind: HazardKind,
+    /// The character that died
+    pub victim: CharacterId,
+    /// Whoever knocked the victim in, if anyone hit them recently
+    pub credited_to: Option<CharacterId>,
+}
+
+/// Owns all hazards and applies their effects to characters
+#[derive(Debug, Default)]
+pub struct HazardManager {
+    hazards: Vec<Hazard>,
+    next_id: HazardId,
+    /// Hazards built from the arena file, and the definitions they came from
+    arena_hazards: Vec<HazardId>,
+    arena_defs: Vec<HazardDef>,
+    /// Fractional damage owed by damage-over-time hazards
+    pending_damage: HashMap<(HazardId, CharacterId), f32>,
+    /// Time left before a knockback hazard can hit a character again
+    rehit_timers: HashMap<(HazardId, CharacterId), f32>,
+}
+
+impl HazardManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a hazard on its own fixed body
+    pub fn spawn_hazard(
+        &mut self,
+        physics: &mut PhysicsWorld,
+        kind: HazardKind,
+        effect: HazardEffect,
+        center: Vec2,
+        size: Vec2,
+    ) -> HazardId {
+        let body_handle = physics.add_rigid_body(presets::platform_body(center.x, center.y));
+        let collider_handle =
+            physics.add_collider(presets::hazard_collider(size.x, size.y), body_handle);
+        self.insert(kind, effect, body_handle, collider_handle, true)
+    }
+
+    /// Attach a hazard to an existing body (it moves with the body)
+    /// `offset` is relative to the body's origin
+    pub fn attach_hazard(
+        &mut self,
+        physics: &mut PhysicsWorld,
+        body_handle: RigidBodyHandle,
+        kind: HazardKind,
+        effect: HazardEffect,
+        offset: Vec2,
+        size: Vec2,
+    ) -> HazardId {
+        let mut collider = presets::hazard_collider(size.x, size.y);
+        // Before insertion, the collider's position is taken relative to its parent
+        collider.set_translation(Vector::new(offset.x, offset.y));
+        let collider_handle = physics.add_collider(collider, body_handle);
+        self.insert(kind, effect, body_handle, collider_handle, false)
+    }
+
+    fn insert(
+        &mut self,
+        kind: HazardKind,
+        effect: HazardEffect,
+        body_handle: RigidBodyHandle,
+        collider_handle: ColliderHandle,
+        owns_body: bool,
+    ) -> HazardId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.hazards.push(Hazard {
+            id,
+            kind,
+            effect,
+            body_handle,
+            collider_handle,
+            owns_body,
+        });
+        id
+    }
+
+    /// Remove a hazard (and its body, if it created one)
+    pub fn remove(&mut self, physics: &mut PhysicsWorld, id: HazardId) {
+        let Some(index) = self.hazards.iter().position(|h| h.id == id) else {
+            return;
+        };
+        let hazard = self.hazards.remove(index);
+        if hazard.owns_body {
+            physics.remove_rigid_body(hazard.body_handle);
+        } else {
+            physics.remove_collider(hazard.collider_handle);
+        }
+        self.pending_damage
+            .retain(|(hazard_id, _), _| *hazard_id != id);
+        self.rehit_timers
+            .retain(|(hazard_id, _), _| *hazard_id != id);
+    }
+
+    /// Keep the hazards from an arena file in sync with its definitions
+    /// Only rebuilds when the definitions changed (e.g. in the editor)
+    pub fn sync_arena(&mut self, physics: &mut PhysicsWorld, defs: &[HazardDef]) {
+        if self.arena_defs == defs {
+            return;
+        }
+
+        for id in std::mem::take(&mut self.arena_hazards) {
+            self.remove(physics, id);
+        }
+        self.arena_hazards = defs
+            .iter()
+            .map(|def| {
+                self.spawn_hazard(
+                    physics,
+                    def.kind,
+                    HazardEffect::for_kind(def.kind),
+                    Vec2::from(def.position),
+                    Vec2::from(def.size),
+                )
+            })
+            .collect();
+        self.arena_defs = defs.to_vec();
+    }
+
+    /// Get a hazard by ID
+    pub fn get(&self, id: HazardId) -> Option<&Hazard> {
+        self.hazards.iter().find(|h| h.id == id)
+    }
+
+    /// Get all hazards
+    pub fn hazards(&self) -> &[Hazard] {
+        &self.hazards
+    }
+
+    /// Apply hazard effects to every character touching a hazard
+    /// Call after stepping physics so overlaps are up to date
+    /// Returns the characters killed this frame
+    pub fn update(
+        &mut self,
+        physics: &mut PhysicsWorld,
+        characters: &mut CharacterManager,
+        dt: f32,
+    ) -> Vec<HazardKill> {
+        let mut kills = Vec::new();
+        let mut pending_damage = HashMap::new();
+
+        for timer in self.rehit_timers.values_mut() {
+            *timer -= dt;
+        }
+        self.rehit_timers.retain(|_, timer| *timer > 0.0);
+
+        for hazard in &self.hazards {
+            for other in physics.intersecting_colliders(hazard.collider_handle) {
+                let Some(character) = characters
+                    .all_mut()
+                    .iter_mut()
+                    .find(|c| c.collider_handle == other)
+                else {
+                    continue;
+                };
+                if !character.is_alive() || character.is_spawn_protected() {
+                    continue;
+                }
+                let key = (hazard.id, character.id);
+                let source = DamageSource::hazard(hazard.kind);
+
+                match hazard.effect {
+                    HazardEffect::InstantKill => {
+                        character.take_chip_damage(character.health, source);
+                    }
+                    HazardEffect::DamageOverTime { damage_per_second } => {
+                        let owed = self.pending_damage.get(&key).copied().unwrap_or(0.0)
+                            + damage_per_second * dt;
+                        let damage = owed.floor();
+                        character.take_chip_damage(damage as i32, source);
+                        pending_damage.insert(key, owed - damage);
+                    }
+                    HazardEffect::DamageKnockback { damage, knockback } => {
+                        if self.rehit_timers.contains_key(&key) {
+                            continue;
+                        }
+                        self.rehit_timers.insert(key, KNOCKBACK_REHIT_TIME);
+
+                        let Some((x, y)) = character.position(physics) else {
+                            continue;
+                        };
+                        let normal = physics
+                            .surface_normal(hazard.collider_handle, Vector::new(x, y))
+                            .unwrap_or_else(|| Vector::new(0.0, 1.0));
+                        let launch = normal * knockback;
+                        let hit =
+                            Hit::new(damage, source).with_knockback(Vec2::new(launch.x, launch.y));
+                        character.take_damage(physics, hit);
+                    }
+                }
+
+                if !character.is_alive() {
+                    kills.push(HazardKill {
+                        hazard: hazard.id,
+                        kind: hazard.kind,
+                        victim: character.id,
+                        credited_to: character.last_attacker(),
+                    });
+                }
+            }
+        }
+
+        // Characters that left a damage-over-time hazard stop owing damage
+        self.pending_damage = pending_damage;
+        kills
+    }
+
+    /// Remove all hazards
+    pub fn clear(&mut self, physics: &mut PhysicsWorld) {
+        for id in self.hazards.iter().map(|h| h.id).collect::<Vec<_>>() {
+            self.remove(physics, id);
+        }
+        self.arena_hazards.clear();
+        self.arena_defs.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::arena::{MovingPlatform, WaypointPath};
+    use crate::game::characters::{CharacterState, CharacterStats};
+
+    fn test_world(x: f32, y: f32) -> (PhysicsWorld, CharacterManager, CharacterId) {
+        let mut physics = PhysicsWorld::new();
+        let floor = physics.add_rigid_body(presets::platform_body(0.0, -5.0));
+        physics.add_collider(presets::platform_collider(40.0, 1.0), floor);
+
+        let mut stats = CharacterStats::standard();
+        stats.spawn_protection_time = 0.0;
+        let mut characters = CharacterManager::new();
+        let id = characters.spawn_character("Victim", Some(0), stats, &mut physics, x, y);
+        (physics, characters, id)
+    }
+
+    fn run(
+        physics: &mut PhysicsWorld,
+        characters: &mut CharacterManager,
+        hazards: &mut HazardManager,
+        frames: usize,
+    ) -> Vec<HazardKill> {
+        let dt = 1.0 / 60.0;
+        let mut kills = Vec::new();
+        for _ in 0..frames {
+            characters.update(physics, dt);
+            physics.step();
+            kills.extend(hazards.update(physics, characters, dt));
+        }
+        kills
+    }
+
+    #[test]
+    fn test_instant_kill_credits_attacker() {
+        let (mut physics, mut characters, id) = test_world(0.0, -3.5);
+        let mut hazards = HazardManager::new();
+        let saw = hazards.spawn_hazard(
+            &mut physics,
+            HazardKind::Saw,
+            HazardEffect::InstantKill,
+            Vec2::new(0.0, -4.0),
+            Vec2::new(2.0, 1.0),
+        );
+        characters.get_mut(id).unwrap().register_hit(42);
+
+        let kills = run(&mut physics, &mut characters, &mut hazards, 5);
+        assert_eq!(
+            kills,
+            vec![HazardKill {
+                hazard: saw,
+                kind: HazardKind::Saw,
+                victim: id,
+                credited_to: Some(42),
+            }]
+        );
+        assert!(!characters.get(id).unwrap().is_alive());
+    }
+
+    #[test]
+    fn test_damage_over_time() {
+        let (mut physics, mut characters, id) = test_world(0.0, -3.5);
+        let mut hazards = HazardManager::new();
+        hazards.spawn_hazard(
+            &mut physics,
+            HazardKind::Lava,
+            HazardEffect::DamageOverTime {
+                damage_per_second: 30.0,
+            },
+            Vec2::new(0.0, -4.0),
+            Vec2::new(4.0, 1.0),
+        );
+
+        run(&mut physics, &mut characters, &mut hazards, 60);
+        let character = characters.get(id).unwrap();
+        let lost = character.stats.max_health - character.health;
+        assert!((28..=30).contains(&lost), "Lost {} health", lost);
+        assert_ne!(character.state(), CharacterState::HitStun);
+    }
+
+    #[test]
+    fn test_knockback_away_from_surface() {
+        let (mut physics, mut characters, id) = test_world(0.0, -2.0);
+        let mut hazards = HazardManager::new();
+        hazards.spawn_hazard(
+            &mut physics,
+            HazardKind::Spikes,
+            HazardEffect::DamageKnockback {
+                damage: 10,
+                knockback: 12.0,
+            },
+            Vec2::new(0.0, -4.25),
+            Vec2::new(4.0, 0.5),
+        );
+
+        let mut launched = false;
+        for _ in 0..60 {
+            run(&mut physics, &mut characters, &mut hazards, 1);
+            let (_, vy) = characters.get(id).unwrap().velocity(&physics).unwrap();
+            launched |= vy > 8.0;
+        }
+
+        let character = characters.get(id).unwrap();
+        assert!(launched, "Spikes should launch the character upwards");
+        assert!(character.health < character.stats.max_health);
+    }
+
+    #[test]
+    fn test_hazard_on_moving_platform() {
+        let (mut physics, mut characters, id) = test_world(6.0, -3.5);
+        let mut hazards = HazardManager::new();
+
+        // A saw hanging under a platform that sweeps across the character
+        let path = WaypointPath::new(vec![Vec2::new(0.0, -1.5), Vec2::new(12.0, -1.5)], 6.0);
+        let mut platform = MovingPlatform::new(&mut physics, path, 2.0, 0.25);
+        hazards.attach_hazard(
+            &mut physics,
+            platform.body_handle,
+            HazardKind::Saw,
+            HazardEffect::InstantKill,
+            Vec2::new(0.0, -1.0),
+            Vec2::new(1.0, 1.0),
+        );
+
+        for _ in 0..120 {
+            platform.update(&mut physics, 1.0 / 60.0);
+            run(&mut physics, &mut characters, &mut hazards, 1);
+        }
+
+        assert!(!characters.get(id).unwrap().is_alive());
+    }
+
+    #[test]
+    fn test_sync_arena() {
+        let mut physics = PhysicsWorld::new();
+        let mut hazards = HazardManager::new();
+        let mut defs = vec![HazardDef {
+            kind: HazardKind::Lava,
+            position: [0.0, 0.0],
+            size: [2.0, 1.0],
+        }];
+
+        hazards.sync_arena(&mut physics, &defs);
+        let first = hazards.hazards()[0].id;
+        hazards.sync_arena(&mut physics, &defs);
+        assert_eq!(
+            hazards.hazards()[0].id,
+            first,
+            "Unchanged defs don't rebuild"
+        );
+
+        defs[0].position = [3.0, 0.0];
+        hazards.sync_arena(&mut physics, &defs);
+        assert_eq!(hazards.hazards().len(), 1);
+        assert_ne!(hazards.hazards()[0].id, first);
+        assert_eq!(
+            hazards.hazards()[0].effect,
+            HazardEffect::for_kind(HazardKind::Lava)
+        );
+    }
+}
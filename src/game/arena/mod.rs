@@ -1,3 +1,43 @@
 // Arena/stage system
+//
+// This module contains everything related to the arena the fight happens in:
+// - Moving platforms following waypoint paths
+// - Destructible platforms that fracture into debris
+// - Tilemap arenas with merged collision shapes
+// - Hazard volumes (spikes, lava, saws)
+// - Force zones (updrafts, crosswinds, fans) and conveyor surfaces
+// - Pickups spawned on timers from per-arena spawn tables
+// - The king-of-the-hill capture zone moving between arena-defined spots
+// - Blast zones and round hazards that close in on long rounds
+// - The arena file format and the in-game arena editor
+// - Spawn point selection away from enemies and hazards
 
-// TODO: Implement arena system
+pub mod bounds;
+pub mod capture_zone;
+pub mod destructible;
+pub mod editor;
+pub mod file;
+pub mod force_zone;
+pub mod hazard;
+pub mod pickup;
+pub mod platform;
+pub mod round_hazard;
+pub mod spawn;
+pub mod tilemap;
+
+// Re-export commonly used types
+pub use bounds::ArenaBounds;
+pub use capture_zone::CaptureZone;
+pub use destructible::{DestructibleId, DestructibleManager, DestructiblePlatform};
+pub use editor::{ArenaEditor, EditorTool};
+pub use file::{
+    ArenaFile, ArenaFileError, CaptureZoneDef, HazardKind, PickupKind, PickupTableDef,
+    RoundHazardDef,
+};
+pub use force_zone::{ForceZone, ForceZoneManager};
+pub use hazard::{Hazard, HazardEffect, HazardId, HazardKill, HazardManager};
+pub use pickup::{CollectFlash, Pickup, PickupCollected, PickupManager};
+pub use platform::{Easing, MovingPlatform, PathMode, WaypointPath};
+pub use round_hazard::{HazardAnnouncement, RoundHazard, RoundHazardConfig};
+pub use spawn::SpawnContext;
+pub use tilemap::{TileColliderMode, TileCollision, TileDef, TileSet, Tilemap};
@@ -0,0 +1,337 @@
+// Collectible pickups spawned on a timer at arena-defined spots
+//
+// Each arena has a spawn table (how often pickups appear and the relative
+// chance of each kind) and a list of spots. Pickups are sensors in
+// `CollisionGroups::Pickup`; touching one applies its effect and despawns it.
+
+use super::file::{PickupKind, PickupTableDef};
+use crate::engine::physics::{body::presets, ColliderHandle, PhysicsWorld, RigidBodyHandle};
+use crate::game::characters::{Character, CharacterId, CharacterManager};
+use glam::Vec2;
+
+/// Radius of a pickup's collider (world units)
+pub const PICKUP_RADIUS: f32 = 0.4;
+
+/// Health restored by a health pickup
+const HEALTH_AMOUNT: i32 = 40;
+
+/// Speed multiplier and duration of a speed pickup
+const SPEED_MULTIPLIER: f32 = 1.5;
+const SPEED_DURATION: f32 = 6.0;
+
+/// How long the collect flash is shown (seconds)
+const FLASH_DURATION: f32 = 0.4;
+
+/// A pickup waiting to be collected
+#[derive(Debug)]
+pub struct Pickup {
+    pub kind: PickupKind,
+    /// Index of the spot it sits on
+    pub spot: usize,
+    pub position: Vec2,
+    pub body_handle: RigidBodyHandle,
+    pub collider_handle: ColliderHandle,
+}
+
+/// A pickup that was just collected
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PickupCollected {
+    pub kind: PickupKind,
+    pub character: CharacterId,
+    pub position: Vec2,
+}
+
+/// Short-lived visual feedback where a pickup was collected
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollectFlash {
+    pub kind: PickupKind,
+    pub position: Vec2,
+    /// Time since collection (seconds)
+    pub age: f32,
+}
+
+impl CollectFlash {
+    /// Get how far the flash has faded (0.0 = just collected, 1.0 = gone)
+    pub fn progress(&self) -> f32 {
+        (self.age / FLASH_DURATION).min(1.0)
+    }
+}
+
+/// Spawns pickups and applies them to characters
+#[derive(Debug)]
+pub struct PickupManager {
+    table: PickupTableDef,
+    spots: Vec<Vec2>,
+    pickups: Vec<Pickup>,
+    flashes: Vec<CollectFlash>,
+    /// Time until the next spawn attempt (seconds)
+    spawn_timer: f32,
+    rng: fastrand::Rng,
+}
+
+impl PickupManager {
+    /// Create a manager for an arena's spawn table and spots
+    pub fn new(table: PickupTableDef, spots: Vec<Vec2>) -> Self {
+        Self {
+            spawn_timer: table.interval,
+            table,
+            spots,
+            pickups: Vec::new(),
+            flashes: Vec::new(),
+            rng: fastrand::Rng::new(),
+        }
+    }
+
+    /// Use a fixed random seed (for deterministic spawns)
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = fastrand::Rng::with_seed(seed);
+        self
+    }
+
+    /// Get the pickups waiting to be collected
+    pub fn pickups(&self) -> &[Pickup] {
+        &self.pickups
+    }
+
+    /// Get the active collect flashes
+    pub fn flashes(&self) -> &[CollectFlash] {
+        &self.flashes
+    }
+
+    /// Spawn timers, collection and feedback
+    /// Call after stepping physics so overlaps are up to date
+    /// Returns the pickups collected this frame
+    pub fn update(
+        &mut self,
+        physics: &mut PhysicsWorld,
+        characters: &mut CharacterManager,
+        dt: f32,
+    ) -> Vec<PickupCollected> {
+        for flash in &mut self.flashes {
+            flash.age += dt;
+        }
+        self.flashes.retain(|flash| flash.age < FLASH_DURATION);
+
+        self.spawn_timer -= dt;
+        if self.spawn_timer <= 0.0 {
+            self.spawn_timer += self.table.interval.max(0.1);
+            if self.pickups.len() < self.table.max_active {
+                self.spawn_random(physics);
+            }
+        }
+
+        let mut collected = Vec::new();
+        let mut index = 0;
+        while index < self.pickups.len() {
+            let pickup = &self.pickups[index];
+            let toucher = physics
+                .intersecting_colliders(pickup.collider_handle)
+                .into_iter()
+                .find_map(|other| {
+                    characters
+                        .all()
+                        .iter()
+                        .find(|c| c.collider_handle == other && c.is_alive())
+                        .map(|c| c.id)
+                });
+
+            let Some(character_id) = toucher else {
+                index += 1;
+                continue;
+            };
+
+            let pickup = self.pickups.remove(index);
+            physics.remove_rigid_body(pickup.body_handle);
+            if let Some(character) = characters.get_mut(character_id) {
+                apply_effect(character, pickup.kind);
+            }
+
+            self.flashes.push(CollectFlash {
+                kind: pickup.kind,
+                position: pickup.position,
+                age: 0.0,
+            });
+            collected.push(PickupCollected {
+                kind: pickup.kind,
+                character: character_id,
+                position: pickup.position,
+            });
+        }
+
+        collected
+    }
+
+    /// Spawn a pickup from the table at a random free spot
+    /// Returns false if every spot is taken or the table is empty
+    pub fn spawn_random(&mut self, physics: &mut PhysicsWorld) -> bool {
+        let free: Vec<usize> = (0..self.spots.len())
+            .filter(|spot| !self.pickups.iter().any(|p| p.spot == *spot))
+            .collect();
+        if free.is_empty() {
+            return false;
+        }
+        let Some(kind) = self.choose_kind() else {
+            return false;
+        };
+
+        let spot = free[self.rng.usize(..free.len())];
+        self.spawn_at(physics, kind, spot);
+        true
+    }
+
+    /// Spawn a specific pickup at a spot
+    pub fn spawn_at(&mut self, physics: &mut PhysicsWorld, kind: PickupKind, spot: usize) {
+        let Some(&position) = self.spots.get(spot) else {
+            return;
+        };
+        let body_handle = physics.add_rigid_body(presets::platform_body(position.x, position.y));
+        let collider_handle =
+            physics.add_collider(presets::pickup_collider(PICKUP_RADIUS), body_handle);
+        self.pickups.push(Pickup {
+            kind,
+            spot,
+            position,
+            body_handle,
+            collider_handle,
+        });
+    }
+
+    /// Pick a kind from the spawn table, weighted by each entry's weight
+    fn choose_kind(&mut self) -> Option<PickupKind> {
+        let total: f32 = self
+            .table
+            .entries
+            .iter()
+            .map(|entry| entry.weight.max(0.0))
+            .sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut roll = self.rng.f32() * total;
+        for entry in &self.table.entries {
+            roll -= entry.weight.max(0.0);
+            if roll < 0.0 {
+                return Some(entry.kind);
+            }
+        }
+        // Rounding can leave a sliver of the roll; give it to the last valid entry
+        self.table
+            .entries
+            .iter()
+            .rev()
+            .find(|entry| entry.weight > 0.0)
+            .map(|entry| entry.kind)
+    }
+
+    /// Remove all pickups and restart the spawn timer
+    pub fn clear(&mut self, physics: &mut PhysicsWorld) {
+        for pickup in self.pickups.drain(..) {
+            physics.remove_rigid_body(pickup.body_handle);
+        }
+        self.flashes.clear();
+        self.spawn_timer = self.table.interval;
+    }
+}
+
+/// Apply a pickup's effect to the character that collected it
+fn apply_effect(character: &mut Character, kind: PickupKind) {
+    match kind {
+        PickupKind::Health => character.heal(HEALTH_AMOUNT),
+        PickupKind::Speed => character.apply_speed_boost(SPEED_MULTIPLIER, SPEED_DURATION),
+        PickupKind::ExtraJump => character.bonus_jumps = character.bonus_jumps.saturating_add(1),
+        // Abilities aren't implemented yet; the collection event lets the
+        // ability system perform the swap once it exists
+        PickupKind::AbilitySwap => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::arena::file::PickupEntryDef;
+    use crate::game::characters::CharacterStats;
+
+    fn table(interval: f32, kinds: &[PickupKind]) -> PickupTableDef {
+        PickupTableDef {
+            interval,
+            max_active: 2,
+            entries: kinds
+                .iter()
+                .map(|&kind| PickupEntryDef { kind, weight: 1.0 })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_spawns_on_timer_up_to_max_active() {
+        let mut physics = PhysicsWorld::new();
+        let mut characters = CharacterManager::new();
+        let spots = vec![
+            Vec2::new(-5.0, 0.0),
+            Vec2::new(0.0, 0.0),
+            Vec2::new(5.0, 0.0),
+        ];
+        let mut pickups = PickupManager::new(table(1.0, &[PickupKind::Speed]), spots).with_seed(7);
+
+        pickups.update(&mut physics, &mut characters, 0.5);
+        assert!(pickups.pickups().is_empty());
+
+        for _ in 0..10 {
+            pickups.update(&mut physics, &mut characters, 0.5);
+        }
+        assert_eq!(pickups.pickups().len(), 2);
+        assert_ne!(pickups.pickups()[0].spot, pickups.pickups()[1].spot);
+    }
+
+    #[test]
+    fn test_weighted_table() {
+        let mut table = table(1.0, &[PickupKind::Health, PickupKind::Speed]);
+        table.entries[1].weight = 0.0;
+        let mut pickups = PickupManager::new(table, Vec::new()).with_seed(1);
+
+        for _ in 0..20 {
+            assert_eq!(pickups.choose_kind(), Some(PickupKind::Health));
+        }
+
+        let mut empty = PickupManager::new(self::table(1.0, &[]), Vec::new());
+        assert_eq!(empty.choose_kind(), None);
+    }
+
+    #[test]
+    fn test_collect_applies_effect_and_despawns() {
+        let mut physics = PhysicsWorld::new();
+        let floor = physics.add_rigid_body(presets::platform_body(0.0, -5.0));
+        physics.add_collider(presets::platform_collider(20.0, 1.0), floor);
+
+        let mut characters = CharacterManager::new();
+        let id = characters.spawn_character(
+            "Collector",
+            Some(0),
+            CharacterStats::standard(),
+            &mut physics,
+            0.0,
+            -3.5,
+        );
+
+        let mut pickups = PickupManager::new(
+            table(100.0, &[PickupKind::ExtraJump]),
+            vec![Vec2::new(0.0, -3.5)],
+        );
+        pickups.spawn_at(&mut physics, PickupKind::ExtraJump, 0);
+
+        let mut collected = Vec::new();
+        for _ in 0..5 {
+            characters.update(&mut physics, 1.0 / 60.0);
+            physics.step();
+            collected.extend(pickups.update(&mut physics, &mut characters, 1.0 / 60.0));
+        }
+
+        assert_eq!(collected.len(), 1);
+        assert_eq!(collected[0].kind, PickupKind::ExtraJump);
+        assert_eq!(collected[0].character, id);
+        assert!(pickups.pickups().is_empty());
+        assert_eq!(pickups.flashes().len(), 1);
+        assert_eq!(characters.get(id).unwrap().bonus_jumps, 1);
+    }
+}
@@ -0,0 +1,288 @@
+// Moving platforms that follow waypoint paths
+
+use crate::engine::physics::{
+    body::{presets, BodyBuilder},
+    ColliderHandle, PhysicsWorld, RigidBodyHandle, Vector,
+};
+use glam::Vec2;
+
+/// Easing curve applied to each segment of a waypoint path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Constant speed between waypoints
+    #[default]
+    Linear,
+    /// Start slow, end fast
+    EaseIn,
+    /// Start fast, end slow
+    EaseOut,
+    /// Slow at both ends (smoothstep)
+    EaseInOut,
+}
+
+impl Easing {
+    /// Map a linear progress value (0.0 to 1.0) onto the easing curve
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// How a path continues after reaching its last waypoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathMode {
+    /// Go from the last waypoint straight back to the first one
+    #[default]
+    Loop,
+    /// Reverse direction and walk the waypoints backwards
+    PingPong,
+}
+
+/// A sequence of waypoints traversed at a constant speed
+#[derive(Debug, Clone)]
+pub struct WaypointPath {
+    /// Waypoints in world units
+    pub waypoints: Vec<Vec2>,
+    /// Travel speed along the path (units/second)
+    pub speed: f32,
+    /// What happens at the end of the path
+    pub mode: PathMode,
+    /// Easing applied to every segment
+    pub easing: Easing,
+}
+
+impl WaypointPath {
+    /// Create a new path with linear easing that loops back to the start
+    pub fn new(waypoints: Vec<Vec2>, speed: f32) -> Self {
+        Self {
+            waypoints,
+            speed,
+            mode: PathMode::Loop,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Set the path mode
+    pub fn with_mode(mut self, mode: PathMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the easing curve
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Get the starting point of the path
+    pub fn start(&self) -> Vec2 {
+        self.waypoints.first().copied().unwrap_or(Vec2::ZERO)
+    }
+
+    /// Get the list of segments for one full cycle of the path
+    fn segments(&self) -> Vec<(Vec2, Vec2)> {
+        let points = &self.waypoints;
+        let mut segments: Vec<(Vec2, Vec2)> = points.windows(2).map(|w| (w[0], w[1])).collect();
+
+        match self.mode {
+            PathMode::Loop => {
+                if points.len() > 2 {
+                    segments.push((points[points.len() - 1], points[0]));
+                } else if points.len() == 2 {
+                    // Two points loop the same way as ping-pong
+                    segments.push((points[1], points[0]));
+                }
+            }
+            PathMode::PingPong => {
+                let back: Vec<_> = segments.iter().rev().map(|&(a, b)| (b, a)).collect();
+                segments.extend(back);
+            }
+        }
+
+        segments
+    }
+
+    /// Get the time needed to complete one full cycle (seconds)
+    pub fn cycle_duration(&self) -> f32 {
+        if self.speed <= 0.0 {
+            return 0.0;
+        }
+        self.segments()
+            .iter()
+            .map(|(a, b)| a.distance(*b) / self.speed)
+            .sum()
+    }
+
+    /// Sample the position on the path after `elapsed` seconds
+    pub fn sample(&self, elapsed: f32) -> Vec2 {
+        let cycle = self.cycle_duration();
+        if cycle <= 0.0 {
+            return self.start();
+        }
+
+        let mut time = elapsed.rem_euclid(cycle);
+        for (a, b) in self.segments() {
+            let duration = a.distance(b) / self.speed;
+            if time <= duration {
+                let t = if duration > 0.0 { time / duration } else { 1.0 };
+                return a.lerp(b, self.easing.apply(t));
+            }
+            time -= duration;
+        }
+
+        self.start()
+    }
+}
+
+/// A kinematic platform that moves along a waypoint path
+///
+/// Characters standing on the platform are carried along by
+/// `Character::update_movement`, which adds the ground body's velocity.
+#[derive(Debug)]
+pub struct MovingPlatform {
+    /// Handle to the platform's kinematic body
+    pub body_handle: RigidBodyHandle,
+    /// Handle to the platform's collider
+    pub collider_handle: ColliderHandle,
+    /// Path the platform follows
+    pub path: WaypointPath,
+    /// Platform size in world units
+    pub size: Vec2,
+    /// Time spent travelling along the path
+    elapsed: f32,
+}
+
+impl MovingPlatform {
+    /// Create a moving platform and add it to the physics world
+    pub fn new(physics: &mut PhysicsWorld, path: WaypointPath, width: f32, height: f32) -> Self {
+        let start = path.start();
+        let body = BodyBuilder::new_kinematic_position_based()
+            .position(start.x, start.y)
+            .build();
+        let body_handle = physics.add_rigid_body(body);
+
+        let collider = presets::platform_collider(width, height);
+        let collider_handle = physics.add_collider(collider, body_handle);
+
+        Self {
+            body_handle,
+            collider_handle,
+            path,
+            size: Vec2::new(width, height),
+            elapsed: 0.0,
+        }
+    }
+
+    /// Advance the platform along its path
+    /// Call this before stepping physics so the body reaches its target this step
+    pub fn update(&mut self, physics: &mut PhysicsWorld, dt: f32) {
+        self.elapsed += dt;
+        let target = self.path.sample(self.elapsed);
+
+        if let Some(body) = physics.get_rigid_body_mut(self.body_handle) {
+            body.set_next_kinematic_translation(Vector::new(target.x, target.y));
+        }
+    }
+
+    /// Get the platform's current position
+    pub fn position(&self, physics: &PhysicsWorld) -> Option<Vec2> {
+        physics.get_rigid_body(self.body_handle).map(|body| {
+            let pos = body.translation();
+            Vec2::new(pos.x, pos.y)
+        })
+    }
+
+    /// Restart the platform from the beginning of its path
+    pub fn reset(&mut self, physics: &mut PhysicsWorld) {
+        self.elapsed = 0.0;
+        let start = self.path.start();
+        if let Some(body) = physics.get_rigid_body_mut(self.body_handle) {
+            body.set_translation(Vector::new(start.x, start.y), true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easing_endpoints() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_loop_path_returns_to_start() {
+        let path = WaypointPath::new(
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(4.0, 0.0),
+                Vec2::new(4.0, 3.0),
+            ],
+            1.0,
+        );
+        // 4 + 3 + 5 units at 1 unit/second
+        assert!((path.cycle_duration() - 12.0).abs() < 1e-4);
+        assert!(path.sample(2.0).distance(Vec2::new(2.0, 0.0)) < 1e-4);
+        assert!(path.sample(12.0).distance(Vec2::ZERO) < 1e-4);
+    }
+
+    #[test]
+    fn test_ping_pong_path_reverses() {
+        let path = WaypointPath::new(
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(2.0, 0.0),
+                Vec2::new(4.0, 0.0),
+            ],
+            2.0,
+        )
+        .with_mode(PathMode::PingPong);
+
+        assert!((path.cycle_duration() - 4.0).abs() < 1e-4);
+        assert!(path.sample(2.0).distance(Vec2::new(4.0, 0.0)) < 1e-4);
+        assert!(path.sample(3.0).distance(Vec2::new(2.0, 0.0)) < 1e-4);
+    }
+
+    #[test]
+    fn test_single_waypoint_is_static() {
+        let path = WaypointPath::new(vec![Vec2::new(1.0, 2.0)], 5.0);
+        assert_eq!(path.sample(10.0), Vec2::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn test_platform_follows_path() {
+        let mut physics = PhysicsWorld::new();
+        let path = WaypointPath::new(vec![Vec2::ZERO, Vec2::new(10.0, 0.0)], 6.0);
+        let mut platform = MovingPlatform::new(&mut physics, path, 4.0, 1.0);
+
+        for _ in 0..30 {
+            platform.update(&mut physics, 1.0 / 60.0);
+            physics.step();
+        }
+
+        let pos = platform.position(&physics).unwrap();
+        assert!((pos.x - 3.0).abs() < 0.05);
+
+        // Kinematic velocity is derived from the target position
+        let vel = physics
+            .get_rigid_body(platform.body_handle)
+            .unwrap()
+            .linvel();
+        assert!((vel.x - 6.0).abs() < 0.1);
+    }
+}
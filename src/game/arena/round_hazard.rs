@@ -0,0 +1,368 @@
+// Round hazards that close in on long rounds (rising water, shrinking blast zones)
+
+use super::bounds::ArenaBounds;
+use super::file::{RoundHazardDef, RoundHazardMode};
+use glam::Vec2;
+
+/// What closes in once the round runs long
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoundHazardKind {
+    /// A kill plane rises from `start_level` to `end_level`
+    RisingWater { start_level: f32, end_level: f32 },
+    /// The blast zones shrink towards `final_bounds`
+    ClosingBlastZones { final_bounds: ArenaBounds },
+}
+
+/// Configuration for a round hazard
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundHazardConfig {
+    /// What closes in
+    pub kind: RoundHazardKind,
+    /// Round time before the hazard starts moving (seconds)
+    pub start_time: f32,
+    /// Time the hazard takes to reach its final state (seconds)
+    pub close_duration: f32,
+    /// How long before the start players are warned (seconds)
+    pub warning_time: f32,
+}
+
+impl RoundHazardConfig {
+    /// Rising water that starts after `start_time` seconds
+    pub fn rising_water(start_time: f32, start_level: f32, end_level: f32) -> Self {
+        Self {
+            kind: RoundHazardKind::RisingWater {
+                start_level,
+                end_level,
+            },
+            start_time,
+            close_duration: 30.0,
+            warning_time: 10.0,
+        }
+    }
+
+    /// Blast zones that close in after `start_time` seconds
+    pub fn closing_blast_zones(start_time: f32, final_bounds: ArenaBounds) -> Self {
+        Self {
+            kind: RoundHazardKind::ClosingBlastZones { final_bounds },
+            start_time,
+            close_duration: 30.0,
+            warning_time: 10.0,
+        }
+    }
+
+    /// Build the hazard an arena file asks for, filling in what it leaves out
+    /// from the arena's blast zone
+    /// Returns None when the hazard is off
+    pub fn from_def(def: &RoundHazardDef, blast_zone: &ArenaBounds) -> Option<Self> {
+        match def.kind {
+            RoundHazardMode::Off => None,
+            RoundHazardMode::RisingWater => {
+                let [start_level, end_level] = def
+                    .water_levels
+                    .unwrap_or([blast_zone.min.y + 2.0, blast_zone.min.y + 11.0]);
+                Some(Self::rising_water(def.start_time, start_level, end_level))
+            }
+            RoundHazardMode::ClosingBlastZones => {
+                let final_bounds =
+                    def.final_bounds
+                        .map(ArenaBounds::from)
+                        .unwrap_or(ArenaBounds::from_center(
+                            blast_zone.center(),
+                            blast_zone.size() * 0.5,
+                        ));
+                Some(Self::closing_blast_zones(def.start_time, final_bounds))
+            }
+        }
+    }
+
+    /// Set how long the hazard takes to close in
+    pub fn with_close_duration(mut self, close_duration: f32) -> Self {
+        self.close_duration = close_duration;
+        self
+    }
+
+    /// Set how long before the start players are warned
+    pub fn with_warning_time(mut self, warning_time: f32) -> Self {
+        self.warning_time = warning_time;
+        self
+    }
+}
+
+/// Milestones announced to players as the hazard progresses
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HazardAnnouncement {
+    /// The hazard starts in the given number of seconds
+    Warning { seconds: f32 },
+    /// The hazard started closing in
+    Started,
+    /// The hazard reached its final state
+    Finished,
+}
+
+impl HazardAnnouncement {
+    /// Format the announcement for a given hazard kind
+    pub fn message(&self, kind: &RoundHazardKind) -> String {
+        let what = match kind {
+            RoundHazardKind::RisingWater { .. } => "The water",
+            RoundHazardKind::ClosingBlastZones { .. } => "The arena",
+        };
+        let verb = match kind {
+            RoundHazardKind::RisingWater { .. } => "rise",
+            RoundHazardKind::ClosingBlastZones { .. } => "shrink",
+        };
+
+        match self {
+            HazardAnnouncement::Warning { seconds } => {
+                format!("{} will {} in {:.0} seconds!", what, verb, seconds)
+            }
+            HazardAnnouncement::Started => format!("{} is starting to {}!", what, verb),
+            HazardAnnouncement::Finished => format!("{} has stopped moving", what),
+        }
+    }
+}
+
+/// Tracks round time and the current state of a round hazard
+#[derive(Debug, Clone)]
+pub struct RoundHazard {
+    pub config: RoundHazardConfig,
+    /// Time since the round started (seconds)
+    elapsed: f32,
+    warned: bool,
+    started: bool,
+    finished: bool,
+}
+
+impl RoundHazard {
+    pub fn new(config: RoundHazardConfig) -> Self {
+        Self {
+            config,
+            elapsed: 0.0,
+            warned: false,
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Advance the round clock
+    /// Returns an announcement when the hazard reaches a new milestone
+    pub fn update(&mut self, dt: f32) -> Option<HazardAnnouncement> {
+        self.elapsed += dt;
+        let config = &self.config;
+
+        if !self.warned && config.warning_time > 0.0 {
+            let warn_at = config.start_time - config.warning_time;
+            if self.elapsed >= warn_at && self.elapsed < config.start_time {
+                self.warned = true;
+                return Some(HazardAnnouncement::Warning {
+                    seconds: (config.start_time - self.elapsed).ceil(),
+                });
+            }
+        }
+
+        if !self.started && self.elapsed >= config.start_time {
+            self.warned = true;
+            self.started = true;
+            return Some(HazardAnnouncement::Started);
+        }
+
+        if self.started && !self.finished && self.progress() >= 1.0 {
+            self.finished = true;
+            return Some(HazardAnnouncement::Finished);
+        }
+
+        None
+    }
+
+    /// Restart the round clock
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.warned = false;
+        self.started = false;
+        self.finished = false;
+    }
+
+    /// Get the time since the round started (seconds)
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Check if the hazard has started closing in
+    pub fn is_active(&self) -> bool {
+        self.elapsed >= self.config.start_time
+    }
+
+    /// Get how far the hazard has closed in (0.0 to 1.0)
+    pub fn progress(&self) -> f32 {
+        let time = self.elapsed - self.config.start_time;
+        if time <= 0.0 {
+            return 0.0;
+        }
+        if self.config.close_duration <= 0.0 {
+            return 1.0;
+        }
+        (time / self.config.close_duration).min(1.0)
+    }
+
+    /// Get the current water level (None if this hazard has no water)
+    pub fn water_level(&self) -> Option<f32> {
+        match self.config.kind {
+            RoundHazardKind::RisingWater {
+                start_level,
+                end_level,
+            } => Some(start_level + (end_level - start_level) * self.progress()),
+            RoundHazardKind::ClosingBlastZones { .. } => None,
+        }
+    }
+
+    /// Get the current blast zone given the arena's normal one
+    pub fn blast_zone(&self, base: &ArenaBounds) -> ArenaBounds {
+        match self.config.kind {
+            RoundHazardKind::ClosingBlastZones { final_bounds } => {
+                base.lerp(&final_bounds, self.progress())
+            }
+            RoundHazardKind::RisingWater { .. } => *base,
+        }
+    }
+
+    /// Get the area the camera may show given the arena's normal camera and
+    /// blast zone bounds
+    /// The camera follows the hazard in, keeping `margin` units of it visible
+    pub fn camera_bounds(
+        &self,
+        camera: &ArenaBounds,
+        blast_zone: &ArenaBounds,
+        margin: f32,
+    ) -> ArenaBounds {
+        let mut bounds = *camera;
+
+        if let Some(level) = self.water_level() {
+            bounds.min.y = bounds.min.y.max(level - margin).min(bounds.max.y);
+        }
+
+        if let RoundHazardKind::ClosingBlastZones { .. } = self.config.kind {
+            let zone = self.blast_zone(blast_zone);
+            bounds.min = bounds.min.max(zone.min - margin);
+            bounds.max = bounds.max.min(zone.max + margin).max(bounds.min);
+        }
+
+        bounds
+    }
+
+    /// Check if a point has been caught by the hazard
+    pub fn is_killed(&self, base: &ArenaBounds, point: Vec2) -> bool {
+        if let Some(level) = self.water_level() {
+            if point.y < level {
+                return true;
+            }
+        }
+        !self.blast_zone(base).contains(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_bounds() -> ArenaBounds {
+        ArenaBounds::new(Vec2::new(-20.0, -10.0), Vec2::new(20.0, 20.0))
+    }
+
+    #[test]
+    fn test_announcements_in_order() {
+        let config = RoundHazardConfig::rising_water(10.0, -10.0, 0.0)
+            .with_warning_time(5.0)
+            .with_close_duration(4.0);
+        let mut hazard = RoundHazard::new(config);
+
+        let mut announcements = Vec::new();
+        for _ in 0..(16 * 10) {
+            if let Some(a) = hazard.update(0.1) {
+                announcements.push(a);
+            }
+        }
+
+        assert_eq!(announcements.len(), 3);
+        assert!(matches!(
+            announcements[0],
+            HazardAnnouncement::Warning { seconds } if seconds == 5.0
+        ));
+        assert_eq!(announcements[1], HazardAnnouncement::Started);
+        assert_eq!(announcements[2], HazardAnnouncement::Finished);
+    }
+
+    #[test]
+    fn test_water_rises_and_kills() {
+        let config = RoundHazardConfig::rising_water(1.0, -10.0, 0.0).with_close_duration(2.0);
+        let mut hazard = RoundHazard::new(config);
+        let base = base_bounds();
+
+        hazard.update(1.0);
+        assert_eq!(hazard.water_level(), Some(-10.0));
+        assert!(!hazard.is_killed(&base, Vec2::new(0.0, -4.0)));
+
+        hazard.update(1.5);
+        assert!((hazard.water_level().unwrap() - -2.5).abs() < 1e-4);
+        assert!(hazard.is_killed(&base, Vec2::new(0.0, -4.0)));
+
+        // Camera follows the water up
+        let camera = hazard.camera_bounds(&base, &base, 2.0);
+        assert!((camera.min.y - -4.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_blast_zones_close_in() {
+        let final_bounds = ArenaBounds::new(Vec2::new(-10.0, -5.0), Vec2::new(10.0, 10.0));
+        let config =
+            RoundHazardConfig::closing_blast_zones(0.0, final_bounds).with_close_duration(10.0);
+        let mut hazard = RoundHazard::new(config);
+        let base = base_bounds();
+
+        assert!(!hazard.is_killed(&base, Vec2::new(15.0, 0.0)));
+        hazard.update(10.0);
+        assert_eq!(hazard.blast_zone(&base), final_bounds);
+        assert!(hazard.is_killed(&base, Vec2::new(15.0, 0.0)));
+
+        let camera = hazard.camera_bounds(&base, &base, 1.0);
+        assert_eq!(camera.max, Vec2::new(11.0, 11.0));
+    }
+
+    #[test]
+    fn test_from_def() {
+        let base = base_bounds();
+        let mut def = RoundHazardDef::default();
+        assert_eq!(RoundHazardConfig::from_def(&def, &base), None);
+
+        def.kind = RoundHazardMode::RisingWater;
+        let config = RoundHazardConfig::from_def(&def, &base).unwrap();
+        assert_eq!(config.start_time, 90.0);
+        assert_eq!(
+            config.kind,
+            RoundHazardKind::RisingWater {
+                start_level: -8.0,
+                end_level: 1.0
+            }
+        );
+
+        def.kind = RoundHazardMode::ClosingBlastZones;
+        def.start_time = 30.0;
+        let config = RoundHazardConfig::from_def(&def, &base).unwrap();
+        assert_eq!(config.start_time, 30.0);
+        assert_eq!(
+            config.kind,
+            RoundHazardKind::ClosingBlastZones {
+                final_bounds: ArenaBounds::new(Vec2::new(-10.0, -2.5), Vec2::new(10.0, 12.5))
+            }
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hazard = RoundHazard::new(RoundHazardConfig::rising_water(1.0, -10.0, 0.0));
+        hazard.update(5.0);
+        assert!(hazard.is_active());
+
+        hazard.reset();
+        assert!(!hazard.is_active());
+        assert_eq!(hazard.progress(), 0.0);
+    }
+}
@@ -0,0 +1,143 @@
+// Spawn point selection
+//
+// Picks the spawn point furthest from living enemies while keeping clear of
+// hazards and projectiles in flight.
+
+use super::bounds::ArenaBounds;
+use glam::Vec2;
+
+/// Distance from the nearest enemy that counts as "completely safe"
+/// Points further away than this all score the same, so ties are broken by
+/// hazards and projectiles instead of by how far across the map they are
+const MAX_USEFUL_DISTANCE: f32 = 30.0;
+/// Clearance kept around hazard volumes
+const HAZARD_CLEARANCE: f32 = 1.5;
+/// Radius around projectiles considered dangerous
+const PROJECTILE_DANGER_RADIUS: f32 = 4.0;
+/// Score penalty for a point inside a hazard's clearance
+const HAZARD_PENALTY: f32 = 1000.0;
+/// Score penalty for a point right on top of a projectile (scaled by distance)
+const PROJECTILE_PENALTY: f32 = 20.0;
+
+/// What a spawn point has to keep away from
+#[derive(Debug, Clone, Default)]
+pub struct SpawnContext {
+    /// Positions of living enemies
+    pub enemies: Vec<Vec2>,
+    /// Active hazard volumes (spikes, lava, rising water, ...)
+    pub hazards: Vec<ArenaBounds>,
+    /// Positions of projectiles in flight
+    pub projectiles: Vec<Vec2>,
+}
+
+impl SpawnContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a living enemy position
+    pub fn with_enemy(mut self, position: Vec2) -> Self {
+        self.enemies.push(position);
+        self
+    }
+
+    /// Add an active hazard volume
+    pub fn with_hazard(mut self, hazard: ArenaBounds) -> Self {
+        self.hazards.push(hazard);
+        self
+    }
+
+    /// Add a projectile position
+    pub fn with_projectile(mut self, position: Vec2) -> Self {
+        self.projectiles.push(position);
+        self
+    }
+
+    /// Score a candidate spawn point (higher is safer)
+    pub fn score(&self, point: Vec2) -> f32 {
+        let enemy_distance = self
+            .enemies
+            .iter()
+            .map(|enemy| enemy.distance(point))
+            .fold(MAX_USEFUL_DISTANCE, f32::min);
+
+        let mut score = enemy_distance;
+
+        for hazard in &self.hazards {
+            let distance = distance_to_bounds(hazard, point);
+            if distance < HAZARD_CLEARANCE {
+                // Deeper inside the clearance is worse
+                score -= HAZARD_PENALTY + (HAZARD_CLEARANCE - distance);
+            }
+        }
+
+        for projectile in &self.projectiles {
+            let distance = projectile.distance(point);
+            if distance < PROJECTILE_DANGER_RADIUS {
+                score -= PROJECTILE_PENALTY * (1.0 - distance / PROJECTILE_DANGER_RADIUS);
+            }
+        }
+
+        score
+    }
+
+    /// Pick the safest of the given spawn points
+    /// Ties go to the earliest point; returns None if there are no points
+    pub fn choose(&self, points: &[Vec2]) -> Option<Vec2> {
+        let mut best: Option<(Vec2, f32)> = None;
+        for &point in points {
+            let score = self.score(point);
+            match best {
+                Some((_, best_score)) if score <= best_score => {}
+                _ => best = Some((point, score)),
+            }
+        }
+        best.map(|(point, _)| point)
+    }
+}
+
+/// Distance from a point to the closest point of a rectangle (0 inside)
+fn distance_to_bounds(bounds: &ArenaBounds, point: Vec2) -> f32 {
+    let closest = point.clamp(bounds.min, bounds.max);
+    closest.distance(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points() -> Vec<Vec2> {
+        vec![
+            Vec2::new(-10.0, 0.0),
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn test_no_points() {
+        assert_eq!(SpawnContext::new().choose(&[]), None);
+    }
+
+    #[test]
+    fn test_furthest_from_enemies() {
+        let context = SpawnContext::new()
+            .with_enemy(Vec2::new(-9.0, 0.0))
+            .with_enemy(Vec2::new(1.0, 1.0));
+        assert_eq!(context.choose(&points()), Some(Vec2::new(10.0, 0.0)));
+
+        // Without enemies the first point wins
+        assert_eq!(SpawnContext::new().choose(&points()), Some(points()[0]));
+    }
+
+    #[test]
+    fn test_avoids_hazards_and_projectiles() {
+        let enemy = Vec2::new(-12.0, 0.0);
+        let hazard = ArenaBounds::from_center(Vec2::new(10.0, -0.5), Vec2::new(4.0, 1.0));
+        let context = SpawnContext::new().with_enemy(enemy).with_hazard(hazard);
+        assert_eq!(context.choose(&points()), Some(Vec2::new(0.0, 0.0)));
+
+        let context = context.with_projectile(Vec2::new(1.0, 0.5));
+        assert_eq!(context.choose(&points()), Some(Vec2::new(-10.0, 0.0)));
+    }
+}
@@ -0,0 +1,547 @@
+// Tile-based arenas with merged collision shapes
+
+use crate::engine::assets::TextureAtlas;
+use crate::engine::physics::{
+    body::{presets, ColliderBuilder2D},
+    ColliderHandle, CollisionGroups, PhysicsWorld, RigidBodyHandle,
+};
+use crate::engine::renderer::{Sprite, SpriteUV};
+use glam::{Vec2, Vec4};
+
+/// Index into a `TileSet` (0 is always the empty tile)
+pub type TileId = u16;
+
+/// The empty tile
+pub const EMPTY_TILE: TileId = 0;
+
+/// How a tile takes part in collision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TileCollision {
+    /// Decoration only
+    #[default]
+    None,
+    /// Blocks from every side
+    Solid,
+    /// Only blocks from above (see `PhysicsWorld::set_one_way_platform`)
+    OneWay,
+}
+
+/// Definition of a tile type
+#[derive(Debug, Clone, Copy)]
+pub struct TileDef {
+    /// Name of the atlas region to draw (None = invisible)
+    pub region: Option<&'static str>,
+    /// Collision behavior
+    pub collision: TileCollision,
+    /// Color tint applied when drawing
+    pub tint: Vec4,
+}
+
+impl TileDef {
+    /// Create a tile drawn with an atlas region
+    pub fn new(region: &'static str, collision: TileCollision) -> Self {
+        Self {
+            region: Some(region),
+            collision,
+            tint: Vec4::ONE,
+        }
+    }
+
+    /// Set the color tint
+    pub fn with_tint(mut self, tint: Vec4) -> Self {
+        self.tint = tint;
+        self
+    }
+}
+
+/// The tile types a tilemap can use
+#[derive(Debug, Clone, Default)]
+pub struct TileSet {
+    defs: Vec<TileDef>,
+}
+
+impl TileSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a tile type and return its ID
+    pub fn add(&mut self, def: TileDef) -> TileId {
+        self.defs.push(def);
+        self.defs.len() as TileId
+    }
+
+    /// Get a tile definition (None for the empty tile or unknown IDs)
+    pub fn get(&self, id: TileId) -> Option<&TileDef> {
+        if id == EMPTY_TILE {
+            return None;
+        }
+        self.defs.get(id as usize - 1)
+    }
+
+    /// Get the collision behavior of a tile
+    pub fn collision(&self, id: TileId) -> TileCollision {
+        self.get(id).map(|def| def.collision).unwrap_or_default()
+    }
+}
+
+/// How solid tiles are turned into colliders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TileColliderMode {
+    /// Greedily merge solid tiles into as few boxes as possible
+    #[default]
+    Boxes,
+    /// Trace the outline of solid regions as a single polyline
+    /// (no internal seams at all, but hollow inside)
+    Outline,
+}
+
+/// A rectangle of tiles (in tile coordinates, y up)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileRect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// A grid of tiles positioned in the world
+///
+/// Row 0 is the bottom row, so tile (x, y) covers the world area starting
+/// at `origin + (x, y) * tile_size`.
+#[derive(Debug, Clone)]
+pub struct Tilemap {
+    /// Number of columns
+    pub width: usize,
+    /// Number of rows
+    pub height: usize,
+    /// Size of one tile in world units
+    pub tile_size: f32,
+    /// World position of the map's bottom-left corner
+    pub origin: Vec2,
+    tiles: Vec<TileId>,
+}
+
+impl Tilemap {
+    /// Create an empty tilemap
+    pub fn new(width: usize, height: usize, tile_size: f32, origin: Vec2) -> Self {
+        Self {
+            width,
+            height,
+            tile_size,
+            origin,
+            tiles: vec![EMPTY_TILE; width * height],
+        }
+    }
+
+    /// Parse a tilemap from text rows (first line is the top row)
+    /// Characters missing from `legend` are empty; short lines are padded
+    pub fn from_ascii(text: &str, tile_size: f32, origin: Vec2, legend: &[(char, TileId)]) -> Self {
+        let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut map = Self::new(width, lines.len(), tile_size, origin);
+
+        for (row, line) in lines.iter().rev().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                if let Some(&(_, id)) = legend.iter().find(|(key, _)| *key == c) {
+                    map.set(column, row, id);
+                }
+            }
+        }
+
+        map
+    }
+
+    /// Get the tile at (x, y), or the empty tile when out of bounds
+    pub fn get(&self, x: usize, y: usize) -> TileId {
+        if x < self.width && y < self.height {
+            self.tiles[y * self.width + x]
+        } else {
+            EMPTY_TILE
+        }
+    }
+
+    /// Set the tile at (x, y) (ignored when out of bounds)
+    pub fn set(&mut self, x: usize, y: usize, id: TileId) {
+        if x < self.width && y < self.height {
+            self.tiles[y * self.width + x] = id;
+        }
+    }
+
+    /// Get the world-space center of tile (x, y)
+    pub fn tile_center(&self, x: usize, y: usize) -> Vec2 {
+        self.origin + (Vec2::new(x as f32, y as f32) + 0.5) * self.tile_size
+    }
+
+    /// Get the world-space center of a tile rectangle
+    pub fn rect_center(&self, rect: &TileRect) -> Vec2 {
+        self.origin
+            + Vec2::new(
+                rect.x as f32 + rect.width as f32 / 2.0,
+                rect.y as f32 + rect.height as f32 / 2.0,
+            ) * self.tile_size
+    }
+
+    /// Merge all tiles with the given collision into as few rectangles as possible
+    ///
+    /// Tiles are merged into horizontal runs first, then each run grows
+    /// upwards for as long as the whole row above it matches.
+    pub fn merged_rects(&self, tileset: &TileSet, collision: TileCollision) -> Vec<TileRect> {
+        let mut used = vec![false; self.tiles.len()];
+        let mut rects = Vec::new();
+        let matches = |x: usize, y: usize, used: &[bool]| {
+            !used[y * self.width + x] && tileset.collision(self.get(x, y)) == collision
+        };
+
+        for y in 0..self.height {
+            let mut x = 0;
+            while x < self.width {
+                if !matches(x, y, &used) {
+                    x += 1;
+                    continue;
+                }
+
+                // Extend to the right
+                let mut width = 1;
+                while x + width < self.width && matches(x + width, y, &used) {
+                    width += 1;
+                }
+
+                // Extend upwards while the whole run matches
+                let mut height = 1;
+                while y + height < self.height
+                    && (x..x + width).all(|cx| matches(cx, y + height, &used))
+                {
+                    height += 1;
+                }
+
+                for ry in y..y + height {
+                    for rx in x..x + width {
+                        used[ry * self.width + rx] = true;
+                    }
+                }
+
+                rects.push(TileRect {
+                    x,
+                    y,
+                    width,
+                    height,
+                });
+                x += width;
+            }
+        }
+
+        rects
+    }
+
+    /// Merge one-way tiles into horizontal runs (they are never stacked,
+    /// since only the top surface of each run should catch characters)
+    pub fn one_way_runs(&self, tileset: &TileSet) -> Vec<TileRect> {
+        let mut runs = Vec::new();
+
+        for y in 0..self.height {
+            let mut x = 0;
+            while x < self.width {
+                if tileset.collision(self.get(x, y)) != TileCollision::OneWay {
+                    x += 1;
+                    continue;
+                }
+
+                let mut width = 1;
+                while x + width < self.width
+                    && tileset.collision(self.get(x + width, y)) == TileCollision::OneWay
+                {
+                    width += 1;
+                }
+
+                runs.push(TileRect {
+                    x,
+                    y,
+                    width,
+                    height: 1,
+                });
+                x += width;
+            }
+        }
+
+        runs
+    }
+
+    /// Trace the boundary between solid and non-solid tiles
+    /// Returns merged segments in tile coordinates (corners, y up)
+    pub fn solid_outline(&self, tileset: &TileSet) -> Vec<([usize; 2], [usize; 2])> {
+        let solid = |x: isize, y: isize| {
+            x >= 0
+                && y >= 0
+                && tileset.collision(self.get(x as usize, y as usize)) == TileCollision::Solid
+        };
+        let mut segments = Vec::new();
+
+        // Horizontal edges: boundary line y separates rows y - 1 and y
+        for y in 0..=self.height as isize {
+            for facing_up in [true, false] {
+                let mut start: Option<usize> = None;
+                for x in 0..=self.width as isize {
+                    let (below, above) = (solid(x, y - 1), solid(x, y));
+                    let edge = x < self.width as isize
+                        && if facing_up {
+                            below && !above
+                        } else {
+                            above && !below
+                        };
+                    match (edge, start) {
+                        (true, None) => start = Some(x as usize),
+                        (false, Some(s)) => {
+                            segments.push(([s, y as usize], [x as usize, y as usize]));
+                            start = None;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // Vertical edges: boundary line x separates columns x - 1 and x
+        for x in 0..=self.width as isize {
+            for facing_right in [true, false] {
+                let mut start: Option<usize> = None;
+                for y in 0..=self.height as isize {
+                    let (left, right) = (solid(x - 1, y), solid(x, y));
+                    let edge = y < self.height as isize
+                        && if facing_right {
+                            left && !right
+                        } else {
+                            right && !left
+                        };
+                    match (edge, start) {
+                        (true, None) => start = Some(y as usize),
+                        (false, Some(s)) => {
+                            segments.push(([x as usize, s], [x as usize, y as usize]));
+                            start = None;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        segments
+    }
+
+    /// Create a static body with merged colliders for every solid and one-way tile
+    pub fn build_colliders(
+        &self,
+        tileset: &TileSet,
+        physics: &mut PhysicsWorld,
+        mode: TileColliderMode,
+    ) -> TilemapColliders {
+        let body_handle = physics.add_rigid_body(presets::platform_body(0.0, 0.0));
+        let mut collider_handles = Vec::new();
+
+        let add_box = |physics: &mut PhysicsWorld, rect: &TileRect| {
+            let center = self.rect_center(rect);
+            let collider = ColliderBuilder2D::box_shape(
+                rect.width as f32 * self.tile_size / 2.0,
+                rect.height as f32 * self.tile_size / 2.0,
+            )
+            .collision_groups(CollisionGroups::Platform)
+            .friction(0.3)
+            .translation(center.x, center.y)
+            .build();
+            physics.add_collider(collider, body_handle)
+        };
+
+        match mode {
+            TileColliderMode::Boxes => {
+                for rect in self.merged_rects(tileset, TileCollision::Solid) {
+                    collider_handles.push(add_box(physics, &rect));
+                }
+            }
+            TileColliderMode::Outline => {
+                let segments = self.solid_outline(tileset);
+                if !segments.is_empty() {
+                    let to_world = |p: [usize; 2]| {
+                        let world =
+                            self.origin + Vec2::new(p[0] as f32, p[1] as f32) * self.tile_size;
+                        [world.x, world.y]
+                    };
+                    let points: Vec<_> = segments
+                        .iter()
+                        .flat_map(|(a, b)| [to_world(*a), to_world(*b)])
+                        .collect();
+                    let indices = (0..segments.len() as u32)
+                        .map(|i| [i * 2, i * 2 + 1])
+                        .collect();
+
+                    let collider = ColliderBuilder2D::polyline(&points, Some(indices))
+                        .collision_groups(CollisionGroups::Platform)
+                        .friction(0.3)
+                        .build();
+                    collider_handles.push(physics.add_collider(collider, body_handle));
+                }
+            }
+        }
+
+        for run in self.one_way_runs(tileset) {
+            let handle = add_box(physics, &run);
+            physics.set_one_way_platform(handle, true);
+            collider_handles.push(handle);
+        }
+
+        TilemapColliders {
+            body_handle,
+            collider_handles,
+        }
+    }
+
+    /// Build one sprite per visible tile, for `Renderer::add_tile_batch`
+    pub fn tile_sprites(&self, tileset: &TileSet, atlas: &TextureAtlas) -> Vec<Sprite> {
+        let mut sprites = Vec::new();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let Some(def) = tileset.get(self.get(x, y)) else {
+                    continue;
+                };
+                let Some(region) = def.region.and_then(|name| atlas.get_region(name)) else {
+                    continue;
+                };
+
+                sprites.push(
+                    Sprite::new(self.tile_center(x, y), Vec2::splat(self.tile_size))
+                        .with_uv(SpriteUV {
+                            min: region.uv_min,
+                            max: region.uv_max,
+                        })
+                        .with_color(def.tint),
+                );
+            }
+        }
+
+        sprites
+    }
+}
+
+/// Physics objects created from a tilemap
+#[derive(Debug)]
+pub struct TilemapColliders {
+    /// The static body all tile colliders are attached to
+    pub body_handle: RigidBodyHandle,
+    /// Merged colliders (solid shapes first, then one-way runs)
+    pub collider_handles: Vec<ColliderHandle>,
+}
+
+impl TilemapColliders {
+    /// Remove the tilemap's body and colliders from the physics world
+    pub fn remove(self, physics: &mut PhysicsWorld) {
+        physics.remove_rigid_body(self.body_handle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::assets::AtlasRegion;
+    use crate::engine::physics::Vector;
+    use rapier2d::prelude::QueryFilter;
+
+    fn test_tileset() -> (TileSet, TileId, TileId) {
+        let mut tileset = TileSet::new();
+        let wall = tileset.add(TileDef::new("wall", TileCollision::Solid));
+        let ledge = tileset.add(TileDef::new("ledge", TileCollision::OneWay));
+        (tileset, wall, ledge)
+    }
+
+    fn parse(text: &str) -> (Tilemap, TileSet) {
+        let (tileset, wall, ledge) = test_tileset();
+        let map = Tilemap::from_ascii(text, 1.0, Vec2::ZERO, &[('#', wall), ('=', ledge)]);
+        (map, tileset)
+    }
+
+    #[test]
+    fn test_from_ascii_bottom_row_first() {
+        let (map, tileset) = parse("#..\n###\n");
+        assert_eq!(map.width, 3);
+        assert_eq!(map.height, 2);
+        assert_eq!(tileset.collision(map.get(0, 1)), TileCollision::Solid);
+        assert_eq!(map.get(1, 1), EMPTY_TILE);
+        assert_eq!(tileset.collision(map.get(2, 0)), TileCollision::Solid);
+    }
+
+    #[test]
+    fn test_floor_merges_into_one_box() {
+        let (map, tileset) = parse("##########\n##########\n");
+        let rects = map.merged_rects(&tileset, TileCollision::Solid);
+        assert_eq!(
+            rects,
+            vec![TileRect {
+                x: 0,
+                y: 0,
+                width: 10,
+                height: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_l_shape_merges_into_two_boxes() {
+        let (map, tileset) = parse("#...\n#...\n####\n");
+        let rects = map.merged_rects(&tileset, TileCollision::Solid);
+        assert_eq!(rects.len(), 2);
+
+        let covered: usize = rects.iter().map(|r| r.width * r.height).sum();
+        assert_eq!(covered, 6);
+    }
+
+    #[test]
+    fn test_one_way_runs_are_not_stacked() {
+        let (map, tileset) = parse("===\n===\n");
+        let runs = map.one_way_runs(&tileset);
+        assert_eq!(runs.len(), 2);
+        assert!(runs.iter().all(|r| r.width == 3 && r.height == 1));
+    }
+
+    #[test]
+    fn test_outline_of_block_is_four_segments() {
+        let (map, tileset) = parse("###\n###\n");
+        let segments = map.solid_outline(&tileset);
+        assert_eq!(segments.len(), 4);
+        assert!(segments.contains(&([0, 2], [3, 2])));
+        assert!(segments.contains(&([3, 0], [3, 2])));
+    }
+
+    #[test]
+    fn test_build_colliders() {
+        let (map, tileset) = parse("=....\n#####\n");
+        let mut physics = PhysicsWorld::new();
+
+        let colliders = map.build_colliders(&tileset, &mut physics, TileColliderMode::Boxes);
+        assert_eq!(colliders.collider_handles.len(), 2);
+        assert!(physics.is_one_way_platform(colliders.collider_handles[1]));
+
+        // The merged floor's top surface is at y = 1
+        physics.step();
+        let hit = physics.raycast(
+            Vector::new(2.5, 5.0),
+            Vector::new(0.0, -1.0),
+            10.0,
+            true,
+            QueryFilter::default(),
+        );
+        let (_, toi) = hit.expect("ray should hit the merged floor");
+        assert!((toi - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_tile_sprites_use_atlas_regions() {
+        let (map, tileset) = parse("#.=\n");
+        let mut atlas = TextureAtlas::new(2, 1);
+        atlas.add_region(AtlasRegion::new("wall", 0, 0, 1, 1, 2, 1));
+
+        // Only the wall has a region in the atlas
+        let sprites = map.tile_sprites(&tileset, &atlas);
+        assert_eq!(sprites.len(), 1);
+        assert_eq!(sprites[0].position, Vec2::new(0.5, 0.5));
+        assert_eq!(sprites[0].uv.max, Vec2::new(0.5, 1.0));
+    }
+}
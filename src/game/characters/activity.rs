@@ -0,0 +1,32 @@
+// Per-character activity for match statistics
+//
+// Characters note what they did since it was last collected: jumps, time in
+// the air and every hit taken (with its source, so damage can be credited to
+// the attacker). The game drains this each step and adds it to the match
+// stats (see `MatchStats`), which only count while a round is being fought.
+
+use super::damage::DamageSource;
+
+/// Damage taken from one hit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DamageTaken {
+    /// Health actually lost
+    pub amount: i32,
+    pub source: DamageSource,
+}
+
+/// What a character did since the activity was last collected
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Activity {
+    pub jumps: u32,
+    /// Time spent jumping or falling (seconds)
+    pub airborne_time: f32,
+    pub damage_taken: Vec<DamageTaken>,
+}
+
+impl Activity {
+    /// Check if nothing happened
+    pub fn is_empty(&self) -> bool {
+        self.jumps == 0 && self.airborne_time == 0.0 && self.damage_taken.is_empty()
+    }
+}
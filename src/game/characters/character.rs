@@ -4,13 +4,23 @@ use crate::engine::physics::{
     body::presets, ColliderHandle, PhysicsWorld, RigidBodyHandle, Vector,
 };
 
+use super::activity::{Activity, DamageTaken};
 use super::animation::AnimationPlayer;
+use super::damage::{DamageSource, Hit, KillCause};
 use super::state::{CharacterState, CharacterStateMachine};
 use super::stats::CharacterStats;
 
 /// Unique identifier for a character
 pub type CharacterId = u32;
 
+/// How quickly wind drift dies down once out of the wind (per second)
+/// A constant force `f` settles at a drift of `f / WIND_DRAG`
+pub const WIND_DRAG: f32 = 2.0;
+
+/// How long a hit counts towards a later KO (seconds)
+/// A character knocked into a hazard within this time is credited to the attacker
+pub const ATTACKER_MEMORY_TIME: f32 = 5.0;
+
 /// Represents a player-controlled or AI-controlled character in the game
 #[derive(Debug)]
 pub struct Character {
@@ -50,6 +60,33 @@ pub struct Character {
     pub input_jump: bool,
     /// Whether duck/down is held
     pub input_duck: bool,
+    /// Whether duck was pressed this frame (drops through one-way platforms)
+    pub input_drop_through: bool,
+
+    /// Time left to ignore one-way platforms after dropping through
+    drop_through_timer: f32,
+    /// Time left of invulnerability after (re)spawning
+    spawn_protection_timer: f32,
+    /// Who last hit this character, and how long that still counts for
+    last_attacker: Option<(CharacterId, f32)>,
+    /// Where the latest damage came from
+    last_damage: Option<DamageSource>,
+    /// Damage taken since (re)spawning; knockback grows with it
+    damage_percent: f32,
+    /// What happened since the game last collected it (see `take_activity`)
+    activity: Activity,
+
+    // Pickup effects
+    /// Active speed boost (multiplier, time left)
+    speed_boost: Option<(f32, f32)>,
+    /// Extra jumps usable once all regular jumps are spent
+    pub bonus_jumps: u8,
+
+    // Environment
+    /// Horizontal drift built up by wind zones, added on top of movement
+    wind_drift: f32,
+    /// Drift included in the body's velocity as of the last movement update
+    applied_wind_drift: f32,
 }
 
 impl Character {
@@ -79,13 +116,24 @@ impl Character {
             collider_handle,
             health: stats.max_health,
             jumps_remaining: stats.max_jumps,
-            stats,
             state_machine: CharacterStateMachine::new(),
             animation: AnimationPlayer::with_standard_animations(),
             facing_direction: 1.0,
             input_horizontal: 0.0,
             input_jump: false,
             input_duck: false,
+            input_drop_through: false,
+            drop_through_timer: 0.0,
+            spawn_protection_timer: stats.spawn_protection_time,
+            last_attacker: None,
+            last_damage: None,
+            damage_percent: 0.0,
+            activity: Activity::default(),
+            speed_boost: None,
+            bonus_jumps: 0,
+            wind_drift: 0.0,
+            applied_wind_drift: 0.0,
+            stats,
         }
     }
 
@@ -93,8 +141,15 @@ impl Character {
     pub fn update_movement(&mut self, physics: &mut PhysicsWorld, dt: f32) {
         let state = self.state_machine.state();
 
-        // Skip if can't move
+        // Knockback keeps its velocity while stunned; only the stun timer runs
         if !state.can_move() {
+            let velocity_y = physics
+                .get_rigid_body(self.body_handle)
+                .map_or(0.0, |body| body.linvel().y);
+            let is_grounded = self.is_grounded_check(physics);
+            self.state_machine
+                .update(dt, is_grounded, velocity_y, false);
+            self.update_animation();
             return;
         }
 
@@ -102,32 +157,54 @@ impl Character {
             return;
         };
 
+        // Work on the velocity without last step's wind drift; it's added back
+        // at the end so wind composes with movement instead of being overwritten
         let mut velocity = *body.linvel();
-        let is_grounded = self.is_grounded_check(physics);
-
-        // Horizontal movement
-        if self.input_horizontal.abs() > 0.1 {
+        velocity.x -= self.applied_wind_drift;
+        self.wind_drift *= (-WIND_DRAG * dt).exp();
+
+        let ground = self.ground_collider(physics);
+        let is_grounded = ground.is_some();
+
+        // Velocity of whatever we're standing on (moving platforms carry
+        // riders, conveyors drag them along)
+        let ground_velocity = ground
+            .map(|collider| {
+                let conveyor = Vector::new(physics.conveyor_speed(collider), 0.0);
+                Self::collider_velocity(physics, collider) + conveyor
+            })
+            .unwrap_or_else(Vector::zeros);
+
+        // Horizontal movement, proportional to how far the stick is pushed
+        // (deadzones are applied by the input system)
+        let input_horizontal = self.input_horizontal.clamp(-1.0, 1.0);
+        if input_horizontal != 0.0 {
             // Determine movement speed based on ground/air state
             let target_speed = if is_grounded {
-                self.input_horizontal * self.stats.move_speed
+                input_horizontal * self.move_speed() + ground_velocity.x
             } else {
-                self.input_horizontal * self.stats.move_speed * self.stats.air_control
+                input_horizontal * self.move_speed() * self.stats.air_control
             };
 
             velocity.x = target_speed;
 
             // Update facing direction
-            if self.input_horizontal > 0.0 {
+            if input_horizontal > 0.0 {
                 self.facing_direction = 1.0;
-            } else if self.input_horizontal < 0.0 {
+            } else {
                 self.facing_direction = -1.0;
             }
         } else if is_grounded {
-            // Stop on ground when no input
-            velocity.x = 0.0;
+            // Stop relative to the ground when no input
+            velocity.x = ground_velocity.x;
         }
         // In air: maintain current velocity when no input (reduced air friction)
 
+        // Follow platforms moving downward instead of bouncing off them
+        if is_grounded && ground_velocity.y < 0.0 && velocity.y <= 0.0 {
+            velocity.y = velocity.y.min(ground_velocity.y);
+        }
+
         // Apply fast fall
         if self.input_duck && !is_grounded && velocity.y < 0.0 {
             // Only fast fall when moving downward
@@ -135,6 +212,9 @@ impl Character {
             velocity.y = velocity.y.min(fast_fall_velocity);
         }
 
+        velocity.x += self.wind_drift;
+        self.applied_wind_drift = self.wind_drift;
+
         // Get mutable body again for the actual update
         if let Some(body) = physics.get_rigid_body_mut(self.body_handle) {
             body.set_linvel(velocity, true);
@@ -148,12 +228,50 @@ impl Character {
         self.update_animation();
     }
 
+    /// Start or finish dropping through one-way platforms
+    pub fn update_drop_through(&mut self, physics: &mut PhysicsWorld, dt: f32) {
+        if self.drop_through_timer > 0.0 {
+            self.drop_through_timer -= dt;
+            if self.drop_through_timer <= 0.0 {
+                self.drop_through_timer = 0.0;
+                physics.set_drop_through(self.body_handle, false);
+            }
+            return;
+        }
+
+        if !self.input_drop_through || !self.state_machine.state().can_move() {
+            return;
+        }
+
+        let on_one_way = self
+            .ground_collider(physics)
+            .is_some_and(|collider| physics.is_one_way_platform(collider));
+
+        if on_one_way {
+            self.drop_through_timer = self.stats.drop_through_time;
+            physics.set_drop_through(self.body_handle, true);
+
+            // Push downward so the character clears the platform before the timer ends
+            if let Some(body) = physics.get_rigid_body_mut(self.body_handle) {
+                let mut velocity = *body.linvel();
+                velocity.y = velocity.y.min(-self.stats.move_speed);
+                body.set_linvel(velocity, true);
+            }
+        }
+    }
+
+    /// Check if the character is currently dropping through a one-way platform
+    pub fn is_dropping_through(&self) -> bool {
+        self.drop_through_timer > 0.0
+    }
+
     /// Attempt to jump (checks if jump is allowed)
     pub fn try_jump(&mut self, physics: &mut PhysicsWorld) {
         let state = self.state_machine.state();
 
-        // Can we jump?
-        if !state.can_jump() || self.jumps_remaining == 0 {
+        // Can we jump? Bonus jumps kick in once the regular ones are spent
+        let use_bonus = self.jumps_remaining == 0 && self.bonus_jumps > 0;
+        if !state.can_jump() || (self.jumps_remaining == 0 && !use_bonus) {
             return;
         }
 
@@ -167,23 +285,37 @@ impl Character {
         body.set_linvel(velocity, true);
 
         // Consume a jump
-        self.jumps_remaining = self.jumps_remaining.saturating_sub(1);
+        if use_bonus {
+            self.bonus_jumps -= 1;
+        } else {
+            self.jumps_remaining = self.jumps_remaining.saturating_sub(1);
+        }
 
         // Update state
         self.state_machine.jump();
+        self.activity.jumps += 1;
     }
 
     /// Check if character is on the ground using raycast
     fn is_grounded_check(&self, physics: &PhysicsWorld) -> bool {
+        self.ground_collider(physics).is_some()
+    }
+
+    /// Find the collider directly below the character's feet, if any
+    fn ground_collider(&self, physics: &PhysicsWorld) -> Option<ColliderHandle> {
         use rapier2d::prelude::QueryFilter;
 
-        let Some(body) = physics.get_rigid_body(self.body_handle) else {
-            return false;
-        };
+        let body = physics.get_rigid_body(self.body_handle)?;
 
         let position = body.translation();
         let half_height = self.stats.height / 2.0;
 
+        // One-way platforms don't count as ground while passing through them
+        let passing_through = body.linvel().y > 0.1 || self.is_dropping_through();
+        let ignore_one_way = |collider: ColliderHandle, _: &rapier2d::prelude::Collider| {
+            !(passing_through && physics.is_one_way_platform(collider))
+        };
+
         // Cast a ray downward from the character's feet
         let ray_origin = Vector::new(position.x, position.y - half_height + 0.1);
         let ray_direction = Vector::new(0.0, -1.0);
@@ -195,9 +327,21 @@ impl Character {
                 ray_direction,
                 max_distance,
                 true,
-                QueryFilter::default().exclude_rigid_body(self.body_handle),
+                QueryFilter::default()
+                    .exclude_rigid_body(self.body_handle)
+                    .predicate(&ignore_one_way),
             )
-            .is_some()
+            .map(|(collider, _)| collider)
+    }
+
+    /// Get the linear velocity of the body a collider is attached to
+    fn collider_velocity(physics: &PhysicsWorld, collider: ColliderHandle) -> Vector<f32> {
+        physics
+            .get_collider(collider)
+            .and_then(|c| c.parent())
+            .and_then(|parent| physics.get_rigid_body(parent))
+            .map(|body| *body.linvel())
+            .unwrap_or_else(Vector::zeros)
     }
 
     /// Called when character lands on ground
@@ -251,12 +395,27 @@ impl Character {
         }
     }
 
-    /// Apply damage to the character
-    pub fn take_damage(&mut self, damage: i32, knockback_x: f32, knockback_y: f32) {
-        self.health = (self.health - damage).max(0);
+    /// Apply a hit and launch the character
+    /// The base knockback velocity is scaled by the damage taken so far
+    /// (see `CharacterStats::knockback_scaling`), including this hit
+    pub fn take_damage(&mut self, physics: &mut PhysicsWorld, hit: Hit) {
+        if self.is_spawn_protected() {
+            return;
+        }
 
-        // Note: Knockback will be applied through physics when combat is implemented
-        let _ = (knockback_x, knockback_y); // TODO: Apply knockback via physics
+        let damage = hit.damage;
+        self.record_damage(damage, hit.source);
+
+        if hit.knockback != glam::Vec2::ZERO {
+            let scale = self.stats.knockback_scaling.multiplier(self.damage_percent);
+            let launch = Vector::new(hit.knockback.x, hit.knockback.y) * scale;
+            if let Some(body) = physics.get_rigid_body_mut(self.body_handle) {
+                body.set_linvel(launch, true);
+            }
+            // The launch replaces any drift the body was carrying
+            self.wind_drift = 0.0;
+            self.applied_wind_drift = 0.0;
+        }
 
         // Apply hit stun based on damage
         let hit_stun_duration = 0.2 + (damage as f32 * 0.01);
@@ -267,6 +426,100 @@ impl Character {
         }
     }
 
+    /// Apply damage without hit stun or knockback (burns and other damage over time)
+    pub fn take_chip_damage(&mut self, damage: i32, source: DamageSource) {
+        if self.is_spawn_protected() {
+            return;
+        }
+
+        self.record_damage(damage, source);
+        if self.health <= 0 {
+            self.die();
+        }
+    }
+
+    /// Lose health and remember where the damage came from
+    fn record_damage(&mut self, damage: i32, source: DamageSource) {
+        let health = self.health;
+        self.health = (self.health - damage).max(0);
+        self.activity.damage_taken.push(DamageTaken {
+            amount: health - self.health,
+            source,
+        });
+        self.damage_percent += damage.max(0) as f32;
+        self.last_damage = Some(source);
+        if let Some(attacker) = source.attacker {
+            self.register_hit(attacker);
+        }
+    }
+
+    /// Push the character with an environmental force (wind, fans) for one step
+    /// Vertical force accelerates the body directly; horizontal force builds
+    /// up drift that movement input adds to rather than overwrites
+    pub fn apply_environment_force(
+        &mut self,
+        physics: &mut PhysicsWorld,
+        force: Vector<f32>,
+        dt: f32,
+    ) {
+        self.wind_drift += force.x * dt;
+        if let Some(body) = physics.get_rigid_body_mut(self.body_handle) {
+            let mut velocity = *body.linvel();
+            velocity.y += force.y * dt;
+            body.set_linvel(velocity, true);
+        }
+    }
+
+    /// Restore health, up to the maximum
+    pub fn heal(&mut self, amount: i32) {
+        if self.is_alive() {
+            self.health = (self.health + amount).min(self.stats.max_health);
+        }
+    }
+
+    /// Multiply movement speed for a while (replaces any active boost)
+    pub fn apply_speed_boost(&mut self, multiplier: f32, duration: f32) {
+        self.speed_boost = Some((multiplier, duration));
+    }
+
+    /// Get the current movement speed, including boosts
+    pub fn move_speed(&self) -> f32 {
+        let multiplier = self.speed_boost.map_or(1.0, |(multiplier, _)| multiplier);
+        self.stats.move_speed * multiplier
+    }
+
+    /// Get the damage taken since (re)spawning, in percent
+    pub fn damage_percent(&self) -> f32 {
+        self.damage_percent
+    }
+
+    /// Remember who hit this character, so a later KO can be credited to them
+    pub fn register_hit(&mut self, attacker: CharacterId) {
+        if attacker != self.id {
+            self.last_attacker = Some((attacker, ATTACKER_MEMORY_TIME));
+        }
+    }
+
+    /// Get the character credited with a KO right now, if any
+    pub fn last_attacker(&self) -> Option<CharacterId> {
+        self.last_attacker.map(|(attacker, _)| attacker)
+    }
+
+    /// Get where the latest damage came from
+    pub fn last_damage(&self) -> Option<DamageSource> {
+        self.last_damage
+    }
+
+    /// Get what knocked the character out if it ran out of health
+    pub fn health_knockout_cause(&self) -> KillCause {
+        KillCause::from_source(self.last_damage)
+    }
+
+    /// Collect what the character did since the last call
+    pub fn take_activity(&mut self) -> Activity {
+        std::mem::take(&mut self.activity)
+    }
+
     /// Kill the character
     pub fn die(&mut self) {
         self.state_machine.die();
@@ -275,11 +528,45 @@ impl Character {
     /// Respawn the character at a given position
     pub fn respawn(&mut self, physics: &mut PhysicsWorld, x: f32, y: f32) {
         self.set_position(physics, x, y);
+        self.drop_through_timer = 0.0;
+        physics.set_drop_through(self.body_handle, false);
         self.health = self.stats.max_health;
         self.jumps_remaining = self.stats.max_jumps;
+        self.spawn_protection_timer = self.stats.spawn_protection_time;
+        self.last_attacker = None;
+        self.last_damage = None;
+        self.damage_percent = 0.0;
+        self.speed_boost = None;
+        self.bonus_jumps = 0;
+        self.wind_drift = 0.0;
+        self.applied_wind_drift = 0.0;
         self.state_machine.respawn();
     }
 
+    /// Tick down spawn protection and boosts, and forget old attackers
+    pub fn update_timers(&mut self, dt: f32) {
+        self.spawn_protection_timer = (self.spawn_protection_timer - dt).max(0.0);
+
+        if let Some((_, time_left)) = &mut self.speed_boost {
+            *time_left -= dt;
+            if *time_left <= 0.0 {
+                self.speed_boost = None;
+            }
+        }
+
+        if let Some((_, time_left)) = &mut self.last_attacker {
+            *time_left -= dt;
+            if *time_left <= 0.0 {
+                self.last_attacker = None;
+            }
+        }
+    }
+
+    /// Check if the character is invulnerable after (re)spawning
+    pub fn is_spawn_protected(&self) -> bool {
+        self.spawn_protection_timer > 0.0
+    }
+
     /// Check if character is alive
     pub fn is_alive(&self) -> bool {
         self.state_machine.state() != CharacterState::Dead
@@ -298,6 +585,7 @@ impl Character {
     /// Clear input state (called at end of frame)
     pub fn clear_input(&mut self) {
         self.input_jump = false;
+        self.input_drop_through = false;
         // Note: horizontal and duck inputs are continuous, not cleared
     }
 }
@@ -378,12 +666,22 @@ impl CharacterManager {
                 character.try_jump(physics);
             }
 
+            // Handle dropping through one-way platforms
+            character.update_drop_through(physics, dt);
+
+            // Spawn protection and attacker memory wear off over time
+            character.update_timers(dt);
+
             // Update movement
             character.update_movement(physics, dt);
 
             // Update animation timing
             character.update_animation_timing(dt);
 
+            if character.state_machine.state().is_airborne() {
+                character.activity.airborne_time += dt;
+            }
+
             // Check for landing
             if character.state_machine.state().is_airborne() && character.is_grounded_check(physics)
             {
@@ -435,6 +733,273 @@ mod tests {
         assert_eq!(manager.count(), 0);
     }
 
+    #[test]
+    fn test_character_carried_by_moving_platform() {
+        use crate::game::arena::{MovingPlatform, WaypointPath};
+        use glam::Vec2;
+
+        let mut physics = PhysicsWorld::new();
+        let path = WaypointPath::new(vec![Vec2::ZERO, Vec2::new(100.0, 0.0)], 3.0);
+        let mut platform = MovingPlatform::new(&mut physics, path, 10.0, 1.0);
+
+        let mut manager = CharacterManager::new();
+        let id = manager.spawn_character(
+            "Rider",
+            Some(0),
+            CharacterStats::standard(),
+            &mut physics,
+            0.0,
+            1.6,
+        );
+
+        let dt = 1.0 / 60.0;
+        for _ in 0..120 {
+            platform.update(&mut physics, dt);
+            manager.update(&mut physics, dt);
+            physics.step();
+        }
+
+        let (x, _) = manager.get(id).unwrap().position(&physics).unwrap();
+        let platform_x = platform.position(&physics).unwrap().x;
+        assert!(platform_x > 5.0);
+        assert!(
+            (x - platform_x).abs() < 1.0,
+            "Rider should stay on the platform"
+        );
+    }
+
+    /// A character standing on a solid floor
+    fn floor_test_world() -> (PhysicsWorld, CharacterManager, CharacterId) {
+        let mut physics = PhysicsWorld::new();
+        let floor = physics.add_rigid_body(presets::platform_body(0.0, 0.0));
+        physics.add_collider(presets::platform_collider(10.0, 1.0), floor);
+
+        let mut manager = CharacterManager::new();
+        let id = manager.spawn_character(
+            "Tester",
+            Some(0),
+            CharacterStats::standard(),
+            &mut physics,
+            0.0,
+            1.6,
+        );
+        (physics, manager, id)
+    }
+
+    /// A character standing on a one-way platform
+    fn one_way_test_world() -> (PhysicsWorld, CharacterManager, CharacterId) {
+        let mut physics = PhysicsWorld::new();
+        let platform = physics.add_rigid_body(presets::platform_body(0.0, 0.0));
+        let collider = physics.add_collider(presets::platform_collider(10.0, 1.0), platform);
+        physics.set_one_way_platform(collider, true);
+
+        let mut manager = CharacterManager::new();
+        let id = manager.spawn_character(
+            "Dropper",
+            Some(0),
+            CharacterStats::standard(),
+            &mut physics,
+            0.0,
+            1.6,
+        );
+        (physics, manager, id)
+    }
+
+    fn step_frames(physics: &mut PhysicsWorld, manager: &mut CharacterManager, frames: usize) {
+        for _ in 0..frames {
+            manager.update(physics, 1.0 / 60.0);
+            physics.step();
+        }
+    }
+
+    #[test]
+    fn test_stand_on_one_way_platform() {
+        let (mut physics, mut manager, id) = one_way_test_world();
+        step_frames(&mut physics, &mut manager, 60);
+
+        let character = manager.get(id).unwrap();
+        let (_, y) = character.position(&physics).unwrap();
+        assert!(y > 1.0, "Character should rest on top of the platform");
+        assert!(character.is_grounded(&physics));
+    }
+
+    #[test]
+    fn test_drop_through_one_way_platform() {
+        let (mut physics, mut manager, id) = one_way_test_world();
+        step_frames(&mut physics, &mut manager, 60);
+
+        manager.get_mut(id).unwrap().input_drop_through = true;
+        step_frames(&mut physics, &mut manager, 60);
+
+        let (_, y) = manager.get(id).unwrap().position(&physics).unwrap();
+        assert!(y < -1.0, "Character should fall through the platform");
+        assert!(!physics.is_dropping_through(manager.get(id).unwrap().body_handle));
+    }
+
+    #[test]
+    fn test_jump_up_through_one_way_platform() {
+        let (mut physics, mut manager, id) = one_way_test_world();
+        let character = manager.get(id).unwrap();
+        character.set_position(&mut physics, 0.0, -2.0);
+        if let Some(body) = physics.get_rigid_body_mut(character.body_handle) {
+            body.set_linvel(Vector::new(0.0, 15.0), true);
+        }
+
+        step_frames(&mut physics, &mut manager, 120);
+
+        let (_, y) = manager.get(id).unwrap().position(&physics).unwrap();
+        assert!(y > 1.0, "Character should pass through and land on top");
+    }
+
+    #[test]
+    fn test_spawn_protection() {
+        let (mut physics, mut manager, id) = floor_test_world();
+        let character = manager.get_mut(id).unwrap();
+        assert!(character.is_spawn_protected());
+
+        character.take_damage(&mut physics, Hit::new(30, DamageSource::default()));
+        assert_eq!(character.health, character.stats.max_health);
+
+        step_frames(&mut physics, &mut manager, 150);
+        let character = manager.get_mut(id).unwrap();
+        assert!(!character.is_spawn_protected());
+        character.take_damage(&mut physics, Hit::new(30, DamageSource::default()));
+        assert_eq!(character.health, character.stats.max_health - 30);
+
+        // Respawning grants protection again
+        character.respawn(&mut physics, 0.0, 5.0);
+        assert!(character.is_spawn_protected());
+    }
+
+    #[test]
+    fn test_knockback_grows_with_damage() {
+        let (mut physics, mut manager, id) = floor_test_world();
+        step_frames(&mut physics, &mut manager, 150);
+
+        let launch_speed = |physics: &mut PhysicsWorld, manager: &mut CharacterManager| {
+            let character = manager.get_mut(id).unwrap();
+            character.take_damage(
+                physics,
+                Hit::new(20, DamageSource::default()).with_knockback(glam::Vec2::new(5.0, 0.0)),
+            );
+            character.velocity(physics).unwrap().0
+        };
+        let fresh = launch_speed(&mut physics, &mut manager);
+        manager
+            .get_mut(id)
+            .unwrap()
+            .take_chip_damage(60, DamageSource::default());
+        let hurt = launch_speed(&mut physics, &mut manager);
+
+        assert!(fresh > 5.0);
+        assert!(hurt > fresh * 1.5, "fresh = {}, hurt = {}", fresh, hurt);
+        assert_eq!(manager.get(id).unwrap().damage_percent(), 100.0);
+    }
+
+    #[test]
+    fn test_hit_stun_keeps_knockback() {
+        let (mut physics, mut manager, id) = floor_test_world();
+        step_frames(&mut physics, &mut manager, 150);
+
+        let character = manager.get_mut(id).unwrap();
+        character.take_damage(
+            &mut physics,
+            Hit::new(10, DamageSource::default()).with_knockback(glam::Vec2::new(-8.0, 6.0)),
+        );
+        assert_eq!(character.state(), CharacterState::HitStun);
+
+        // Holding right doesn't cancel the launch to the left while stunned
+        for _ in 0..6 {
+            manager.get_mut(id).unwrap().input_horizontal = 1.0;
+            step_frames(&mut physics, &mut manager, 1);
+        }
+        let character = manager.get(id).unwrap();
+        assert!(character.velocity(&physics).unwrap().0 < 0.0);
+
+        // Once the stun ends, input takes over again
+        step_frames(&mut physics, &mut manager, 30);
+        manager.get_mut(id).unwrap().input_horizontal = 1.0;
+        step_frames(&mut physics, &mut manager, 1);
+        let character = manager.get(id).unwrap();
+        assert_ne!(character.state(), CharacterState::HitStun);
+        assert!(character.velocity(&physics).unwrap().0 > 0.0);
+    }
+
+    #[test]
+    fn test_walk_speed_follows_analog_input() {
+        let (mut physics, mut manager, id) = floor_test_world();
+        step_frames(&mut physics, &mut manager, 60);
+
+        let walk = |physics: &mut PhysicsWorld, manager: &mut CharacterManager, input| {
+            manager.get_mut(id).unwrap().input_horizontal = input;
+            step_frames(physics, manager, 1);
+            manager.get(id).unwrap().velocity(physics).unwrap().0
+        };
+        let full = walk(&mut physics, &mut manager, 1.0);
+        let half = walk(&mut physics, &mut manager, 0.5);
+        let creep = walk(&mut physics, &mut manager, -0.05);
+
+        assert!(full > 9.0, "full = {}", full);
+        assert!((half / full - 0.5).abs() < 0.05, "half = {}", half);
+        assert!(creep < 0.0 && creep > -full * 0.1, "creep = {}", creep);
+    }
+
+    #[test]
+    fn test_attacker_memory() {
+        let (mut physics, mut manager, id) = floor_test_world();
+        let character = manager.get_mut(id).unwrap();
+        character.register_hit(id);
+        assert_eq!(character.last_attacker(), None, "Self-hits don't count");
+
+        character.register_hit(7);
+        assert_eq!(character.last_attacker(), Some(7));
+
+        step_frames(&mut physics, &mut manager, 60 * 6);
+        assert_eq!(manager.get(id).unwrap().last_attacker(), None);
+    }
+
+    #[test]
+    fn test_activity_is_collected() {
+        let (mut physics, mut manager, id) = floor_test_world();
+        step_frames(&mut physics, &mut manager, 150);
+        manager.get_mut(id).unwrap().take_activity();
+
+        manager.get_mut(id).unwrap().input_jump = true;
+        step_frames(&mut physics, &mut manager, 30);
+        let character = manager.get_mut(id).unwrap();
+        character.take_chip_damage(130, DamageSource::attack(4, "stomp"));
+
+        let activity = character.take_activity();
+        assert_eq!(activity.jumps, 1);
+        assert!(activity.airborne_time > 0.2);
+        assert_eq!(activity.damage_taken.len(), 1);
+        assert_eq!(activity.damage_taken[0].amount, character.stats.max_health);
+        assert!(character.take_activity().is_empty());
+    }
+
+    #[test]
+    fn test_pickup_effects() {
+        let (mut physics, mut manager, id) = floor_test_world();
+        let character = manager.get_mut(id).unwrap();
+        character.health = 50;
+        character.heal(80);
+        assert_eq!(character.health, character.stats.max_health);
+
+        character.apply_speed_boost(1.5, 1.0);
+        assert_eq!(character.move_speed(), character.stats.move_speed * 1.5);
+        step_frames(&mut physics, &mut manager, 70);
+        let character = manager.get_mut(id).unwrap();
+        assert_eq!(character.move_speed(), character.stats.move_speed);
+
+        // A bonus jump is used only after the regular jumps run out
+        character.bonus_jumps = 1;
+        character.jumps_remaining = 0;
+        character.try_jump(&mut physics);
+        assert_eq!(character.bonus_jumps, 0);
+        let (_, vy) = character.velocity(&physics).unwrap();
+        assert_eq!(vy, character.stats.jump_force);
+    }
+
     #[test]
     fn test_character_state_helpers() {
         assert!(CharacterState::Idle.is_grounded());
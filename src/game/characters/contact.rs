@@ -0,0 +1,293 @@
+// Player-vs-player contact: shoving and head stomps
+//
+// By default characters pass through each other. With contact rules enabled
+// their colliders collide (see `PhysicsWorld::set_player_collision`), so
+// walking into someone shoves them, and landing on an opponent's head
+// bounces you up and stuns (and optionally damages) them.
+
+use super::character::{CharacterId, CharacterManager};
+use super::damage::{DamageSource, Hit};
+use crate::engine::physics::PhysicsWorld;
+
+/// How far feet may be from a head and still count as landing on it
+const STOMP_TOLERANCE: f32 = 0.25;
+
+/// Fraction of the combined widths that must overlap horizontally for a stomp
+const STOMP_OVERLAP: f32 = 0.8;
+
+/// Rules for players touching each other
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerContactRules {
+    /// Players collide, push each other and can be stomped
+    pub enabled: bool,
+    /// Damage dealt to a stomped player
+    pub stomp_damage: i32,
+    /// Hit stun of a stomped player (seconds)
+    pub stomp_stun: f32,
+    /// Upward speed the stomper bounces off with
+    pub stomp_bounce: f32,
+}
+
+impl Default for PlayerContactRules {
+    fn default() -> Self {
+        Self::OFF
+    }
+}
+
+impl PlayerContactRules {
+    /// Players pass through each other
+    pub const OFF: Self = Self {
+        enabled: false,
+        stomp_damage: 0,
+        stomp_stun: 0.0,
+        stomp_bounce: 0.0,
+    };
+
+    /// Players push each other, and stomps stun and hurt a little
+    pub const SHOVE_AND_STOMP: Self = Self {
+        enabled: true,
+        stomp_damage: 10,
+        stomp_stun: 0.6,
+        stomp_bounce: 9.0,
+    };
+
+    /// Get a short description for menus and logs
+    pub fn describe(&self) -> String {
+        if self.enabled {
+            format!("on ({} damage stomps)", self.stomp_damage)
+        } else {
+            "off".to_string()
+        }
+    }
+}
+
+/// A character landed on another character's head
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stomp {
+    pub stomper: CharacterId,
+    pub victim: CharacterId,
+    /// Whether the stomp hurt the victim (those taking no damage, like
+    /// protected teammates, are only bounced off)
+    pub hurt: bool,
+    /// Whether the stomp knocked the victim out
+    pub knocked_out: bool,
+}
+
+/// Bounce characters off the heads of those they land on
+/// Call after stepping physics; `damage(stomper, victim, rules.stomp_damage)`
+/// returns the damage that lands (after friendly fire), and a victim taking
+/// none is not stunned either, just used as a springboard
+pub fn resolve_stomps(
+    characters: &mut CharacterManager,
+    physics: &mut PhysicsWorld,
+    rules: &PlayerContactRules,
+    damage: impl Fn(CharacterId, CharacterId, i32) -> i32,
+) -> Vec<Stomp> {
+    if !rules.enabled {
+        return Vec::new();
+    }
+
+    // Find every stomper/victim pair first, then apply them
+    let mut pairs = Vec::new();
+    for stomper in characters.all() {
+        let (Some((sx, sy)), Some((_, svy))) =
+            (stomper.position(physics), stomper.velocity(physics))
+        else {
+            continue;
+        };
+        if !stomper.is_alive() || svy > 0.5 {
+            continue;
+        }
+        let feet = sy - stomper.stats.height / 2.0;
+
+        let victim = characters.all().iter().find(|victim| {
+            if victim.id == stomper.id || !victim.is_alive() {
+                return false;
+            }
+            let Some((vx, vy)) = victim.position(physics) else {
+                return false;
+            };
+            let head = vy + victim.stats.height / 2.0;
+            let reach = (stomper.stats.width + victim.stats.width) / 2.0 * STOMP_OVERLAP;
+            sy > vy && (feet - head).abs() <= STOMP_TOLERANCE && (sx - vx).abs() < reach
+        });
+        if let Some(victim) = victim {
+            pairs.push((stomper.id, victim.id));
+        }
+    }
+
+    let mut stomps = Vec::new();
+    for (stomper_id, victim_id) in pairs {
+        if let Some(stomper) = characters.get_mut(stomper_id) {
+            if let Some(body) = physics.get_rigid_body_mut(stomper.body_handle) {
+                let mut velocity = *body.linvel();
+                velocity.y = rules.stomp_bounce;
+                body.set_linvel(velocity, true);
+            }
+            stomper.on_land();
+        }
+
+        let damage = damage(stomper_id, victim_id, rules.stomp_damage);
+        let hurt = damage > 0;
+        let Some(victim) = characters.get_mut(victim_id) else {
+            continue;
+        };
+        // Someone already stunned (or protected) can't be stomped again right away
+        let stunnable = !victim.is_spawn_protected() && victim.state().can_move();
+        if hurt && stunnable {
+            let source = DamageSource::attack(stomper_id, "stomp");
+            victim.take_damage(physics, Hit::new(damage, source));
+            victim.state_machine.apply_hit_stun(rules.stomp_stun);
+        }
+        stomps.push(Stomp {
+            stomper: stomper_id,
+            victim: victim_id,
+            hurt: hurt && stunnable,
+            knocked_out: !victim.is_alive(),
+        });
+    }
+    stomps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::physics::body::presets;
+    use crate::game::characters::{CharacterState, CharacterStats};
+    use crate::game::match_flow::{FriendlyFire, TeamAssignment, TeamMode};
+    use glam::Vec2;
+
+    fn contact_world() -> (PhysicsWorld, CharacterManager, CharacterId, CharacterId) {
+        let mut physics = PhysicsWorld::new();
+        let floor = physics.add_rigid_body(presets::platform_body(0.0, -5.0));
+        physics.add_collider(presets::platform_collider(60.0, 1.0), floor);
+
+        let mut characters = CharacterManager::new();
+        let mut spawn = |name, player, x| {
+            characters.spawn_character(
+                name,
+                Some(player),
+                CharacterStats::standard(),
+                &mut physics,
+                x,
+                -3.5,
+            )
+        };
+        let a = spawn("A", 0, -2.0);
+        let b = spawn("B", 1, 2.0);
+        for character in characters.all() {
+            physics.set_player_collision(character.collider_handle, true);
+        }
+        (physics, characters, a, b)
+    }
+
+    fn run(physics: &mut PhysicsWorld, characters: &mut CharacterManager, frames: usize) {
+        for _ in 0..frames {
+            characters.update(physics, 1.0 / 60.0);
+            physics.step();
+        }
+    }
+
+    fn x(physics: &PhysicsWorld, characters: &CharacterManager, id: CharacterId) -> f32 {
+        characters.get(id).unwrap().position(physics).unwrap().0
+    }
+
+    #[test]
+    fn test_walking_into_a_player_shoves_them() {
+        let (mut physics, mut characters, a, b) = contact_world();
+        run(&mut physics, &mut characters, 30);
+
+        for _ in 0..90 {
+            characters.get_mut(a).unwrap().input_horizontal = 1.0;
+            run(&mut physics, &mut characters, 1);
+        }
+        let gap = x(&physics, &characters, b) - x(&physics, &characters, a);
+        assert!(gap > 0.5, "Players should not overlap, gap = {}", gap);
+        assert!(x(&physics, &characters, b) > 2.5, "B should be shoved");
+    }
+
+    /// Drop A onto B's head once spawn protection has worn off
+    fn stomp(
+        physics: &mut PhysicsWorld,
+        characters: &mut CharacterManager,
+        a: CharacterId,
+        b: CharacterId,
+        damage: impl Fn(CharacterId, CharacterId, i32) -> i32,
+    ) -> Vec<Stomp> {
+        run(physics, characters, 30);
+        let rules = PlayerContactRules::SHOVE_AND_STOMP;
+
+        for character in characters.all_mut() {
+            character.update_timers(10.0);
+        }
+        let head = characters.get(b).unwrap().position(physics).unwrap().1
+            + CharacterStats::standard().height;
+        characters
+            .get(a)
+            .unwrap()
+            .set_position(physics, 2.0, head + 1.0);
+
+        let mut stomps = Vec::new();
+        for _ in 0..60 {
+            run(physics, characters, 1);
+            stomps.extend(resolve_stomps(characters, physics, &rules, &damage));
+            if !stomps.is_empty() {
+                break;
+            }
+        }
+        stomps
+    }
+
+    #[test]
+    fn test_head_stomp() {
+        let (mut physics, mut characters, a, b) = contact_world();
+        let stomps = stomp(&mut physics, &mut characters, a, b, |_, _, damage| damage);
+
+        assert_eq!(stomps.len(), 1);
+        assert_eq!((stomps[0].stomper, stomps[0].victim), (a, b));
+        assert!(stomps[0].hurt);
+        let victim = characters.get(b).unwrap();
+        assert_eq!(victim.state(), CharacterState::HitStun);
+        assert_eq!(victim.last_attacker(), Some(a));
+        assert_eq!(
+            victim.health,
+            CharacterStats::standard().max_health
+                - PlayerContactRules::SHOVE_AND_STOMP.stomp_damage
+        );
+        assert!(characters.get(a).unwrap().velocity(&physics).unwrap().1 > 0.0);
+    }
+
+    #[test]
+    fn test_stomping_a_teammate_with_knockback_only_friendly_fire() {
+        let (mut physics, mut characters, a, b) = contact_world();
+        let teams =
+            TeamAssignment::assign(TeamMode::Teams(1), &[0, 1], FriendlyFire::KNOCKBACK_ONLY);
+        let player_of = |id| {
+            if id == a {
+                0
+            } else {
+                1
+            }
+        };
+        let stomps = stomp(
+            &mut physics,
+            &mut characters,
+            a,
+            b,
+            |stomper, victim, damage| {
+                teams
+                    .resolve_hit(player_of(stomper), player_of(victim), damage, Vec2::ZERO)
+                    .map_or(0, |hit| hit.damage)
+            },
+        );
+
+        // The teammate is only a springboard: no damage, no stun
+        assert_eq!(stomps.len(), 1);
+        assert!(!stomps[0].hurt);
+        assert!(!stomps[0].knocked_out);
+        let victim = characters.get(b).unwrap();
+        assert_ne!(victim.state(), CharacterState::HitStun);
+        assert_eq!(victim.health, CharacterStats::standard().max_health);
+        assert!(characters.get(a).unwrap().velocity(&physics).unwrap().1 > 0.0);
+    }
+}
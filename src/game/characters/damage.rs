@@ -0,0 +1,129 @@
+// Damage sources and knockout causes
+//
+// Every hit names where it came from: the attacking character (if any), the
+// ability used and the hazard involved. Characters remember their last
+// attacker for a few seconds (see `ATTACKER_MEMORY_TIME`), so falling into a
+// hazard or out of the arena right after a hit still credits the attacker.
+
+use super::character::CharacterId;
+use crate::game::arena::HazardKind;
+use glam::Vec2;
+
+/// Where a hit came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DamageSource {
+    /// Character that dealt the hit
+    pub attacker: Option<CharacterId>,
+    /// Ability (or move) used, for logs and stats
+    pub ability: Option<&'static str>,
+    /// Hazard that dealt the hit
+    pub hazard: Option<HazardKind>,
+}
+
+impl DamageSource {
+    /// A hit from a character's ability
+    pub fn attack(attacker: CharacterId, ability: &'static str) -> Self {
+        Self {
+            attacker: Some(attacker),
+            ability: Some(ability),
+            hazard: None,
+        }
+    }
+
+    /// A hit from a hazard
+    pub fn hazard(kind: HazardKind) -> Self {
+        Self {
+            hazard: Some(kind),
+            ..Self::default()
+        }
+    }
+}
+
+/// A single damage instance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+    pub damage: i32,
+    /// Base launch velocity, before scaling with damage taken
+    pub knockback: Vec2,
+    pub source: DamageSource,
+}
+
+impl Hit {
+    /// Create a hit without knockback
+    pub fn new(damage: i32, source: DamageSource) -> Self {
+        Self {
+            damage,
+            knockback: Vec2::ZERO,
+            source,
+        }
+    }
+
+    /// Set the base launch velocity
+    pub fn with_knockback(mut self, knockback: Vec2) -> Self {
+        self.knockback = knockback;
+        self
+    }
+}
+
+/// What knocked a character out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillCause {
+    /// Ran out of health from a character's attack
+    Attack { ability: Option<&'static str> },
+    /// A hazard volume
+    Hazard(HazardKind),
+    /// Left the arena through the blast zone
+    BlastZone,
+    /// Caught by the round hazard closing in
+    RoundHazard,
+    /// Nothing recorded (e.g. removed by the game)
+    Unknown,
+}
+
+impl KillCause {
+    /// Get the cause of running out of health from the last hit taken
+    pub fn from_source(source: Option<DamageSource>) -> Self {
+        match source {
+            Some(DamageSource {
+                hazard: Some(kind), ..
+            }) => Self::Hazard(kind),
+            Some(DamageSource { ability, .. }) => Self::Attack { ability },
+            None => Self::Unknown,
+        }
+    }
+
+    /// Get a short description for logs and the UI
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Attack {
+                ability: Some(ability),
+            } => ability.to_string(),
+            Self::Attack { ability: None } => "attack".to_string(),
+            Self::Hazard(kind) => format!("{:?}", kind).to_lowercase(),
+            Self::BlastZone => "blast zone".to_string(),
+            Self::RoundHazard => "round hazard".to_string(),
+            Self::Unknown => "unknown".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kill_cause_from_source() {
+        assert_eq!(
+            KillCause::from_source(Some(DamageSource::hazard(HazardKind::Lava))),
+            KillCause::Hazard(HazardKind::Lava)
+        );
+        assert_eq!(
+            KillCause::from_source(Some(DamageSource::attack(3, "stomp"))),
+            KillCause::Attack {
+                ability: Some("stomp")
+            }
+        );
+        assert_eq!(KillCause::from_source(None), KillCause::Unknown);
+        assert_eq!(KillCause::Hazard(HazardKind::Saw).describe(), "saw");
+    }
+}
@@ -5,14 +5,23 @@
 // - Character stats and properties
 // - State machine for character behavior
 // - Animation system for sprites
+// - Player-vs-player contact (shoving and head stomps)
+// - Damage sources and knockout causes
+// - Activity tracking for match statistics
 
+pub mod activity;
 pub mod animation;
 pub mod character;
+pub mod contact;
+pub mod damage;
 pub mod state;
 pub mod stats;
 
 // Re-export commonly used types
+pub use activity::{Activity, DamageTaken};
 pub use animation::{AnimationClip, AnimationFrameData, AnimationPlayer, SpriteSheetConfig};
 pub use character::{Character, CharacterId, CharacterManager};
+pub use contact::{resolve_stomps, PlayerContactRules, Stomp};
+pub use damage::{DamageSource, Hit, KillCause};
 pub use state::{CharacterState, CharacterStateMachine};
-pub use stats::CharacterStats;
+pub use stats::{CharacterStats, KnockbackScaling};
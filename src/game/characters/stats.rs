@@ -1,6 +1,48 @@
 // Character stats - ALL PLAYERS HAVE THE SAME STATS
 // Differentiation comes from SKILLS/ABILITIES, not base stats
 
+/// How knockback grows with the damage a character has taken
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KnockbackScaling {
+    /// Knockback is the same no matter how hurt the character is
+    Flat,
+    /// Smash-style: every 100% of damage taken adds `growth` times the base knockback
+    Percent { growth: f32 },
+    /// Piecewise-linear curve of (damage percent, multiplier) points, sorted by percent
+    /// Values past either end use the nearest point
+    Curve(&'static [(f32, f32)]),
+}
+
+impl KnockbackScaling {
+    /// Get the knockback multiplier at a damage percent
+    pub fn multiplier(&self, percent: f32) -> f32 {
+        match self {
+            Self::Flat => 1.0,
+            Self::Percent { growth } => 1.0 + percent.max(0.0) / 100.0 * growth,
+            Self::Curve(points) => {
+                let Some(&(first_percent, first)) = points.first() else {
+                    return 1.0;
+                };
+                if percent <= first_percent {
+                    return first;
+                }
+                for pair in points.windows(2) {
+                    let ((p0, m0), (p1, m1)) = (pair[0], pair[1]);
+                    if percent <= p1 {
+                        let t = if p1 > p0 {
+                            (percent - p0) / (p1 - p0)
+                        } else {
+                            1.0
+                        };
+                        return m0 + (m1 - m0) * t;
+                    }
+                }
+                points.last().map_or(1.0, |&(_, multiplier)| multiplier)
+            }
+        }
+    }
+}
+
 /// Fixed character stats - same for all players
 /// This ensures fair gameplay where skill choice matters, not stat differences
 #[derive(Debug, Clone)]
@@ -20,10 +62,16 @@ pub struct CharacterStats {
     pub gravity_scale: f32,
     /// Fall speed multiplier when holding down
     pub fast_fall_multiplier: f32,
+    /// How long one-way platforms are ignored after dropping through (seconds)
+    pub drop_through_time: f32,
 
     // Combat
     /// Base health points
     pub max_health: i32,
+    /// Invulnerability after spawning or respawning (seconds)
+    pub spawn_protection_time: f32,
+    /// How knockback grows with damage taken
+    pub knockback_scaling: KnockbackScaling,
 
     // Dimensions (for physics collider)
     /// Character width in world units
@@ -44,9 +92,12 @@ pub const BASE_STATS: CharacterStats = CharacterStats {
     // Physics
     gravity_scale: 1.0,
     fast_fall_multiplier: 2.0,
+    drop_through_time: 0.25,
 
     // Combat
     max_health: 100,
+    spawn_protection_time: 2.0,
+    knockback_scaling: KnockbackScaling::Percent { growth: 1.5 },
 
     // Dimensions - fits the blob sprite nicely
     width: 1.0,
@@ -96,4 +147,19 @@ mod tests {
         assert_eq!(player1.move_speed, player2.move_speed);
         assert_eq!(player2.max_health, player3.max_health);
     }
+
+    #[test]
+    fn test_knockback_scaling() {
+        assert_eq!(KnockbackScaling::Flat.multiplier(150.0), 1.0);
+
+        let percent = KnockbackScaling::Percent { growth: 1.5 };
+        assert_eq!(percent.multiplier(0.0), 1.0);
+        assert_eq!(percent.multiplier(100.0), 2.5);
+
+        let curve = KnockbackScaling::Curve(&[(0.0, 1.0), (50.0, 1.5), (100.0, 3.5)]);
+        assert_eq!(curve.multiplier(-10.0), 1.0);
+        assert_eq!(curve.multiplier(25.0), 1.25);
+        assert_eq!(curve.multiplier(75.0), 2.5);
+        assert_eq!(curve.multiplier(500.0), 3.5);
+    }
 }
@@ -0,0 +1,1035 @@
+// Match controller: lobby, countdown, rounds, results
+//
+// The controller only tracks phases and scores. It tells the game when to
+// reset the arena and characters through `MatchEvent::ResetRound` and when to
+// bring a knocked out player back through `MatchEvent::Respawn`. It reads who
+// is still alive each step, and kill events as they happen, to decide when a
+// round is over and who won it (see `ScoringMode`).
+//
+// Rounds and matches are won by teams. In free-for-all every player is their
+// own team and the team id is the player index, so winners read naturally.
+
+use super::scoring::{net_kill_leaders, KillEvent, PlayerScore, ScoringMode};
+use super::stats::{KillRecord, MatchReport, MatchStats, PlayerReport};
+use super::teams::{FriendlyFire, TeamAssignment, TeamId, TeamMode};
+
+/// Who is holding the capture zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HillControl {
+    /// Nobody is inside
+    Empty,
+    /// Only this team is inside and scoring
+    Held(TeamId),
+    /// Several teams are inside; nobody scores
+    Contested,
+}
+
+/// Phase of a match
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatchPhase {
+    /// Waiting for players to join
+    Lobby,
+    /// Round is about to start; input is frozen
+    Countdown { remaining: f32 },
+    /// Round in progress
+    InRound,
+    /// Round just ended; the game runs in slow motion
+    RoundOver {
+        remaining: f32,
+        winner: Option<TeamId>,
+    },
+    /// Scores are shown before the next round
+    Scoreboard { remaining: f32 },
+    /// Someone won the match
+    MatchOver { winner: Option<TeamId> },
+}
+
+/// Something that happened this step
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchEvent {
+    PlayerJoined(usize),
+    PlayerLeft(usize),
+    /// Clear projectiles, rebuild the arena and respawn every active player
+    ResetRound {
+        round: u32,
+    },
+    /// Whole seconds left in the countdown
+    Countdown {
+        seconds: u32,
+    },
+    RoundStarted {
+        round: u32,
+    },
+    /// Bring a knocked out player back mid-round
+    Respawn {
+        player_index: usize,
+    },
+    /// A player lost their last stock
+    Eliminated {
+        player_index: usize,
+    },
+    RoundOver {
+        round: u32,
+        winner: Option<TeamId>,
+    },
+    /// The match ended in a tie; these players fight one more round
+    SuddenDeath {
+        players: Vec<usize>,
+    },
+    MatchOver {
+        winner: Option<TeamId>,
+    },
+}
+
+/// Timing and win conditions of a match
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchConfig {
+    /// Players needed before the match can start
+    pub min_players: usize,
+    /// Length of the pre-round countdown (seconds)
+    pub countdown_time: f32,
+    /// How long the round-over slow motion lasts (real seconds)
+    pub round_over_time: f32,
+    /// Game speed during the round-over slow motion
+    pub slow_motion_scale: f32,
+    /// How long scores are shown between rounds (seconds)
+    pub scoreboard_time: f32,
+    /// Delay before a knocked out player respawns in respawning modes (seconds)
+    pub respawn_delay: f32,
+    /// How the match is won
+    pub scoring: ScoringMode,
+    /// How players are split into teams
+    pub teams: TeamMode,
+    /// What hits between teammates do
+    pub friendly_fire: FriendlyFire,
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        Self {
+            min_players: 2,
+            countdown_time: 3.0,
+            round_over_time: 2.0,
+            slow_motion_scale: 0.25,
+            scoreboard_time: 3.0,
+            respawn_delay: 2.0,
+            scoring: ScoringMode::default(),
+            teams: TeamMode::default(),
+            friendly_fire: FriendlyFire::default(),
+        }
+    }
+}
+
+impl MatchConfig {
+    /// Set how many players are needed to start
+    pub fn with_min_players(mut self, min_players: usize) -> Self {
+        self.min_players = min_players;
+        self
+    }
+
+    /// Set how the match is won
+    pub fn with_scoring(mut self, scoring: ScoringMode) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Set how players are split into teams and what teammates' hits do
+    pub fn with_teams(mut self, teams: TeamMode, friendly_fire: FriendlyFire) -> Self {
+        self.teams = teams;
+        self.friendly_fire = friendly_fire;
+        self
+    }
+}
+
+/// A player taking part in the match
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Participant {
+    pub player_index: usize,
+    pub score: PlayerScore,
+}
+
+/// What happens once the scoreboard has been shown
+#[derive(Debug, Clone, PartialEq)]
+enum RoundOutcome {
+    NextRound,
+    SuddenDeath(Vec<usize>),
+    MatchOver(Option<TeamId>),
+}
+
+/// Drives a match from the lobby to the final winner
+#[derive(Debug)]
+pub struct MatchController {
+    pub config: MatchConfig,
+    phase: MatchPhase,
+    participants: Vec<Participant>,
+    teams: TeamAssignment,
+    /// Kills counted this match, in order
+    kills: Vec<KillEvent>,
+    /// Per-player stats of this match (kept after it ends for the results screen)
+    stats: MatchStats,
+    /// Current round (1-based, 0 before the first round)
+    round: u32,
+    /// Time spent in the current round (seconds)
+    round_time: f32,
+    /// Players in a sudden death round (None = a regular round)
+    sudden_death: Option<Vec<usize>>,
+    /// Knocked out players waiting to respawn, with the time left
+    pending_respawns: Vec<(usize, f32)>,
+    /// Players still in the fight at the previous step (tie-breaks for double KOs)
+    standing: Vec<usize>,
+    outcome: RoundOutcome,
+}
+
+impl MatchController {
+    pub fn new(config: MatchConfig) -> Self {
+        Self {
+            config,
+            phase: MatchPhase::Lobby,
+            participants: Vec::new(),
+            teams: TeamAssignment::default(),
+            kills: Vec::new(),
+            stats: MatchStats::new(),
+            round: 0,
+            round_time: 0.0,
+            sudden_death: None,
+            pending_respawns: Vec::new(),
+            standing: Vec::new(),
+            outcome: RoundOutcome::NextRound,
+        }
+    }
+
+    /// Get the current phase
+    pub fn phase(&self) -> MatchPhase {
+        self.phase
+    }
+
+    /// Get the current round (0 before the first round)
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// Get the seconds since the current round started
+    pub fn round_time(&self) -> f32 {
+        self.round_time
+    }
+
+    /// Get the kills counted this match, in order
+    pub fn kill_log(&self) -> &[KillEvent] {
+        &self.kills
+    }
+
+    /// Get the stats of the current (or last finished) match
+    pub fn stats(&self) -> &MatchStats {
+        &self.stats
+    }
+
+    /// Get the stats to add to, only while a round is being fought
+    pub fn stats_mut(&mut self) -> Option<&mut MatchStats> {
+        (self.phase == MatchPhase::InRound).then_some(&mut self.stats)
+    }
+
+    /// Build the report of the match so far, naming players with `name`
+    pub fn report(&self, name: impl Fn(usize) -> String) -> MatchReport {
+        let finished_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let players = self
+            .participants
+            .iter()
+            .map(|participant| {
+                let index = participant.player_index;
+                let stats = self.stats.player(index).cloned().unwrap_or_default();
+                PlayerReport {
+                    player_index: index,
+                    name: name(index),
+                    team: self.teams.team_of(index),
+                    round_wins: participant.score.round_wins,
+                    accuracy: stats.accuracy(),
+                    stats,
+                }
+            })
+            .collect();
+
+        MatchReport {
+            finished_at,
+            mode: self.scoring().describe(),
+            teams: self.teams.mode().describe(),
+            rounds: self.round,
+            winner: self.winner(),
+            players,
+            kills: self.kills.iter().map(KillRecord::from).collect(),
+        }
+    }
+
+    /// Get the players taking part, in join order
+    pub fn participants(&self) -> &[Participant] {
+        &self.participants
+    }
+
+    /// Get the team assignment of the current match
+    pub fn teams(&self) -> &TeamAssignment {
+        &self.teams
+    }
+
+    /// Get a participant's score
+    pub fn score(&self, player_index: usize) -> Option<&PlayerScore> {
+        self.participants
+            .iter()
+            .find(|p| p.player_index == player_index)
+            .map(|p| &p.score)
+    }
+
+    /// Check if a player has joined
+    pub fn is_participant(&self, player_index: usize) -> bool {
+        self.participants
+            .iter()
+            .any(|p| p.player_index == player_index)
+    }
+
+    /// Get the players fighting this round (everyone, or the sudden death players)
+    pub fn active_players(&self) -> Vec<usize> {
+        match &self.sudden_death {
+            Some(players) => players.clone(),
+            None => self.participants.iter().map(|p| p.player_index).collect(),
+        }
+    }
+
+    /// Check if the current round is a sudden death tie-break
+    pub fn is_sudden_death(&self) -> bool {
+        self.sudden_death.is_some()
+    }
+
+    /// Get the time left in a timed round (None = untimed)
+    pub fn time_left(&self) -> Option<f32> {
+        match self.scoring() {
+            ScoringMode::TimedKills { duration } => Some((duration - self.round_time).max(0.0)),
+            _ => None,
+        }
+    }
+
+    /// Add a player while in the lobby
+    pub fn join(&mut self, player_index: usize) -> Option<MatchEvent> {
+        if self.phase != MatchPhase::Lobby || self.is_participant(player_index) {
+            return None;
+        }
+        self.participants.push(Participant {
+            player_index,
+            score: PlayerScore::default(),
+        });
+        Some(MatchEvent::PlayerJoined(player_index))
+    }
+
+    /// Remove a player while in the lobby
+    pub fn leave(&mut self, player_index: usize) -> Option<MatchEvent> {
+        if self.phase != MatchPhase::Lobby || !self.is_participant(player_index) {
+            return None;
+        }
+        self.participants.retain(|p| p.player_index != player_index);
+        Some(MatchEvent::PlayerLeft(player_index))
+    }
+
+    /// Check if enough players have joined to start
+    pub fn can_start(&self) -> bool {
+        self.phase == MatchPhase::Lobby && self.participants.len() >= self.config.min_players
+    }
+
+    /// Leave the lobby and count down to the first round
+    pub fn start(&mut self) -> Vec<MatchEvent> {
+        if !self.can_start() {
+            return Vec::new();
+        }
+        let players: Vec<usize> = self.participants.iter().map(|p| p.player_index).collect();
+        self.teams = TeamAssignment::assign(self.config.teams, &players, self.config.friendly_fire);
+        self.kills.clear();
+        self.stats.clear();
+        self.begin_countdown(None)
+    }
+
+    /// Go back to the lobby, keeping the players but clearing scores
+    pub fn return_to_lobby(&mut self) {
+        self.phase = MatchPhase::Lobby;
+        self.round = 0;
+        self.sudden_death = None;
+        self.pending_respawns.clear();
+        for participant in &mut self.participants {
+            participant.score = PlayerScore::default();
+        }
+    }
+
+    /// Check if players may control their characters
+    pub fn is_input_frozen(&self) -> bool {
+        matches!(
+            self.phase,
+            MatchPhase::Countdown { .. } | MatchPhase::Scoreboard { .. }
+        )
+    }
+
+    /// Get the game speed for this phase (1.0 = normal)
+    pub fn time_scale(&self) -> f32 {
+        match self.phase {
+            MatchPhase::RoundOver { .. } => self.config.slow_motion_scale,
+            _ => 1.0,
+        }
+    }
+
+    /// Get the winning team once the match is over
+    pub fn winner(&self) -> Option<TeamId> {
+        match self.phase {
+            MatchPhase::MatchOver { winner } => winner,
+            _ => None,
+        }
+    }
+
+    /// Score the capture zone for the players standing in it
+    /// Only a single team inside scores; a contested zone freezes scoring.
+    /// The holding team gains `dt` per step however many members stand inside,
+    /// split evenly between them
+    pub fn update_hill(&mut self, occupants: &[usize], dt: f32) -> HillControl {
+        let mut occupants: Vec<usize> = occupants
+            .iter()
+            .copied()
+            .filter(|&index| self.is_participant(index))
+            .collect();
+        occupants.sort_unstable();
+        occupants.dedup();
+        let control = match self.unique_teams(&occupants).as_slice() {
+            [] => HillControl::Empty,
+            [team] => HillControl::Held(*team),
+            _ => HillControl::Contested,
+        };
+
+        let scoring = matches!(self.scoring(), ScoringMode::KingOfTheHill { .. });
+        if self.phase == MatchPhase::InRound && scoring && control != HillControl::Contested {
+            let share = dt / occupants.len().max(1) as f32;
+            for &index in &occupants {
+                if let Some(participant) = self.participant_mut(index) {
+                    participant.score.hill_time += share;
+                }
+            }
+        }
+        control
+    }
+
+    /// Count a knockout towards the scores
+    /// Only kills during a round count, and knocking out a teammate only counts as a death
+    /// Returns an event if the victim ran out of stocks
+    pub fn record_kill(&mut self, mut kill: KillEvent) -> Option<MatchEvent> {
+        if self.phase != MatchPhase::InRound || !self.active_players().contains(&kill.victim) {
+            return None;
+        }
+        kill.teamkill = kill
+            .killer
+            .is_some_and(|killer| self.teams.are_teammates(killer, kill.victim));
+        self.kills.push(kill);
+
+        let killer = kill.credited_killer();
+        if let Some(killer) = killer {
+            if let Some(participant) = self.participant_mut(killer) {
+                participant.score.kills += 1;
+            }
+        }
+        self.stats.record_kill(kill.victim, killer);
+
+        let respawns = self.respawns();
+        let respawn_delay = self.config.respawn_delay;
+        let victim = self.participant_mut(kill.victim)?;
+        victim.score.deaths += 1;
+        if let Some(stocks) = &mut victim.score.stocks {
+            *stocks = stocks.saturating_sub(1);
+        }
+
+        if victim.score.is_eliminated() {
+            return Some(MatchEvent::Eliminated {
+                player_index: kill.victim,
+            });
+        }
+        if respawns {
+            self.pending_respawns.push((kill.victim, respawn_delay));
+        }
+        None
+    }
+
+    /// Advance timers and end the round once its win condition is met
+    /// `dt` is real time; `alive` lists the player indices still in the round
+    pub fn update(&mut self, dt: f32, alive: &[usize]) -> Vec<MatchEvent> {
+        match self.phase {
+            MatchPhase::Lobby | MatchPhase::MatchOver { .. } => Vec::new(),
+            MatchPhase::Countdown { remaining } => {
+                let next = remaining - dt;
+                if next <= 0.0 {
+                    self.phase = MatchPhase::InRound;
+                    return vec![MatchEvent::RoundStarted { round: self.round }];
+                }
+                self.phase = MatchPhase::Countdown { remaining: next };
+                if next.ceil() < remaining.ceil() {
+                    return vec![MatchEvent::Countdown {
+                        seconds: next.ceil() as u32,
+                    }];
+                }
+                Vec::new()
+            }
+            MatchPhase::InRound => self.update_round(dt, alive),
+            MatchPhase::RoundOver { remaining, winner } => {
+                let next = remaining - dt;
+                self.phase = if next <= 0.0 {
+                    MatchPhase::Scoreboard {
+                        remaining: self.config.scoreboard_time,
+                    }
+                } else {
+                    MatchPhase::RoundOver {
+                        remaining: next,
+                        winner,
+                    }
+                };
+                Vec::new()
+            }
+            MatchPhase::Scoreboard { remaining } => {
+                let next = remaining - dt;
+                if next > 0.0 {
+                    self.phase = MatchPhase::Scoreboard { remaining: next };
+                    return Vec::new();
+                }
+
+                match self.outcome.clone() {
+                    RoundOutcome::NextRound => self.begin_countdown(None),
+                    RoundOutcome::SuddenDeath(players) => {
+                        let mut events = vec![MatchEvent::SuddenDeath {
+                            players: players.clone(),
+                        }];
+                        events.extend(self.begin_countdown(Some(players)));
+                        events
+                    }
+                    RoundOutcome::MatchOver(winner) => {
+                        self.phase = MatchPhase::MatchOver { winner };
+                        vec![MatchEvent::MatchOver { winner }]
+                    }
+                }
+            }
+        }
+    }
+
+    fn update_round(&mut self, dt: f32, alive: &[usize]) -> Vec<MatchEvent> {
+        self.round_time += dt;
+
+        let mut events = Vec::new();
+        for (player_index, time_left) in &mut self.pending_respawns {
+            *time_left -= dt;
+            if *time_left <= 0.0 {
+                events.push(MatchEvent::Respawn {
+                    player_index: *player_index,
+                });
+            }
+        }
+        self.pending_respawns
+            .retain(|(_, time_left)| *time_left > 0.0);
+
+        // Players still in the fight: alive, or waiting to respawn with stocks left
+        let active = self.active_players();
+        let standing: Vec<usize> = if self.respawns() {
+            active
+                .iter()
+                .copied()
+                .filter(|&index| self.score(index).is_some_and(|s| !s.is_eliminated()))
+                .collect()
+        } else {
+            active
+                .iter()
+                .copied()
+                .filter(|index| alive.contains(index))
+                .collect()
+        };
+
+        let active_teams = self.unique_teams(&active);
+        let standing_teams = self.unique_teams(&standing);
+        let over = match self.scoring() {
+            ScoringMode::TimedKills { duration } => self.round_time >= duration,
+            ScoringMode::KingOfTheHill { target } => self
+                .team_totals(&active, &active_teams)
+                .iter()
+                .any(|(_, total)| total.hill_time >= target),
+            // A lone team plays until it falls; otherwise the last team standing wins
+            _ if active_teams.len() > 1 => standing_teams.len() <= 1,
+            _ => standing.is_empty(),
+        };
+        if !over {
+            self.standing = standing;
+            return events;
+        }
+
+        // Players knocked out together share the tie
+        let (winner, contenders) = match self.scoring() {
+            ScoringMode::TimedKills { .. } => {
+                // Teams are ranked by their members' combined kills and deaths
+                let scores = self.team_totals(&active, &active_teams);
+                let leaders = net_kill_leaders(&scores);
+                match leaders.as_slice() {
+                    [winner] => (Some(*winner), Vec::new()),
+                    _ => (
+                        None,
+                        active
+                            .iter()
+                            .copied()
+                            .filter(|&index| leaders.contains(&self.teams.team_of(index)))
+                            .collect(),
+                    ),
+                }
+            }
+            ScoringMode::KingOfTheHill { .. } => {
+                let winner = self
+                    .team_totals(&active, &active_teams)
+                    .into_iter()
+                    .max_by(|(_, a), (_, b)| a.hill_time.total_cmp(&b.hill_time))
+                    .map(|(team, _)| team);
+                (winner, Vec::new())
+            }
+            _ if active_teams.len() > 1 => match standing_teams.as_slice() {
+                [winner] => (Some(*winner), Vec::new()),
+                _ => (None, std::mem::take(&mut self.standing)),
+            },
+            _ => (None, Vec::new()),
+        };
+
+        if let Some(team) = winner {
+            for participant in &mut self.participants {
+                if self.teams.team_of(participant.player_index) == team {
+                    participant.score.round_wins += 1;
+                }
+            }
+        }
+
+        self.outcome = match (winner, self.scoring()) {
+            (Some(winner), ScoringMode::RoundWins(rounds)) => {
+                let wins = self
+                    .participants
+                    .iter()
+                    .find(|p| self.teams.team_of(p.player_index) == winner)
+                    .map_or(0, |p| p.score.round_wins);
+                if wins >= rounds {
+                    RoundOutcome::MatchOver(Some(winner))
+                } else {
+                    RoundOutcome::NextRound
+                }
+            }
+            (Some(winner), _) => RoundOutcome::MatchOver(Some(winner)),
+            // Drawn rounds are simply replayed when rounds are being counted
+            (None, ScoringMode::RoundWins(_)) => RoundOutcome::NextRound,
+            (None, _) if contenders.len() > 1 => RoundOutcome::SuddenDeath(contenders),
+            (None, _) => RoundOutcome::NextRound,
+        };
+
+        self.phase = MatchPhase::RoundOver {
+            remaining: self.config.round_over_time,
+            winner,
+        };
+        events.push(MatchEvent::RoundOver {
+            round: self.round,
+            winner,
+        });
+        events
+    }
+
+    /// Scoring rules for the current round; sudden death is always last one standing
+    fn scoring(&self) -> ScoringMode {
+        if self.sudden_death.is_some() {
+            ScoringMode::LastStanding
+        } else {
+            self.config.scoring
+        }
+    }
+
+    fn respawns(&self) -> bool {
+        self.scoring().respawns()
+    }
+
+    /// Sum up the scores of each team's members
+    fn team_totals(&self, players: &[usize], teams: &[TeamId]) -> Vec<(TeamId, PlayerScore)> {
+        teams
+            .iter()
+            .map(|&team| {
+                let mut total = PlayerScore::default();
+                for score in players
+                    .iter()
+                    .filter(|&&index| self.teams.team_of(index) == team)
+                    .filter_map(|&index| self.score(index))
+                {
+                    total.kills += score.kills;
+                    total.deaths += score.deaths;
+                    total.hill_time += score.hill_time;
+                }
+                (team, total)
+            })
+            .collect()
+    }
+
+    /// Get the distinct teams of some players, in ascending order
+    fn unique_teams(&self, players: &[usize]) -> Vec<TeamId> {
+        let mut teams: Vec<TeamId> = players
+            .iter()
+            .map(|&index| self.teams.team_of(index))
+            .collect();
+        teams.sort_unstable();
+        teams.dedup();
+        teams
+    }
+
+    fn participant_mut(&mut self, player_index: usize) -> Option<&mut Participant> {
+        self.participants
+            .iter_mut()
+            .find(|p| p.player_index == player_index)
+    }
+
+    fn begin_countdown(&mut self, sudden_death: Option<Vec<usize>>) -> Vec<MatchEvent> {
+        self.round += 1;
+        self.round_time = 0.0;
+        self.sudden_death = sudden_death;
+        self.pending_respawns.clear();
+        self.standing = self.active_players();
+        self.outcome = RoundOutcome::NextRound;
+
+        let stocks = self.scoring().starting_stocks();
+        for participant in &mut self.participants {
+            participant.score.stocks = stocks;
+        }
+
+        self.phase = MatchPhase::Countdown {
+            remaining: self.config.countdown_time,
+        };
+        vec![
+            MatchEvent::ResetRound { round: self.round },
+            MatchEvent::Countdown {
+                seconds: self.config.countdown_time.ceil() as u32,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::characters::KillCause;
+
+    fn run(controller: &mut MatchController, seconds: f32, alive: &[usize]) -> Vec<MatchEvent> {
+        let mut events = Vec::new();
+        for _ in 0..(seconds * 10.0).round() as usize {
+            events.extend(controller.update(0.1, alive));
+        }
+        events
+    }
+
+    fn kill(victim: usize, killer: Option<usize>) -> KillEvent {
+        KillEvent::new(victim, killer, KillCause::BlastZone)
+    }
+
+    fn started_match(scoring: ScoringMode, players: usize) -> MatchController {
+        start_match(MatchConfig::default().with_scoring(scoring), players)
+    }
+
+    fn start_match(config: MatchConfig, players: usize) -> MatchController {
+        let mut controller = MatchController::new(config);
+        for player in 0..players {
+            controller.join(player);
+        }
+        controller.start();
+        run(&mut controller, 3.5, &[0, 1, 2, 3]);
+        controller
+    }
+
+    #[test]
+    fn test_lobby_join_and_start() {
+        let mut controller = MatchController::new(MatchConfig::default());
+        assert_eq!(controller.join(0), Some(MatchEvent::PlayerJoined(0)));
+        assert_eq!(controller.join(0), None);
+        assert!(controller.start().is_empty(), "Needs two players");
+
+        controller.join(1);
+        let events = controller.start();
+        assert_eq!(events[0], MatchEvent::ResetRound { round: 1 });
+        assert!(controller.is_input_frozen());
+        assert_eq!(controller.join(2), None, "Can't join mid-match");
+    }
+
+    #[test]
+    fn test_countdown_then_round() {
+        let mut controller = MatchController::new(MatchConfig::default());
+        controller.join(0);
+        controller.join(1);
+        controller.start();
+
+        let events = run(&mut controller, 3.5, &[0, 1]);
+        assert_eq!(
+            events,
+            vec![
+                MatchEvent::Countdown { seconds: 2 },
+                MatchEvent::Countdown { seconds: 1 },
+                MatchEvent::RoundStarted { round: 1 },
+            ]
+        );
+        assert_eq!(controller.phase(), MatchPhase::InRound);
+        assert!(!controller.is_input_frozen());
+    }
+
+    #[test]
+    fn test_round_over_slow_motion_and_next_round() {
+        let mut controller = started_match(ScoringMode::RoundWins(2), 2);
+
+        let events = controller.update(0.1, &[1]);
+        assert_eq!(
+            events,
+            vec![MatchEvent::RoundOver {
+                round: 1,
+                winner: Some(1)
+            }]
+        );
+        assert!(controller.time_scale() < 1.0);
+
+        // Slow motion, scoreboard, then the next round resets the arena
+        let events = run(&mut controller, 5.5, &[1]);
+        assert!(matches!(controller.phase(), MatchPhase::Countdown { .. }));
+        assert!(events.contains(&MatchEvent::ResetRound { round: 2 }));
+        assert_eq!(controller.score(1).unwrap().round_wins, 1);
+    }
+
+    #[test]
+    fn test_first_to_round_wins() {
+        let mut controller = started_match(ScoringMode::RoundWins(2), 2);
+
+        let mut events = Vec::new();
+        for _ in 0..2 {
+            events.extend(controller.update(0.1, &[0]));
+            events.extend(run(&mut controller, 5.5, &[0]));
+            run(&mut controller, 3.5, &[0, 1]);
+        }
+
+        assert_eq!(
+            events.last(),
+            Some(&MatchEvent::MatchOver { winner: Some(0) })
+        );
+        assert_eq!(controller.winner(), Some(0));
+
+        controller.return_to_lobby();
+        assert_eq!(controller.phase(), MatchPhase::Lobby);
+        assert_eq!(controller.score(0).unwrap().round_wins, 0);
+    }
+
+    #[test]
+    fn test_drawn_round_is_replayed() {
+        let mut controller = started_match(ScoringMode::RoundWins(2), 2);
+
+        let events = controller.update(0.1, &[]);
+        assert_eq!(
+            events,
+            vec![MatchEvent::RoundOver {
+                round: 1,
+                winner: None
+            }]
+        );
+        let events = run(&mut controller, 5.5, &[]);
+        assert!(events.contains(&MatchEvent::ResetRound { round: 2 }));
+        assert!(!controller.is_sudden_death());
+    }
+
+    #[test]
+    fn test_stocks() {
+        let mut controller = started_match(ScoringMode::Stocks(2), 2);
+
+        // First death respawns after the delay, the second eliminates
+        assert_eq!(controller.record_kill(kill(1, Some(0))), None);
+        let events = run(&mut controller, 2.5, &[0]);
+        assert_eq!(events, vec![MatchEvent::Respawn { player_index: 1 }]);
+
+        assert_eq!(
+            controller.record_kill(kill(1, Some(0))),
+            Some(MatchEvent::Eliminated { player_index: 1 })
+        );
+        let events = controller.update(0.1, &[0]);
+        assert!(events.contains(&MatchEvent::RoundOver {
+            round: 1,
+            winner: Some(0)
+        }));
+        run(&mut controller, 5.5, &[0]);
+        assert_eq!(controller.winner(), Some(0));
+        assert_eq!(controller.score(0).unwrap().kills, 2);
+        assert_eq!(controller.score(1).unwrap().deaths, 2);
+    }
+
+    #[test]
+    fn test_timed_kills_ranked_by_net_kills() {
+        let mut controller = started_match(ScoringMode::TimedKills { duration: 10.0 }, 3);
+
+        controller.record_kill(kill(1, Some(0)));
+        controller.record_kill(kill(2, Some(0)));
+        controller.record_kill(kill(0, Some(2)));
+        // Self-destructs only count as deaths
+        controller.record_kill(kill(2, None));
+        assert_eq!(controller.score(0).unwrap().net_kills(), 1);
+        assert_eq!(controller.score(2).unwrap().net_kills(), -1);
+
+        let events = run(&mut controller, 10.0, &[0, 1, 2]);
+        assert!(events.contains(&MatchEvent::Respawn { player_index: 1 }));
+        assert!(events.contains(&MatchEvent::RoundOver {
+            round: 1,
+            winner: Some(0)
+        }));
+        assert_eq!(controller.time_left(), Some(0.0));
+    }
+
+    #[test]
+    fn test_ties_go_to_sudden_death() {
+        let mut controller = started_match(ScoringMode::TimedKills { duration: 5.0 }, 3);
+        controller.record_kill(kill(2, Some(0)));
+        controller.record_kill(kill(2, Some(1)));
+
+        let mut events = run(&mut controller, 5.0, &[0, 1, 2]);
+        events.extend(run(&mut controller, 5.5, &[0, 1, 2]));
+        assert!(events.contains(&MatchEvent::SuddenDeath {
+            players: vec![0, 1]
+        }));
+        assert!(controller.is_sudden_death());
+        assert_eq!(controller.active_players(), vec![0, 1]);
+
+        // Sudden death is last one standing with a single stock
+        run(&mut controller, 3.5, &[0, 1]);
+        assert_eq!(controller.time_left(), None);
+        controller.record_kill(kill(0, Some(1)));
+        controller.update(0.1, &[1]);
+        run(&mut controller, 5.5, &[1]);
+        assert_eq!(controller.winner(), Some(1));
+    }
+
+    #[test]
+    fn test_last_standing_double_ko_goes_to_sudden_death() {
+        let mut controller = started_match(ScoringMode::LastStanding, 3);
+
+        controller.update(0.1, &[0, 1]);
+        let events = controller.update(0.1, &[]);
+        assert!(events.contains(&MatchEvent::RoundOver {
+            round: 1,
+            winner: None
+        }));
+
+        let events = run(&mut controller, 5.5, &[]);
+        assert!(events.contains(&MatchEvent::SuddenDeath {
+            players: vec![0, 1]
+        }));
+    }
+
+    #[test]
+    fn test_last_team_standing_wins() {
+        let config = MatchConfig::default()
+            .with_scoring(ScoringMode::RoundWins(1))
+            .with_teams(TeamMode::Teams(2), FriendlyFire::OFF);
+        let mut controller = start_match(config, 4);
+
+        // Players 0 and 2 are teammates; the round goes on while both teams stand
+        assert!(controller.update(0.1, &[0, 1]).is_empty());
+        let events = controller.update(0.1, &[0, 2]);
+        assert!(events.contains(&MatchEvent::RoundOver {
+            round: 1,
+            winner: Some(0)
+        }));
+        assert_eq!(controller.score(2).unwrap().round_wins, 1);
+        assert_eq!(controller.score(1).unwrap().round_wins, 0);
+
+        run(&mut controller, 5.5, &[0, 2]);
+        assert_eq!(controller.winner(), Some(0));
+    }
+
+    #[test]
+    fn test_team_kills_are_not_credited() {
+        let config = MatchConfig::default()
+            .with_scoring(ScoringMode::TimedKills { duration: 60.0 })
+            .with_teams(TeamMode::Teams(2), FriendlyFire::ON);
+        let mut controller = start_match(config, 4);
+
+        controller.record_kill(kill(2, Some(0)));
+        assert_eq!(controller.score(0).unwrap().kills, 0);
+        assert_eq!(controller.score(2).unwrap().deaths, 1);
+        assert!(controller.kill_log()[0].teamkill);
+
+        controller.record_kill(kill(1, Some(0)));
+        assert_eq!(controller.score(0).unwrap().kills, 1);
+
+        // Every counted kill is logged, credited or not
+        let victims: Vec<usize> = controller.kill_log().iter().map(|k| k.victim).collect();
+        assert_eq!(victims, vec![2, 1]);
+
+        // The report credits kills the same way the scores do
+        let report = controller.report(|index| format!("P{}", index + 1));
+        assert_eq!(report.kills[0].killer, None);
+        assert!(report.kills[0].teamkill);
+        assert_eq!(report.kills[1].killer, Some(0));
+        assert_eq!(report.players[0].stats.kills, 1);
+    }
+
+    #[test]
+    fn test_stats_are_counted_during_rounds() {
+        let mut controller = started_match(ScoringMode::Stocks(2), 2);
+        controller
+            .stats_mut()
+            .unwrap()
+            .record_ability_use(1, "ability_1");
+        controller
+            .stats_mut()
+            .unwrap()
+            .record_damage(0, Some(1), 40, None);
+        controller.record_kill(kill(0, Some(1)));
+
+        let report = controller.report(|index| format!("P{}", index + 1));
+        assert_eq!(report.players.len(), 2);
+        assert_eq!(report.players[1].name, "P2");
+        assert_eq!(report.players[1].stats.kills, 1);
+        assert_eq!(report.players[1].stats.damage_dealt, 40);
+        assert_eq!(report.players[1].accuracy, Some(0.0));
+        assert_eq!(report.players[0].stats.deaths, 1);
+        assert_eq!(report.kills.len(), 1);
+
+        // Nothing is counted outside a round, but the results stay until the next match
+        controller.return_to_lobby();
+        assert!(controller.stats_mut().is_none());
+        assert_eq!(controller.stats().player(1).unwrap().kills, 1);
+    }
+
+    #[test]
+    fn test_hill_scores_sole_occupant() {
+        let mut controller = started_match(ScoringMode::KingOfTheHill { target: 1.0 }, 3);
+
+        assert_eq!(controller.update_hill(&[1], 0.5), HillControl::Held(1));
+        assert_eq!(controller.update_hill(&[0, 1], 0.5), HillControl::Contested);
+        assert_eq!(controller.score(1).unwrap().hill_time, 0.5);
+        assert_eq!(controller.score(0).unwrap().hill_time, 0.0);
+
+        // Knockouts don't end the round; holding the zone does
+        controller.record_kill(kill(2, Some(1)));
+        assert!(controller.update(0.1, &[0, 1]).is_empty());
+        controller.update_hill(&[1], 0.5);
+        let events = controller.update(0.1, &[0, 1]);
+        assert!(events.contains(&MatchEvent::RoundOver {
+            round: 1,
+            winner: Some(1)
+        }));
+    }
+
+    #[test]
+    fn test_hill_counts_once_per_team() {
+        let config = MatchConfig::default()
+            .with_scoring(ScoringMode::KingOfTheHill { target: 1.0 })
+            .with_teams(TeamMode::Teams(2), FriendlyFire::OFF);
+        let mut controller = start_match(config, 4);
+        let team_time = |controller: &MatchController| {
+            controller.score(0).unwrap().hill_time + controller.score(2).unwrap().hill_time
+        };
+
+        // Players 0 and 2 are teammates; standing in the zone together earns no more
+        assert_eq!(controller.update_hill(&[0, 2], 0.5), HillControl::Held(0));
+        assert!((team_time(&controller) - 0.5).abs() < 1e-6);
+        assert!(controller.update(0.1, &[0, 1, 2, 3]).is_empty());
+
+        controller.update_hill(&[0, 2], 0.5);
+        assert!((team_time(&controller) - 1.0).abs() < 1e-6);
+        let events = controller.update(0.1, &[0, 1, 2, 3]);
+        assert!(events.contains(&MatchEvent::RoundOver {
+            round: 1,
+            winner: Some(0)
+        }));
+    }
+}
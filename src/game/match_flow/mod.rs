@@ -0,0 +1,20 @@
+// Match flow
+//
+// This module contains everything that turns fights into a match:
+// - The match controller (lobby, countdown, rounds, scoreboard, match end)
+// - Scoring modes (last one standing, round wins, stocks, timed kills)
+// - Teams (free-for-all or fixed teams) and friendly-fire rules
+// - End-of-match statistics and the JSON match report
+
+pub mod controller;
+pub mod scoring;
+pub mod stats;
+pub mod teams;
+
+// Re-export commonly used types
+pub use controller::{
+    HillControl, MatchConfig, MatchController, MatchEvent, MatchPhase, Participant,
+};
+pub use scoring::{KillEvent, PlayerScore, ScoringMode};
+pub use stats::{AbilityStats, MatchReport, MatchStats, PlayerStats, StatsError};
+pub use teams::{FriendlyFire, HitResult, TeamAssignment, TeamId, TeamMode, TEAM_COLORS};
@@ -0,0 +1,193 @@
+// Win conditions and kill scoring
+//
+// Scores only change through kill events, so every way of dying (blast
+// zones, hazards, attacks) counts the same once it names a victim and
+// (optionally) the player credited with the kill. The cause and time ride
+// along for the UI, logs and end-of-match stats.
+
+use crate::game::characters::KillCause;
+
+/// How a match is won
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScoringMode {
+    /// A single round; the last player standing wins the match
+    LastStanding,
+    /// Last one standing wins a round; first to this many round wins takes the match
+    RoundWins(u32),
+    /// Each player has this many lives; the last player with lives left wins
+    Stocks(u32),
+    /// Players respawn until time runs out; most kills minus deaths wins
+    TimedKills { duration: f32 },
+    /// Players respawn; the first to hold the capture zone alone for this many seconds wins
+    KingOfTheHill { target: f32 },
+}
+
+impl Default for ScoringMode {
+    fn default() -> Self {
+        Self::RoundWins(3)
+    }
+}
+
+impl ScoringMode {
+    /// Modes offered in the lobby, in the order they are cycled through
+    pub const PRESETS: [ScoringMode; 5] = [
+        ScoringMode::RoundWins(3),
+        ScoringMode::LastStanding,
+        ScoringMode::Stocks(3),
+        ScoringMode::TimedKills { duration: 120.0 },
+        ScoringMode::KingOfTheHill { target: 60.0 },
+    ];
+
+    /// Check if knocked out players come back during a round
+    pub fn respawns(&self) -> bool {
+        matches!(
+            self,
+            Self::Stocks(_) | Self::TimedKills { .. } | Self::KingOfTheHill { .. }
+        )
+    }
+
+    /// Lives each player starts a round with (None = unlimited)
+    pub fn starting_stocks(&self) -> Option<u32> {
+        match self {
+            Self::Stocks(stocks) => Some(*stocks),
+            Self::TimedKills { .. } | Self::KingOfTheHill { .. } => None,
+            _ => Some(1),
+        }
+    }
+
+    /// Get the next preset after this mode
+    pub fn next_preset(&self) -> Self {
+        let index = Self::PRESETS
+            .iter()
+            .position(|mode| mode == self)
+            .map_or(0, |index| (index + 1) % Self::PRESETS.len());
+        Self::PRESETS[index]
+    }
+
+    /// Get a short description for menus and logs
+    pub fn describe(&self) -> String {
+        match self {
+            Self::LastStanding => "Last one standing".to_string(),
+            Self::RoundWins(rounds) => format!("First to {} round wins", rounds),
+            Self::Stocks(stocks) => format!("{} stocks", stocks),
+            Self::TimedKills { duration } => {
+                format!("{:.0} second timed match (kills minus deaths)", duration)
+            }
+            Self::KingOfTheHill { target } => {
+                format!("King of the hill (first to {:.0} seconds)", target)
+            }
+        }
+    }
+}
+
+/// A player was knocked out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KillEvent {
+    pub victim: usize,
+    /// Player credited with the kill (None or the victim = self-destruct)
+    pub killer: Option<usize>,
+    /// What did it
+    pub cause: KillCause,
+    /// Round it happened in
+    pub round: u32,
+    /// Seconds into the round
+    pub time: f32,
+    /// The killer was on the victim's team (set when the match records it)
+    pub teamkill: bool,
+}
+
+impl KillEvent {
+    pub fn new(victim: usize, killer: Option<usize>, cause: KillCause) -> Self {
+        Self {
+            victim,
+            killer,
+            cause,
+            round: 0,
+            time: 0.0,
+            teamkill: false,
+        }
+    }
+
+    /// Set when the kill happened
+    pub fn with_time(mut self, round: u32, time: f32) -> Self {
+        self.round = round;
+        self.time = time;
+        self
+    }
+
+    /// Get the player the kill counts for, if it wasn't a self-destruct or teamkill
+    pub fn credited_killer(&self) -> Option<usize> {
+        self.killer
+            .filter(|&killer| killer != self.victim && !self.teamkill)
+    }
+}
+
+/// Running score of one player
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PlayerScore {
+    pub round_wins: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    /// Lives left this round (None = unlimited)
+    pub stocks: Option<u32>,
+    /// Seconds spent holding the capture zone
+    pub hill_time: f32,
+}
+
+impl PlayerScore {
+    /// Get kills minus deaths
+    pub fn net_kills(&self) -> i32 {
+        self.kills as i32 - self.deaths as i32
+    }
+
+    /// Check if the player is out of lives for this round
+    pub fn is_eliminated(&self) -> bool {
+        self.stocks == Some(0)
+    }
+}
+
+/// Get the players sharing the best kills-minus-deaths score
+pub fn net_kill_leaders(scores: &[(usize, PlayerScore)]) -> Vec<usize> {
+    let Some(best) = scores.iter().map(|(_, score)| score.net_kills()).max() else {
+        return Vec::new();
+    };
+    scores
+        .iter()
+        .filter(|(_, score)| score.net_kills() == best)
+        .map(|(player, _)| *player)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_destruct_is_not_credited() {
+        let kill = |victim, killer| KillEvent::new(victim, killer, KillCause::BlastZone);
+        assert_eq!(kill(0, Some(1)).credited_killer(), Some(1));
+        assert_eq!(kill(0, Some(0)).credited_killer(), None);
+        assert_eq!(kill(0, None).credited_killer(), None);
+    }
+
+    #[test]
+    fn test_net_kill_leaders() {
+        let score = |kills, deaths| PlayerScore {
+            kills,
+            deaths,
+            ..PlayerScore::default()
+        };
+        let scores = [(0, score(3, 1)), (1, score(4, 2)), (2, score(5, 4))];
+        assert_eq!(net_kill_leaders(&scores), vec![0, 1]);
+        assert!(net_kill_leaders(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_presets_cycle() {
+        let mut mode = ScoringMode::default();
+        for _ in 0..ScoringMode::PRESETS.len() {
+            mode = mode.next_preset();
+        }
+        assert_eq!(mode, ScoringMode::default());
+    }
+}
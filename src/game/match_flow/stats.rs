@@ -0,0 +1,281 @@
+// End-of-match statistics
+//
+// Per-player numbers collected while rounds are fought: kills, deaths,
+// damage dealt and taken, ability uses and hits, jumps and time in the air.
+// They are shown on the results screen and written to one JSON file per
+// match (see `MatchReport::save`) so balance can be reviewed afterwards.
+
+use super::scoring::KillEvent;
+use super::teams::TeamId;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Errors that can occur while saving a match report
+#[derive(Debug, thiserror::Error)]
+pub enum StatsError {
+    #[error("could not write match report: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("could not serialize match report: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+/// Uses and hits of one ability
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct AbilityStats {
+    pub uses: u32,
+    pub hits: u32,
+}
+
+impl AbilityStats {
+    /// Get the fraction of uses that hit (None if never used)
+    pub fn accuracy(&self) -> Option<f32> {
+        (self.uses > 0).then(|| self.hits as f32 / self.uses as f32)
+    }
+}
+
+/// Everything counted for one player over a match
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PlayerStats {
+    pub kills: u32,
+    pub deaths: u32,
+    pub damage_dealt: i32,
+    pub damage_taken: i32,
+    /// Per ability, by name (moves like stomps count hits but no uses)
+    pub abilities: BTreeMap<String, AbilityStats>,
+    pub jumps: u32,
+    /// Time spent jumping or falling (seconds)
+    pub airborne_time: f32,
+}
+
+impl PlayerStats {
+    /// Get the total ability uses
+    pub fn ability_uses(&self) -> u32 {
+        self.abilities.values().map(|a| a.uses).sum()
+    }
+
+    /// Get the fraction of ability uses that hit (None if no abilities were used)
+    pub fn accuracy(&self) -> Option<f32> {
+        let (uses, hits) = self
+            .abilities
+            .values()
+            .filter(|a| a.uses > 0)
+            .fold((0, 0), |(uses, hits), a| (uses + a.uses, hits + a.hits));
+        AbilityStats { uses, hits }.accuracy()
+    }
+}
+
+/// Stats of every player in a match
+#[derive(Debug, Clone, Default)]
+pub struct MatchStats {
+    players: BTreeMap<usize, PlayerStats>,
+}
+
+impl MatchStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget everything counted so far
+    pub fn clear(&mut self) {
+        self.players.clear();
+    }
+
+    /// Get a player's stats
+    pub fn player(&self, player_index: usize) -> Option<&PlayerStats> {
+        self.players.get(&player_index)
+    }
+
+    /// Get every player's stats, by player index
+    pub fn players(&self) -> impl Iterator<Item = (usize, &PlayerStats)> {
+        self.players.iter().map(|(&index, stats)| (index, stats))
+    }
+
+    /// Count a knockout (`killer` is the player credited with it, if any)
+    pub fn record_kill(&mut self, victim: usize, killer: Option<usize>) {
+        self.entry(victim).deaths += 1;
+        if let Some(killer) = killer {
+            self.entry(killer).kills += 1;
+        }
+    }
+
+    /// Count damage taken, and dealt if a player caused it
+    /// A hit from a named ability counts towards that ability's accuracy
+    pub fn record_damage(
+        &mut self,
+        victim: usize,
+        attacker: Option<usize>,
+        amount: i32,
+        ability: Option<&str>,
+    ) {
+        self.entry(victim).damage_taken += amount;
+        let Some(attacker) = attacker.filter(|&attacker| attacker != victim) else {
+            return;
+        };
+        let stats = self.entry(attacker);
+        stats.damage_dealt += amount;
+        if let Some(ability) = ability {
+            stats.abilities.entry(ability.to_string()).or_default().hits += 1;
+        }
+    }
+
+    /// Count an ability being used
+    pub fn record_ability_use(&mut self, player_index: usize, ability: &str) {
+        self.entry(player_index)
+            .abilities
+            .entry(ability.to_string())
+            .or_default()
+            .uses += 1;
+    }
+
+    /// Count jumps and time in the air
+    pub fn record_movement(&mut self, player_index: usize, jumps: u32, airborne_time: f32) {
+        let stats = self.entry(player_index);
+        stats.jumps += jumps;
+        stats.airborne_time += airborne_time;
+    }
+
+    fn entry(&mut self, player_index: usize) -> &mut PlayerStats {
+        self.players.entry(player_index).or_default()
+    }
+}
+
+/// One player's line in a match report
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayerReport {
+    pub player_index: usize,
+    pub name: String,
+    pub team: TeamId,
+    pub round_wins: u32,
+    pub accuracy: Option<f32>,
+    #[serde(flatten)]
+    pub stats: PlayerStats,
+}
+
+/// A knockout as written to a match report
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KillRecord {
+    pub round: u32,
+    /// Seconds into the round
+    pub time: f32,
+    pub victim: usize,
+    /// Player credited with the kill (None for self-destructs and teamkills)
+    pub killer: Option<usize>,
+    pub cause: String,
+    /// Knocked out by a teammate (who gets no credit)
+    pub teamkill: bool,
+}
+
+impl From<&KillEvent> for KillRecord {
+    fn from(kill: &KillEvent) -> Self {
+        Self {
+            round: kill.round,
+            time: kill.time,
+            victim: kill.victim,
+            killer: kill.credited_killer(),
+            cause: kill.cause.describe(),
+            teamkill: kill.teamkill,
+        }
+    }
+}
+
+/// Summary of a finished match, saved as JSON
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MatchReport {
+    /// When the match ended (seconds since the Unix epoch)
+    pub finished_at: u64,
+    pub mode: String,
+    pub teams: String,
+    pub rounds: u32,
+    pub winner: Option<TeamId>,
+    pub players: Vec<PlayerReport>,
+    pub kills: Vec<KillRecord>,
+}
+
+impl MatchReport {
+    /// Serialize the report as pretty-printed JSON
+    pub fn to_json_string(&self) -> Result<String, StatsError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write the report to a new file in a directory (created if missing)
+    /// Returns the path written
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, StatsError> {
+        std::fs::create_dir_all(dir)?;
+        let mut path = dir.join(format!("match-{}.json", self.finished_at));
+        let mut copy = 1;
+        while path.exists() {
+            copy += 1;
+            path = dir.join(format!("match-{}-{}.json", self.finished_at, copy));
+        }
+        std::fs::write(&path, self.to_json_string()?)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_accumulate() {
+        let mut stats = MatchStats::new();
+        stats.record_ability_use(0, "ability_1");
+        stats.record_ability_use(0, "ability_1");
+        stats.record_damage(1, Some(0), 15, Some("ability_1"));
+        stats.record_damage(1, Some(0), 10, Some("stomp"));
+        stats.record_damage(0, None, 5, None);
+        stats.record_kill(1, Some(0));
+        stats.record_movement(1, 2, 0.75);
+
+        let attacker = stats.player(0).unwrap();
+        assert_eq!(attacker.kills, 1);
+        assert_eq!(attacker.damage_dealt, 25);
+        assert_eq!(attacker.damage_taken, 5);
+        assert_eq!(attacker.ability_uses(), 2);
+        assert_eq!(attacker.accuracy(), Some(0.5));
+
+        let victim = stats.player(1).unwrap();
+        assert_eq!((victim.deaths, victim.damage_taken), (1, 25));
+        assert_eq!((victim.jumps, victim.airborne_time), (2, 0.75));
+        assert_eq!(victim.accuracy(), None);
+    }
+
+    #[test]
+    fn test_report_is_saved_as_json() {
+        let mut stats = MatchStats::new();
+        stats.record_kill(1, Some(0));
+        let report = MatchReport {
+            finished_at: 1_700_000_000,
+            mode: "2 stocks".to_string(),
+            teams: "Free for all".to_string(),
+            rounds: 1,
+            winner: Some(0),
+            players: stats
+                .players()
+                .map(|(index, stats)| PlayerReport {
+                    player_index: index,
+                    name: format!("Player {}", index + 1),
+                    team: index,
+                    round_wins: 0,
+                    accuracy: stats.accuracy(),
+                    stats: stats.clone(),
+                })
+                .collect(),
+            kills: Vec::new(),
+        };
+
+        let dir = std::env::temp_dir().join(format!("rusted-battle-stats-{}", std::process::id()));
+        let first = report.save(&dir).unwrap();
+        let second = report.save(&dir).unwrap();
+        assert_ne!(first, second, "Reports never overwrite each other");
+
+        let text = std::fs::read_to_string(&first).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(json["players"][0]["kills"], 1);
+        assert_eq!(json["players"][1]["deaths"], 1);
+        assert_eq!(json["winner"], 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
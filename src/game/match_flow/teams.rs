@@ -0,0 +1,256 @@
+// Team assignment and friendly fire
+//
+// In free-for-all every player is on a team of their own, numbered after
+// their player index, so the rest of the match code can always reason about
+// teams. Team ids double as physics team ids
+// (see `CollisionGroups::to_team_interaction_groups`).
+
+use glam::{Vec2, Vec4};
+use std::collections::HashMap;
+
+/// Team identifier
+pub type TeamId = usize;
+
+/// Team colors, applied to character sprite tint
+pub const TEAM_COLORS: [Vec4; 4] = [
+    Vec4::new(1.0, 0.45, 0.45, 1.0),
+    Vec4::new(0.45, 0.6, 1.0, 1.0),
+    Vec4::new(0.5, 1.0, 0.5, 1.0),
+    Vec4::new(1.0, 0.9, 0.4, 1.0),
+];
+
+/// How players are split into teams
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TeamMode {
+    /// Everyone for themselves
+    #[default]
+    FreeForAll,
+    /// Players are dealt into this many teams in join order (2 = 2v2)
+    Teams(usize),
+}
+
+impl TeamMode {
+    /// Get a short description for menus and logs
+    pub fn describe(&self) -> String {
+        match self {
+            Self::FreeForAll => "Free for all".to_string(),
+            Self::Teams(2) => "2v2".to_string(),
+            Self::Teams(count) => format!("{} teams", count),
+        }
+    }
+}
+
+/// How much of a hit lands on a teammate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FriendlyFire {
+    /// Multiplier for damage dealt to teammates
+    pub damage_scale: f32,
+    /// Multiplier for knockback dealt to teammates
+    pub knockback_scale: f32,
+}
+
+impl Default for FriendlyFire {
+    fn default() -> Self {
+        Self::OFF
+    }
+}
+
+impl FriendlyFire {
+    /// Teammates can't hurt or push each other
+    pub const OFF: Self = Self {
+        damage_scale: 0.0,
+        knockback_scale: 0.0,
+    };
+
+    /// Teammates take full damage and knockback
+    pub const ON: Self = Self {
+        damage_scale: 1.0,
+        knockback_scale: 1.0,
+    };
+
+    /// Teammates can be pushed around but not hurt
+    pub const KNOCKBACK_ONLY: Self = Self {
+        damage_scale: 0.0,
+        knockback_scale: 1.0,
+    };
+
+    /// Check if attacks should pass through teammates entirely
+    pub fn passes_through(&self) -> bool {
+        self.damage_scale <= 0.0 && self.knockback_scale <= 0.0
+    }
+
+    /// Get the next rule in the order OFF -> KNOCKBACK_ONLY -> ON
+    pub fn next(&self) -> Self {
+        if *self == Self::OFF {
+            Self::KNOCKBACK_ONLY
+        } else if *self == Self::KNOCKBACK_ONLY {
+            Self::ON
+        } else {
+            Self::OFF
+        }
+    }
+
+    /// Get a short description for menus and logs
+    pub fn describe(&self) -> String {
+        if self.passes_through() {
+            "off".to_string()
+        } else if self.damage_scale <= 0.0 {
+            "knockback only".to_string()
+        } else {
+            format!(
+                "{:.0}% damage, {:.0}% knockback",
+                self.damage_scale * 100.0,
+                self.knockback_scale * 100.0
+            )
+        }
+    }
+}
+
+/// A hit after friendly-fire rules were applied
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitResult {
+    pub damage: i32,
+    pub knockback: Vec2,
+}
+
+/// Which team each player is on for a match
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TeamAssignment {
+    mode: TeamMode,
+    teams: HashMap<usize, TeamId>,
+    pub friendly_fire: FriendlyFire,
+}
+
+impl TeamAssignment {
+    /// Deal players into teams in the given order
+    pub fn assign(mode: TeamMode, players: &[usize], friendly_fire: FriendlyFire) -> Self {
+        let teams = players
+            .iter()
+            .enumerate()
+            .map(|(slot, &player)| match mode {
+                TeamMode::FreeForAll => (player, player),
+                TeamMode::Teams(count) => (player, slot % count.max(1)),
+            })
+            .collect();
+        Self {
+            mode,
+            teams,
+            friendly_fire,
+        }
+    }
+
+    /// Get the team mode the players were assigned with
+    pub fn mode(&self) -> TeamMode {
+        self.mode
+    }
+
+    /// Get a player's team (players without an assignment are on their own)
+    pub fn team_of(&self, player: usize) -> TeamId {
+        self.teams.get(&player).copied().unwrap_or(player)
+    }
+
+    /// Check if two different players are on the same team
+    pub fn are_teammates(&self, a: usize, b: usize) -> bool {
+        a != b && self.team_of(a) == self.team_of(b)
+    }
+
+    /// Get the players on a team, in ascending player order
+    pub fn members(&self, team: TeamId) -> Vec<usize> {
+        let mut members: Vec<usize> = self
+            .teams
+            .iter()
+            .filter(|(_, &t)| t == team)
+            .map(|(&player, _)| player)
+            .collect();
+        members.sort_unstable();
+        members
+    }
+
+    /// Get the sprite tint for a player
+    pub fn color(&self, player: usize) -> Vec4 {
+        self.team_color(self.team_of(player))
+    }
+
+    /// Get the tint of a team
+    pub fn team_color(&self, team: TeamId) -> Vec4 {
+        TEAM_COLORS[team % TEAM_COLORS.len()]
+    }
+
+    /// Apply friendly-fire rules to a hit
+    /// Returns None when the hit should be ignored entirely
+    pub fn resolve_hit(
+        &self,
+        attacker: usize,
+        victim: usize,
+        damage: i32,
+        knockback: Vec2,
+    ) -> Option<HitResult> {
+        if !self.are_teammates(attacker, victim) {
+            return Some(HitResult { damage, knockback });
+        }
+        if self.friendly_fire.passes_through() {
+            return None;
+        }
+        Some(HitResult {
+            damage: (damage as f32 * self.friendly_fire.damage_scale).round() as i32,
+            knockback: knockback * self.friendly_fire.knockback_scale,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assignment() {
+        let ffa = TeamAssignment::assign(TeamMode::FreeForAll, &[0, 1, 2, 3], FriendlyFire::OFF);
+        assert!(!ffa.are_teammates(0, 1));
+        assert_eq!(ffa.team_of(2), 2);
+
+        let teams = TeamAssignment::assign(TeamMode::Teams(2), &[3, 0, 1, 2], FriendlyFire::OFF);
+        assert_eq!(teams.members(0), vec![1, 3]);
+        assert_eq!(teams.members(1), vec![0, 2]);
+        assert!(teams.are_teammates(1, 3));
+        assert!(!teams.are_teammates(1, 1));
+        assert_eq!(teams.color(3), teams.color(1));
+
+        // Team colors follow the team, not the player with the same index
+        assert_eq!(teams.team_color(0), teams.color(3));
+        assert_ne!(teams.team_color(0), teams.color(0));
+    }
+
+    #[test]
+    fn test_friendly_fire_rules() {
+        let mut teams =
+            TeamAssignment::assign(TeamMode::Teams(2), &[0, 1, 2, 3], FriendlyFire::OFF);
+        let knockback = Vec2::new(4.0, 2.0);
+
+        // Enemies always take the full hit
+        assert_eq!(
+            teams.resolve_hit(0, 1, 10, knockback),
+            Some(HitResult {
+                damage: 10,
+                knockback
+            })
+        );
+        assert_eq!(teams.resolve_hit(0, 2, 10, knockback), None);
+
+        teams.friendly_fire = FriendlyFire::KNOCKBACK_ONLY;
+        assert_eq!(
+            teams.resolve_hit(0, 2, 10, knockback),
+            Some(HitResult {
+                damage: 0,
+                knockback
+            })
+        );
+
+        teams.friendly_fire = FriendlyFire {
+            damage_scale: 0.5,
+            knockback_scale: 0.25,
+        };
+        let hit = teams.resolve_hit(0, 2, 10, knockback).unwrap();
+        assert_eq!(hit.damage, 5);
+        assert_eq!(hit.knockback, Vec2::new(1.0, 0.5));
+    }
+}
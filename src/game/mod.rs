@@ -1,6 +1,7 @@
-// Game-specific logic: abilities, characters, arena, UI
+// Game-specific logic: abilities, characters, arena, match flow, UI
 
 pub mod abilities;
 pub mod arena;
 pub mod characters;
+pub mod match_flow;
 pub mod ui;
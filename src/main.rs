@@ -12,13 +12,55 @@ mod core;
 mod engine;
 mod game;
 
-use engine::assets::AssetManager;
+use engine::assets::{AssetManager, AtlasRegion, TextureAtlas};
 use engine::game_loop::GameLoop;
-use engine::input::{Action, InputManager};
-use engine::physics::{body::presets, PhysicsWorld};
-use engine::renderer::{Renderer, Sprite, SpriteUV, TextureHandle};
-use game::characters::{CharacterManager, CharacterStats};
-use glam::Vec2;
+use engine::input::{
+    bindings_path, platform_backend, Action, GamepadBackend, GamepadHotplug, InputConfigManager,
+    InputManager,
+};
+use engine::physics::{body::presets, CollisionGroups, PhysicsWorld};
+use engine::renderer::{
+    Background, BackgroundLayer, Renderer, Sprite, SpriteUV, TextureHandle, Viewport,
+};
+use game::arena::{
+    ArenaBounds, ArenaEditor, ArenaFile, CaptureZone, CaptureZoneDef, DestructibleManager, Easing,
+    ForceZoneManager, HazardEffect, HazardKind, HazardManager, MovingPlatform, PathMode,
+    PickupKind, PickupManager, RoundHazard, RoundHazardConfig, RoundHazardDef, SpawnContext,
+    TileColliderMode, TileCollision, TileDef, TileSet, Tilemap, WaypointPath,
+};
+use game::characters::{
+    resolve_stomps, Activity, CharacterId, CharacterManager, CharacterStats, KillCause,
+    PlayerContactRules,
+};
+use game::match_flow::{
+    HillControl, KillEvent, MatchConfig, MatchController, MatchEvent, MatchPhase, ScoringMode,
+    TeamAssignment, TeamId, TeamMode,
+};
+use glam::{Vec2, Vec4};
+
+/// Spawn points used when the arena file doesn't define any
+const DEFAULT_SPAWN_POINTS: [(f32, f32); 5] = [
+    (0.0, 10.0),
+    (-7.0, 6.0),
+    (7.0, 7.0),
+    (-12.0, 1.0),
+    (12.0, 1.0),
+];
+
+/// Pickup spots used when the arena file doesn't define any
+const DEFAULT_PICKUP_SPOTS: [(f32, f32); 4] = [(-8.0, -3.5), (8.0, -3.5), (6.0, 5.0), (0.0, 3.0)];
+
+/// Capture zone spots used when the arena file doesn't define a zone
+const DEFAULT_HILL_POSITIONS: [[f32; 2]; 3] = [[0.0, -3.0], [-8.0, 1.0], [7.0, 6.5]];
+
+/// How far inside the blast zone the camera stays (world units)
+const CAMERA_MARGIN: f32 = 4.0;
+
+/// Hit points added or removed per key press in the arena editor
+const EDITOR_HEALTH_STEP: i32 = 25;
+
+/// Directory match reports are written to
+const MATCH_STATS_DIR: &str = "stats";
 
 /// Game world that holds all game state
 struct GameWorld {
@@ -28,15 +70,62 @@ struct GameWorld {
     input: InputManager,
     assets: AssetManager,
 
+    // Platform gamepad backend (gilrs, or none if it failed to start)
+    gamepads: Box<dyn GamepadBackend>,
+
     // Character system
     characters: CharacterManager,
 
+    // Lobby, countdown, rounds and scores
+    match_controller: MatchController,
+
     // Demo platform (static)
     #[allow(dead_code)]
     demo_platform_handle: engine::physics::RigidBodyHandle,
 
+    // Moving platforms (kinematic)
+    moving_platforms: Vec<MovingPlatform>,
+
+    // Destructible platforms and their debris
+    destructibles: DestructibleManager,
+
+    // Hazard volumes (spikes, lava, saws)
+    hazards: HazardManager,
+
+    // Updrafts, crosswinds and fans
+    force_zones: ForceZoneManager,
+
+    // Pickups spawned on a timer from the arena's spawn table
+    pickups: PickupManager,
+
+    // King-of-the-hill capture zone and who held it last frame
+    capture_zone: CaptureZone,
+    hill_control: HillControl,
+
+    // Tilemap arena walls (sprites are rebuilt only when the map changes)
+    tile_sprites: Vec<Sprite>,
+    tile_texture: Option<TextureHandle>,
+
+    // Blast zone (leaving it is a KO) and the area the camera may show
+    blast_zone: ArenaBounds,
+    camera_bounds: ArenaBounds,
+
+    // Optional hazard that closes in on long rounds (F6 in the lobby)
+    round_hazard_def: RoundHazardDef,
+    round_hazard: Option<RoundHazard>,
+
+    // Arena editor (F1) and the file it saves to
+    editor: ArenaEditor,
+    arena_path: std::path::PathBuf,
+
+    // Last known cursor position in screen pixels
+    cursor_position: Vec2,
+
     // Character sprite texture (for rendering)
     character_texture: Option<TextureHandle>,
+
+    // Whether players push and stomp each other (F4 in the lobby)
+    player_contact: PlayerContactRules,
 }
 
 impl GameWorld {
@@ -52,23 +141,190 @@ impl GameWorld {
         let platform_collider = presets::platform_collider(20.0, 1.0);
         physics.add_collider(platform_collider, platform_handle);
 
-        // Initialize character manager and spawn player 1
+        // Create a moving platform that ping-pongs above the arena
+        let path = WaypointPath::new(vec![Vec2::new(-8.0, 1.0), Vec2::new(8.0, 1.0)], 3.0)
+            .with_mode(PathMode::PingPong)
+            .with_easing(Easing::EaseInOut);
+        let moving_platforms = vec![MovingPlatform::new(&mut physics, path, 4.0, 0.5)];
+
+        // Create a one-way platform that can be jumped through and dropped through
+        // Its surface is a slow conveyor towards the middle of the arena
+        let one_way_handle = physics.add_rigid_body(presets::platform_body(6.0, 4.0));
+        let one_way_collider =
+            physics.add_collider(presets::platform_collider(5.0, 0.5), one_way_handle);
+        physics.set_one_way_platform(one_way_collider, true);
+        physics.set_conveyor_speed(one_way_collider, -2.0);
+
+        // Create stepped walls on both sides of the arena from a tilemap
+        let mut tileset = TileSet::new();
+        let wall = tileset.add(
+            TileDef::new("tile", TileCollision::Solid)
+                .with_tint(glam::Vec4::new(0.45, 0.45, 0.5, 1.0)),
+        );
+        let ledge = tileset.add(
+            TileDef::new("tile", TileCollision::OneWay)
+                .with_tint(glam::Vec4::new(0.6, 0.5, 0.3, 1.0)),
+        );
+        let tilemap = Tilemap::from_ascii(
+            "\
+##..........................##
+##..........................##
+##===....................===##
+###........................###
+#####....................#####
+#####....................#####",
+            1.0,
+            Vec2::new(-15.0, -5.5),
+            &[('#', wall), ('=', ledge)],
+        );
+        tilemap.build_colliders(&tileset, &mut physics, TileColliderMode::Boxes);
+
+        let mut tile_atlas = TextureAtlas::new(1, 1);
+        tile_atlas.add_region(AtlasRegion::new("tile", 0, 0, 1, 1, 1, 1));
+        let tile_sprites = tilemap.tile_sprites(&tileset, &tile_atlas);
+        let tile_texture = renderer
+            .create_color_texture([255, 255, 255, 255], "tiles")
+            .ok();
+
+        // Load the custom arena layer edited with the in-game editor
+        let arena_path = std::env::current_dir()?.join("assets/arenas/custom.toml");
+        let arena_file = if arena_path.exists() {
+            match ArenaFile::load(&arena_path) {
+                Ok(file) => {
+                    info!("Loaded arena '{}' from {}", file.name, arena_path.display());
+                    file
+                }
+                Err(e) => {
+                    log::warn!("Could not load {}: {}", arena_path.display(), e);
+                    ArenaFile::new("Custom")
+                }
+            }
+        } else {
+            ArenaFile::new("Custom")
+        };
+        let background = arena_background(&mut renderer, &arena_file)?;
+        renderer.set_background(background);
+        let editor = ArenaEditor::new(arena_file, &mut physics);
+
+        // Blast zones around the arena (from the arena file, or the default), with
+        // the camera kept a little inside them, and the arena's round hazard
+        let blast_zone = editor
+            .document()
+            .blast_zone
+            .map(ArenaBounds::from)
+            .unwrap_or(ArenaBounds::new(
+                Vec2::new(-22.0, -14.0),
+                Vec2::new(22.0, 18.0),
+            ));
+        let camera_bounds = ArenaBounds::new(
+            blast_zone.min + Vec2::splat(CAMERA_MARGIN),
+            blast_zone.max - Vec2::splat(CAMERA_MARGIN),
+        );
+        let round_hazard_def = editor.document().round_hazard.unwrap_or_default();
+        let round_hazard =
+            RoundHazardConfig::from_def(&round_hazard_def, &blast_zone).map(RoundHazard::new);
+
+        // Destructible platforms from the arena file, plus one that breaks from
+        // explosions and heavy impacts; debris is removed once it falls out of the arena
+        let mut destructibles = DestructibleManager::new().with_kill_y(blast_zone.min.y);
+        spawn_destructibles(&mut destructibles, &mut physics, editor.document());
+
+        // Hazards from the arena file, plus a saw hanging under the moving platform
+        let mut hazards = HazardManager::new();
+        hazards.sync_arena(&mut physics, &editor.document().hazards);
+        hazards.attach_hazard(
+            &mut physics,
+            moving_platforms[0].body_handle,
+            HazardKind::Saw,
+            HazardEffect::InstantKill,
+            Vec2::new(0.0, -0.6),
+            Vec2::new(0.7, 0.7),
+        );
+
+        // Force zones from the arena file, plus an updraft next to the right wall
+        let mut force_zones = ForceZoneManager::new();
+        force_zones.sync_arena(&mut physics, &editor.document().force_zones);
+        force_zones.spawn_zone(
+            &mut physics,
+            ArenaBounds::new(Vec2::new(10.0, -3.5), Vec2::new(12.0, 6.0)),
+            Vec2::new(0.0, 28.0),
+        );
+
+        // Pickups from the arena's spawn table, or the default table
+        let pickup_spots = if editor.document().pickup_spots.is_empty() {
+            DEFAULT_PICKUP_SPOTS
+                .iter()
+                .map(|&(x, y)| Vec2::new(x, y))
+                .collect()
+        } else {
+            editor
+                .document()
+                .pickup_spots
+                .iter()
+                .map(|spot| Vec2::from(spot.position))
+                .collect()
+        };
+        let pickup_table = editor.document().pickup_table.clone().unwrap_or_default();
+        let pickups = PickupManager::new(pickup_table, pickup_spots);
+
+        // Capture zone for king of the hill, from the arena file or the default spots
+        let capture_zone_def =
+            editor
+                .document()
+                .capture_zone
+                .clone()
+                .unwrap_or_else(|| CaptureZoneDef {
+                    size: [4.0, 3.0],
+                    positions: DEFAULT_HILL_POSITIONS.to_vec(),
+                    move_interval: 20.0,
+                });
+        let capture_zone = CaptureZone::new(&mut physics, &capture_zone_def);
+
+        // Initialize character manager and spawn player 1 at the safest spawn point
         let mut characters = CharacterManager::new();
+        let mut spawn_context = SpawnContext::new();
+        spawn_context
+            .hazards
+            .extend(hazards.hazards().iter().filter_map(|h| h.bounds(&physics)));
+        let spawn = spawn_context
+            .choose(&arena_spawn_points(editor.document()))
+            .unwrap_or(Vec2::new(0.0, 10.0));
         let player1_id = characters.spawn_character(
             "Player 1",
             Some(0), // Player index 0
             CharacterStats::standard(),
             &mut physics,
-            0.0,  // spawn x
-            10.0, // spawn y (above platform)
+            spawn.x,
+            spawn.y,
         );
         info!("Spawned Player 1 character with ID {}", player1_id);
 
         // Enable physics debug rendering
         renderer.physics_debug_renderer_mut().set_enabled(true);
 
-        // Initialize input manager (4 players)
-        let input = InputManager::new(4);
+        // Up to four players join in the lobby (jump) and start the match with Enter
+        // A single player may start a practice match
+        let match_controller = MatchController::new(MatchConfig::default().with_min_players(1));
+        info!(
+            "Lobby: press jump to join, Tab to change the mode ({}), F2 for teams, F3 for friendly fire, F4 for player contact, F6 for the round hazard, Enter to start the match",
+            match_controller.config.scoring.describe()
+        );
+
+        // Initialize input manager (4 players) with the user's saved bindings
+        // The defaults are written out on first run so they can be edited
+        let input = match bindings_path() {
+            Some(path) => {
+                let config = InputConfigManager::load_or_default(&path, 4);
+                if !path.exists() {
+                    match config.save(&path) {
+                        Ok(()) => info!("Wrote default bindings to {}", path.display()),
+                        Err(e) => log::warn!("{}", e),
+                    }
+                }
+                InputManager::with_config(config)
+            }
+            None => InputManager::new(4),
+        };
 
         // Initialize asset manager
         let asset_path = std::env::current_dir()?.join("assets");
@@ -130,10 +386,15 @@ impl GameWorld {
 
         info!("Game initialized with character system");
         info!("Controls:");
-        info!("  Player 1: WASD to move, W to jump");
+        info!("  Player 1: WASD to move, W to jump, S to drop through platforms");
         info!("  Left/Right/Middle mouse buttons for abilities (coming soon)");
+        info!("  Player 2: arrow keys, abilities on , . /");
+        info!("  Player 3: IJKL, abilities on U O H");
+        info!("  Player 4: numpad 8 4 5 6, abilities on numpad 7 9 0");
         info!("  F - Toggle debug rendering");
-        info!("  R - Respawn character");
+        info!("  R - Respawn character (lobby only)");
+        info!("  X - Test explosion at Player 1");
+        info!("  F1 - Toggle arena editor (1-7 tools, mouse to edit, +/- platform HP, Del to delete, F5 to save)");
         info!("  P - Pause/Resume game");
         info!("  ESC - Menu (not implemented yet)");
 
@@ -142,10 +403,29 @@ impl GameWorld {
             physics,
             game_loop: GameLoop::new(),
             input,
+            gamepads: platform_backend(),
             assets,
             characters,
+            match_controller,
             demo_platform_handle: platform_handle,
+            moving_platforms,
+            destructibles,
+            tile_sprites,
+            tile_texture,
+            hazards,
+            force_zones,
+            pickups,
+            capture_zone,
+            hill_control: HillControl::Empty,
+            blast_zone,
+            camera_bounds,
+            round_hazard_def,
+            round_hazard,
+            editor,
+            arena_path,
+            cursor_position: Vec2::ZERO,
             character_texture,
+            player_contact: PlayerContactRules::OFF,
         })
     }
 
@@ -154,6 +434,27 @@ impl GameWorld {
         // Begin frame and get number of updates to run
         let num_updates = self.game_loop.begin_frame();
 
+        // Pick up gamepads plugged in or out, and their buttons and sticks
+        for hotplug in self.input.poll_gamepads(self.gamepads.as_mut()) {
+            match hotplug {
+                GamepadHotplug::Connected {
+                    name,
+                    player: Some(player),
+                    ..
+                } => info!("{} connected as Player {}", name, player + 1),
+                GamepadHotplug::Connected {
+                    name, player: None, ..
+                } => {
+                    info!("{} connected, but every player already has a gamepad", name)
+                }
+                GamepadHotplug::Disconnected {
+                    player: Some(player),
+                    ..
+                } => info!("Player {}'s gamepad disconnected", player + 1),
+                GamepadHotplug::Disconnected { player: None, .. } => {}
+            }
+        }
+
         // Run fixed timestep updates
         for _ in 0..num_updates {
             self.update();
@@ -206,18 +507,139 @@ impl GameWorld {
         }
 
         // Process input-driven actions (only when not paused)
+        if !self.game_loop.is_paused() {
+            // Advance the match with real time, then slow the game down if it asks
+            let alive: Vec<usize> = self
+                .characters
+                .all()
+                .iter()
+                .filter(|c| c.is_alive())
+                .filter_map(|c| c.player_index)
+                .collect();
+            for event in self.match_controller.update(dt, &alive) {
+                self.handle_match_event(event);
+            }
+        }
+        let dt = dt * self.match_controller.time_scale();
+        self.physics.set_timestep(dt);
+
         if !self.game_loop.is_paused() {
             self.process_input();
 
+            // Move platforms before characters so riders see this step's motion
+            for platform in &mut self.moving_platforms {
+                platform.update(&mut self.physics, dt);
+            }
+
+            // Push bodies inside force zones before movement so it can add to it
+            self.force_zones
+                .update(&mut self.physics, &mut self.characters, dt);
+
             // Update all characters (movement, physics, animation)
             self.characters.update(&mut self.physics, dt);
+
+            // Advance the round hazard and apply the kill volumes
+            self.update_round_hazard(dt);
         }
 
+        // Pick up hazards and force zones changed in the editor
+        self.hazards
+            .sync_arena(&mut self.physics, &self.editor.document().hazards);
+        self.force_zones
+            .sync_arena(&mut self.physics, &self.editor.document().force_zones);
+        self.destructibles
+            .sync_arena(&mut self.physics, &self.editor.document().platforms);
+
         // Step physics simulation with fixed timestep
         self.physics.step();
 
+        // Apply hazard effects to characters touching them
+        for kill in self
+            .hazards
+            .update(&mut self.physics, &mut self.characters, dt)
+        {
+            self.record_knockout(kill.victim, kill.credited_to, KillCause::Hazard(kill.kind));
+        }
+
+        // Bounce players off the heads of those they land on
+        let players: Vec<(CharacterId, Option<usize>)> = self
+            .characters
+            .all()
+            .iter()
+            .map(|c| (c.id, c.player_index))
+            .collect();
+        let player_of = |id| players.iter().find(|(c, _)| *c == id).and_then(|(_, p)| *p);
+        let teams = self.match_controller.teams();
+        let stomps = resolve_stomps(
+            &mut self.characters,
+            &mut self.physics,
+            &self.player_contact,
+            |stomper, victim, damage| match (player_of(stomper), player_of(victim)) {
+                (Some(stomper), Some(victim)) => teams
+                    .resolve_hit(stomper, victim, damage, Vec2::ZERO)
+                    .map_or(0, |hit| hit.damage),
+                _ => damage,
+            },
+        );
+        for stomp in stomps {
+            let name = |id| self.characters.get(id).map_or("?", |c| c.name.as_str());
+            if stomp.hurt {
+                info!("{} stomped {}", name(stomp.stomper), name(stomp.victim));
+            }
+            if stomp.knocked_out {
+                let cause = self
+                    .characters
+                    .get(stomp.victim)
+                    .map_or(KillCause::Unknown, |c| c.health_knockout_cause());
+                self.record_knockout(stomp.victim, Some(stomp.stomper), cause);
+            }
+        }
+
+        // Spawn pickups and apply the ones touched this step
+        for pickup in self
+            .pickups
+            .update(&mut self.physics, &mut self.characters, dt)
+        {
+            let name = self
+                .characters
+                .get(pickup.character)
+                .map_or("?", |c| c.name.as_str());
+            match pickup.kind {
+                PickupKind::AbilitySwap => {
+                    info!("{} picked up an ability swap (no abilities yet)", name)
+                }
+                kind => info!("{} picked up {:?}", name, kind),
+            }
+        }
+
+        // Count jumps, air time and damage towards the match stats
+        self.collect_stats();
+
+        // Move the capture zone and score whoever holds it
+        if self.is_king_of_the_hill() && !self.game_loop.is_paused() {
+            if let Some(position) =
+                self.capture_zone
+                    .update(&mut self.physics, &self.characters, dt)
+            {
+                info!("The hill moved to ({:.1}, {:.1})", position.x, position.y);
+            }
+            let occupants = self.capture_zone.occupants().to_vec();
+            let control = self.match_controller.update_hill(&occupants, dt);
+            if control == HillControl::Contested && self.hill_control != control {
+                info!("The hill is contested");
+            }
+            self.hill_control = control;
+        }
+
+        // Clean up settled or fallen debris
+        self.destructibles.update(&mut self.physics, dt);
+
         // Check for collision events
-        for event in self.physics.get_collision_events() {
+        let events = self.physics.get_collision_events();
+        self.destructibles
+            .handle_collision_events(&mut self.physics, &events);
+
+        for event in events {
             match event {
                 engine::physics::CollisionEvent::Started {
                     collider1,
@@ -231,6 +653,245 @@ impl GameWorld {
                 } => {
                     info!("Collision stopped: {:?} <-> {:?}", collider1, collider2);
                 }
+                engine::physics::CollisionEvent::ContactForce { .. } => {}
+            }
+        }
+    }
+
+    /// Add what every player's character did this step to the match stats
+    /// Activity is drained every step, so nothing from the lobby carries over
+    fn collect_stats(&mut self) {
+        let activities: Vec<(usize, Activity)> = self
+            .characters
+            .all_mut()
+            .iter_mut()
+            .filter_map(|c| Some((c.player_index?, c.take_activity())))
+            .filter(|(_, activity)| !activity.is_empty())
+            .collect();
+        let player_of = |id| self.characters.get(id).and_then(|c| c.player_index);
+        let Some(stats) = self.match_controller.stats_mut() else {
+            return;
+        };
+        for (player_index, activity) in activities {
+            stats.record_movement(player_index, activity.jumps, activity.airborne_time);
+            for hit in activity.damage_taken {
+                stats.record_damage(
+                    player_index,
+                    hit.source.attacker.and_then(player_of),
+                    hit.amount,
+                    hit.source.ability,
+                );
+            }
+        }
+    }
+
+    /// Advance the round hazard, then KO characters outside the kill volumes
+    /// and keep the camera inside the (possibly shrinking) arena
+    /// The hazard only moves and kills while a round is being fought; outside
+    /// of rounds only the normal blast zone applies
+    fn update_round_hazard(&mut self, dt: f32) {
+        let mut camera_bounds = self.camera_bounds;
+        let in_round = self.match_controller.phase() == MatchPhase::InRound;
+
+        if let Some(hazard) = &mut self.round_hazard {
+            if in_round {
+                if let Some(announcement) = hazard.update(dt) {
+                    info!("{}", announcement.message(&hazard.config.kind));
+                }
+            }
+            camera_bounds = hazard.camera_bounds(&self.camera_bounds, &self.blast_zone, 2.0);
+        }
+
+        let in_lobby = self.match_controller.phase() == MatchPhase::Lobby;
+        let mut knockouts = Vec::new();
+        for character in self.characters.all_mut() {
+            if !character.is_alive() {
+                continue;
+            }
+            let Some((x, y)) = character.position(&self.physics) else {
+                continue;
+            };
+
+            let point = Vec2::new(x, y);
+            let killed = match &self.round_hazard {
+                Some(hazard) if in_round => hazard.is_killed(&self.blast_zone, point),
+                _ => !self.blast_zone.contains(point),
+            };
+            if killed {
+                if in_lobby && character.player_index == Some(0) {
+                    info!("Press R to respawn");
+                }
+                let cause = if self.blast_zone.contains(point) {
+                    KillCause::RoundHazard
+                } else {
+                    KillCause::BlastZone
+                };
+                character.die();
+                knockouts.push((character.id, character.last_attacker(), cause));
+            }
+        }
+        for (victim, killer, cause) in knockouts {
+            self.record_knockout(victim, killer, cause);
+        }
+
+        self.renderer.camera_mut().set_bounds(Some(Viewport {
+            min: camera_bounds.min,
+            max: camera_bounds.max,
+        }));
+    }
+
+    /// Check if the current mode is king of the hill
+    fn is_king_of_the_hill(&self) -> bool {
+        matches!(
+            self.match_controller.config.scoring,
+            ScoringMode::KingOfTheHill { .. }
+        )
+    }
+
+    /// Add a sprite for the capture zone, colored by who holds it
+    fn render_capture_zone(&mut self) {
+        if !self.is_king_of_the_hill() {
+            return;
+        }
+        let mut color = match self.hill_control {
+            HillControl::Empty => Vec4::new(1.0, 1.0, 1.0, 1.0),
+            HillControl::Held(team) => self.match_controller.teams().team_color(team),
+            HillControl::Contested => Vec4::new(1.0, 0.5, 0.1, 1.0),
+        };
+        color.w = 0.25;
+        let bounds = self.capture_zone.bounds();
+        let sprite = Sprite::new(bounds.center(), bounds.size())
+            .with_color(color)
+            .with_z_order(0.4);
+        self.renderer.add_sprite(sprite);
+    }
+
+    /// Count a knockout towards the match scores
+    /// The killer is whoever dealt the last hit, if it was recent enough
+    fn record_knockout(
+        &mut self,
+        victim: CharacterId,
+        killer: Option<CharacterId>,
+        cause: KillCause,
+    ) {
+        let name = |id| self.characters.get(id).map_or("?", |c| c.name.as_str());
+        let round = self.match_controller.round();
+        let time = self.match_controller.round_time();
+        match killer {
+            Some(killer) => info!(
+                "[round {} {:.1}s] {} was knocked out by {} ({})",
+                round,
+                time,
+                name(victim),
+                name(killer),
+                cause.describe()
+            ),
+            None => info!(
+                "[round {} {:.1}s] {} was knocked out ({})",
+                round,
+                time,
+                name(victim),
+                cause.describe()
+            ),
+        }
+
+        let player = |id| self.characters.get(id).and_then(|c| c.player_index);
+        let Some(victim) = player(victim) else {
+            return;
+        };
+        let kill = KillEvent::new(victim, killer.and_then(player), cause).with_time(round, time);
+        if let Some(event) = self.match_controller.record_kill(kill) {
+            self.handle_match_event(event);
+        }
+    }
+
+    /// Add sprites that show the hazard volumes
+    fn render_hazards(&mut self) {
+        for hazard in self.hazards.hazards() {
+            let Some(bounds) = hazard.bounds(&self.physics) else {
+                continue;
+            };
+            let color = match hazard.kind {
+                HazardKind::Spikes => glam::Vec4::new(0.7, 0.7, 0.75, 1.0),
+                HazardKind::Lava => glam::Vec4::new(1.0, 0.35, 0.05, 1.0),
+                HazardKind::Saw => glam::Vec4::new(0.85, 0.1, 0.1, 1.0),
+            };
+            let sprite = Sprite::new(bounds.center(), bounds.size())
+                .with_color(color)
+                .with_z_order(0.5);
+            self.renderer.add_sprite(sprite);
+        }
+    }
+
+    /// Add sprites for waiting pickups and the flashes of collected ones
+    fn render_pickups(&mut self) {
+        let color = |kind| match kind {
+            PickupKind::Health => glam::Vec4::new(0.2, 0.9, 0.3, 1.0),
+            PickupKind::Speed => glam::Vec4::new(1.0, 0.9, 0.2, 1.0),
+            PickupKind::ExtraJump => glam::Vec4::new(0.3, 0.7, 1.0, 1.0),
+            PickupKind::AbilitySwap => glam::Vec4::new(0.8, 0.4, 1.0, 1.0),
+        };
+        let size = Vec2::splat(game::arena::pickup::PICKUP_RADIUS * 2.0);
+
+        for pickup in self.pickups.pickups() {
+            let sprite = Sprite::new(pickup.position, size).with_color(color(pickup.kind));
+            self.renderer.add_sprite(sprite);
+        }
+
+        // Collected pickups burst outwards and fade
+        for flash in self.pickups.flashes() {
+            let t = flash.progress();
+            let mut tint = color(flash.kind);
+            tint.w = 1.0 - t;
+            let sprite = Sprite::new(flash.position, size * (1.0 + 2.0 * t)).with_color(tint);
+            self.renderer.add_sprite(sprite);
+        }
+    }
+
+    /// Add sprites that show the round hazard
+    fn render_round_hazard(&mut self) {
+        let Some(hazard) = &self.round_hazard else {
+            return;
+        };
+        let blast_zone = self.blast_zone;
+
+        // Rising water covers everything below the current level
+        if let Some(level) = hazard.water_level() {
+            let bottom = blast_zone.min.y;
+            if level > bottom {
+                let water = Sprite::new(
+                    Vec2::new(blast_zone.center().x, (level + bottom) / 2.0),
+                    Vec2::new(blast_zone.size().x, level - bottom),
+                )
+                .with_color(glam::Vec4::new(0.1, 0.35, 0.8, 0.55))
+                .with_z_order(2.0);
+                self.renderer.add_sprite(water);
+            }
+        }
+
+        // Closing blast zones darken the area that is no longer safe
+        let zone = hazard.blast_zone(&blast_zone);
+        if zone != blast_zone {
+            let color = glam::Vec4::new(0.6, 0.05, 0.05, 0.45);
+            let bands = [
+                // Left and right (full height)
+                ArenaBounds::new(blast_zone.min, Vec2::new(zone.min.x, blast_zone.max.y)),
+                ArenaBounds::new(Vec2::new(zone.max.x, blast_zone.min.y), blast_zone.max),
+                // Bottom and top (between the side bands)
+                ArenaBounds::new(
+                    Vec2::new(zone.min.x, blast_zone.min.y),
+                    Vec2::new(zone.max.x, zone.min.y),
+                ),
+                ArenaBounds::new(
+                    Vec2::new(zone.min.x, zone.max.y),
+                    Vec2::new(zone.max.x, blast_zone.max.y),
+                ),
+            ];
+            for band in bands {
+                let sprite = Sprite::new(band.center(), band.size())
+                    .with_color(color)
+                    .with_z_order(2.0);
+                self.renderer.add_sprite(sprite);
             }
         }
     }
@@ -243,39 +904,89 @@ impl GameWorld {
             // TODO: Open menu system when implemented
         }
 
-        // Process Player 1 input
-        if let Some(player_input) = self.input.player(0) {
+        // Players join from the lobby by jumping
+        if self.match_controller.phase() == MatchPhase::Lobby {
+            for player_index in self.input.get_players_who_pressed(Action::Jump) {
+                if let Some(event) = self.match_controller.join(player_index) {
+                    self.handle_match_event(event);
+                }
+            }
+        }
+
+        // Characters stand still during countdowns and the scoreboard
+        if self.match_controller.is_input_frozen() {
+            for character in self.characters.all_mut() {
+                character.input_horizontal = 0.0;
+                character.input_duck = false;
+                character.clear_input();
+            }
+            return;
+        }
+
+        // Process input for every player with a character
+        for player_index in 0..self.input.num_players() {
+            let Some(player_input) = self.input.player(player_index) else {
+                continue;
+            };
+
             // Get movement direction
             let (horizontal, _vertical) = player_input.get_direction();
 
             // Get character and apply input
-            if let Some(character) = self.characters.get_by_player_mut(0) {
+            if let Some(character) = self.characters.get_by_player_mut(player_index) {
                 // Set movement input
                 character.input_horizontal = horizontal;
 
                 // Set duck input
                 character.input_duck = player_input.is_held(Action::Duck);
 
+                // Pressing duck drops through one-way platforms
+                if player_input.just_pressed(Action::Duck) {
+                    character.input_drop_through = true;
+                }
+
                 // Jump (just pressed)
                 if player_input.just_pressed(Action::Jump) {
                     character.input_jump = true;
                 }
 
                 // Ability demos
+                // TODO: Count uses towards accuracy in the match stats once
+                // abilities actually fire (`MatchStats::record_ability_use`)
+                let player = player_index + 1;
                 if player_input.just_pressed(Action::Ability1) {
-                    info!("P1 used Ability 1! (State: {:?})", character.state());
+                    info!(
+                        "P{} used Ability 1! (State: {:?})",
+                        player,
+                        character.state()
+                    );
                 }
                 if player_input.just_pressed(Action::Ability2) {
-                    info!("P1 used Ability 2!");
+                    info!("P{} used Ability 2!", player);
                 }
                 if player_input.just_pressed(Action::Ability3) {
-                    info!("P1 used Ability 3!");
+                    info!("P{} used Ability 3!", player);
                 }
             }
         }
     }
 
     fn render(&mut self) -> Result<()> {
+        // Add the tilemap arena in a single batch
+        if let Some(texture) = self.tile_texture {
+            self.renderer.add_tile_batch(texture, &self.tile_sprites);
+        }
+
+        // Draw force zones, hazards and pickups behind the characters
+        for zone in self.force_zones.zones() {
+            let sprite = Sprite::new(zone.bounds.center(), zone.bounds.size())
+                .with_color(glam::Vec4::new(0.6, 0.85, 1.0, 0.15));
+            self.renderer.add_sprite(sprite);
+        }
+        self.render_hazards();
+        self.render_pickups();
+        self.render_capture_zone();
+
         // Add character sprites to the renderer
         for character in self.characters.all() {
             if let Some((x, y)) = character.position(&self.physics) {
@@ -326,10 +1037,33 @@ impl GameWorld {
                 sprite.uv = uv;
                 sprite.z_order = 1.0; // Above background
 
+                // Tint by team when playing in teams
+                let teams = self.match_controller.teams();
+                if let (TeamMode::Teams(_), Some(index)) = (teams.mode(), character.player_index) {
+                    sprite.color = teams.color(index);
+                }
+
+                // Flash while spawn protection is active
+                if character.is_spawn_protected() {
+                    let pulse = (self.game_loop.elapsed_secs() * 12.0).sin() * 0.5 + 0.5;
+                    sprite.color.w = 0.35 + 0.5 * pulse;
+                }
+
                 self.renderer.add_sprite(sprite);
             }
         }
 
+        // Draw the round hazard over the characters
+        self.render_round_hazard();
+
+        // Highlight the item selected in the editor
+        if let Some((center, size)) = self.editor.selection_rect() {
+            let highlight = Sprite::new(center, size + Vec2::splat(0.2))
+                .with_color(glam::Vec4::new(1.0, 0.9, 0.2, 0.35))
+                .with_z_order(3.0);
+            self.renderer.add_sprite(highlight);
+        }
+
         // Prepare physics debug rendering
         let debug_data = self.physics.debug_data();
         let device = self.renderer.device() as *const wgpu::Device;
@@ -394,16 +1128,520 @@ impl GameWorld {
         button: winit::event::MouseButton,
         state: winit::event::ElementState,
     ) {
+        // The editor takes over the mouse while it is enabled
+        if self.editor.is_enabled() {
+            let world_pos = self.cursor_world_position();
+            let pressed = state == winit::event::ElementState::Pressed;
+            match button {
+                winit::event::MouseButton::Left if pressed => {
+                    self.editor.mouse_pressed(&mut self.physics, world_pos)
+                }
+                winit::event::MouseButton::Left => self.editor.mouse_released(),
+                winit::event::MouseButton::Right if pressed => {
+                    self.editor.delete_at(&mut self.physics, world_pos)
+                }
+                _ => {}
+            }
+            return;
+        }
+
         // Process mouse input through the input manager
         self.input.process_mouse_button_event(button, state);
     }
 
+    fn handle_cursor_moved(&mut self, position: winit::dpi::PhysicalPosition<f64>) {
+        self.cursor_position = Vec2::new(position.x as f32, position.y as f32);
+        if self.editor.is_enabled() {
+            let world_pos = self.cursor_world_position();
+            self.editor.mouse_moved(&mut self.physics, world_pos);
+        }
+    }
+
+    /// Get the cursor position in world coordinates
+    fn cursor_world_position(&self) -> Vec2 {
+        self.renderer.camera().screen_to_world(self.cursor_position)
+    }
+
+    fn handle_editor_key(&mut self, key_code: KeyCode) {
+        if key_code == KeyCode::F1 {
+            self.editor.toggle();
+            return;
+        }
+        if !self.editor.is_enabled() {
+            return;
+        }
+
+        match key_code {
+            KeyCode::Digit1 => self.editor.select_tool(1),
+            KeyCode::Digit2 => self.editor.select_tool(2),
+            KeyCode::Digit3 => self.editor.select_tool(3),
+            KeyCode::Digit4 => self.editor.select_tool(4),
+            KeyCode::Digit5 => self.editor.select_tool(5),
+            KeyCode::Digit6 => self.editor.select_tool(6),
+            KeyCode::Digit7 => self.editor.select_tool(7),
+            KeyCode::Equal => self
+                .editor
+                .adjust_selected_health(&mut self.physics, EDITOR_HEALTH_STEP),
+            KeyCode::Minus => self
+                .editor
+                .adjust_selected_health(&mut self.physics, -EDITOR_HEALTH_STEP),
+            KeyCode::Delete | KeyCode::Backspace => self.editor.delete_selected(&mut self.physics),
+            KeyCode::F5 => {
+                if let Err(e) = self.editor.save(&self.arena_path) {
+                    log::error!("Failed to save arena: {}", e);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Enter starts the match from the lobby, and returns to it once the match is over
+    fn advance_match(&mut self) {
+        match self.match_controller.phase() {
+            MatchPhase::Lobby => {
+                if !self.match_controller.can_start() {
+                    info!("Not enough players have joined yet");
+                    return;
+                }
+                for event in self.match_controller.start() {
+                    self.handle_match_event(event);
+                }
+            }
+            MatchPhase::MatchOver { .. } => {
+                self.match_controller.return_to_lobby();
+                if let Some(hazard) = &mut self.round_hazard {
+                    hazard.reset();
+                }
+                info!("Back in the lobby: press Enter to start another match");
+            }
+            _ => {}
+        }
+    }
+
+    /// Tab switches to the next scoring mode while in the lobby
+    fn cycle_scoring_mode(&mut self) {
+        if self.match_controller.phase() != MatchPhase::Lobby {
+            return;
+        }
+        let config = &mut self.match_controller.config;
+        config.scoring = config.scoring.next_preset();
+        info!("Mode: {}", config.scoring.describe());
+    }
+
+    /// F2 toggles between free-for-all and 2v2 while in the lobby
+    fn cycle_team_mode(&mut self) {
+        if self.match_controller.phase() != MatchPhase::Lobby {
+            return;
+        }
+        let config = &mut self.match_controller.config;
+        config.teams = match config.teams {
+            TeamMode::FreeForAll => TeamMode::Teams(2),
+            TeamMode::Teams(_) => TeamMode::FreeForAll,
+        };
+        info!("Teams: {}", config.teams.describe());
+    }
+
+    /// F3 switches to the next friendly-fire rule while in the lobby
+    fn cycle_friendly_fire(&mut self) {
+        if self.match_controller.phase() != MatchPhase::Lobby {
+            return;
+        }
+        let config = &mut self.match_controller.config;
+        config.friendly_fire = config.friendly_fire.next();
+        info!("Friendly fire: {}", config.friendly_fire.describe());
+    }
+
+    /// F4 toggles players pushing and stomping each other while in the lobby
+    fn toggle_player_contact(&mut self) {
+        if self.match_controller.phase() != MatchPhase::Lobby {
+            return;
+        }
+        self.player_contact = if self.player_contact.enabled {
+            PlayerContactRules::OFF
+        } else {
+            PlayerContactRules::SHOVE_AND_STOMP
+        };
+        info!("Player contact: {}", self.player_contact.describe());
+        self.apply_player_colliders();
+    }
+
+    /// F6 switches to the next round hazard while in the lobby
+    /// The arena file's settings (start time, levels, bounds) are kept
+    fn cycle_round_hazard(&mut self) {
+        if self.match_controller.phase() != MatchPhase::Lobby {
+            return;
+        }
+        self.round_hazard_def.kind = self.round_hazard_def.kind.next();
+        self.round_hazard = RoundHazardConfig::from_def(&self.round_hazard_def, &self.blast_zone)
+            .map(RoundHazard::new);
+        match &self.round_hazard {
+            Some(hazard) => info!(
+                "Round hazard: {} after {:.0} seconds",
+                self.round_hazard_def.kind.describe(),
+                hazard.config.start_time
+            ),
+            None => info!("Round hazard: off"),
+        }
+    }
+
+    /// Put each participant's collider on their team, and make players solid
+    /// to each other if contact rules are on
+    /// Teammates' attacks only pass through each other when friendly fire is off
+    fn apply_player_colliders(&mut self) {
+        let teams = self.match_controller.teams();
+        let team_physics =
+            matches!(teams.mode(), TeamMode::Teams(_)) && teams.friendly_fire.passes_through();
+        for participant in self.match_controller.participants() {
+            let index = participant.player_index;
+            if let Some(character) = self.characters.get_by_player(index) {
+                self.physics.set_collider_team(
+                    character.collider_handle,
+                    CollisionGroups::Player,
+                    team_physics.then(|| teams.team_of(index)),
+                );
+                self.physics
+                    .set_player_collision(character.collider_handle, self.player_contact.enabled);
+            }
+        }
+    }
+
+    /// React to what the match controller reports
+    fn handle_match_event(&mut self, event: MatchEvent) {
+        let name = |characters: &CharacterManager, player_index: usize| {
+            characters
+                .get_by_player(player_index)
+                .map_or(format!("Player {}", player_index + 1), |c| c.name.clone())
+        };
+        // Winners are teams; in free-for-all the team is the player
+        let team_name = |characters: &CharacterManager, teams: &TeamAssignment, team: TeamId| {
+            match teams.mode() {
+                TeamMode::FreeForAll => name(characters, team),
+                TeamMode::Teams(_) => format!("Team {}", team + 1),
+            }
+        };
+
+        match event {
+            MatchEvent::PlayerJoined(player_index) => {
+                if !self.characters.is_player_taken(player_index) {
+                    let spawn = self.choose_spawn_point(player_index);
+                    self.characters.spawn_character(
+                        &format!("Player {}", player_index + 1),
+                        Some(player_index),
+                        CharacterStats::standard(),
+                        &mut self.physics,
+                        spawn.x,
+                        spawn.y,
+                    );
+                }
+                info!("{} joined", name(&self.characters, player_index));
+                self.apply_player_colliders();
+            }
+            MatchEvent::PlayerLeft(player_index) => {
+                info!("{} left", name(&self.characters, player_index));
+            }
+            MatchEvent::ResetRound { round } => {
+                self.reset_round();
+                self.apply_player_colliders();
+                info!("Round {}", round);
+            }
+            MatchEvent::Countdown { seconds } => info!("{}...", seconds),
+            MatchEvent::RoundStarted { .. } => info!("Fight!"),
+            MatchEvent::Respawn { player_index } => {
+                let spawn = self.choose_spawn_point(player_index);
+                if let Some(character) = self.characters.get_by_player_mut(player_index) {
+                    character.respawn(&mut self.physics, spawn.x, spawn.y);
+                }
+            }
+            MatchEvent::Eliminated { player_index } => {
+                info!("{} is out", name(&self.characters, player_index));
+            }
+            MatchEvent::RoundOver { round, winner } => {
+                match winner {
+                    Some(team) => info!(
+                        "{} wins round {}",
+                        team_name(&self.characters, self.match_controller.teams(), team),
+                        round
+                    ),
+                    None => info!("Round {} is a draw", round),
+                }
+                for participant in self.match_controller.participants() {
+                    let score = participant.score;
+                    info!(
+                        "  {}: {} round wins, {} kills, {} deaths, {:.0}s on the hill",
+                        name(&self.characters, participant.player_index),
+                        score.round_wins,
+                        score.kills,
+                        score.deaths,
+                        score.hill_time
+                    );
+                }
+            }
+            MatchEvent::SuddenDeath { players } => {
+                let names: Vec<String> = players
+                    .iter()
+                    .map(|&index| name(&self.characters, index))
+                    .collect();
+                info!("Sudden death: {}", names.join(" vs "));
+            }
+            MatchEvent::MatchOver { winner } => {
+                match winner {
+                    Some(team) => info!(
+                        "{} wins the match! Press Enter to return to the lobby",
+                        team_name(&self.characters, self.match_controller.teams(), team)
+                    ),
+                    None => info!("The match ended without a winner"),
+                }
+                self.show_results();
+            }
+        }
+    }
+
+    /// Show the end-of-match stats and save them to a match report
+    fn show_results(&self) {
+        let report = self.match_controller.report(|player_index| {
+            self.characters
+                .get_by_player(player_index)
+                .map_or(format!("Player {}", player_index + 1), |c| c.name.clone())
+        });
+
+        info!("Results:");
+        for player in &report.players {
+            let stats = &player.stats;
+            let accuracy = player
+                .accuracy
+                .map_or("-".to_string(), |a| format!("{:.0}%", a * 100.0));
+            info!(
+                "  {}: {} kills, {} deaths, {} damage dealt, {} taken, {} ability uses ({} accuracy), {} jumps, {:.1}s airborne",
+                player.name,
+                stats.kills,
+                stats.deaths,
+                stats.damage_dealt,
+                stats.damage_taken,
+                stats.ability_uses(),
+                accuracy,
+                stats.jumps,
+                stats.airborne_time
+            );
+        }
+
+        match report.save(std::path::Path::new(MATCH_STATS_DIR)) {
+            Ok(path) => info!("Match report saved to {}", path.display()),
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+
+    /// Put the arena and every participant back to the start of a round
+    fn reset_round(&mut self) {
+        let cleared = self
+            .physics
+            .remove_bodies_in_group(CollisionGroups::Projectile);
+        if cleared > 0 {
+            info!("Cleared {} projectiles", cleared);
+        }
+
+        // Rebuild the parts of the arena a round can change
+        self.destructibles.clear(&mut self.physics);
+        spawn_destructibles(
+            &mut self.destructibles,
+            &mut self.physics,
+            self.editor.document(),
+        );
+        for platform in &mut self.moving_platforms {
+            platform.reset(&mut self.physics);
+        }
+        self.pickups.clear(&mut self.physics);
+        self.capture_zone.reset(&mut self.physics);
+        self.hill_control = HillControl::Empty;
+        if let Some(hazard) = &mut self.round_hazard {
+            hazard.reset();
+        }
+
+        // Respawn one at a time so each player starts away from those already placed
+        // Players sitting out a sudden death stay knocked out
+        for participant in self.match_controller.participants() {
+            if let Some(character) = self.characters.get_by_player_mut(participant.player_index) {
+                character.die();
+            }
+        }
+        for player_index in self.match_controller.active_players() {
+            let spawn = self.choose_spawn_point(player_index);
+            if let Some(character) = self.characters.get_by_player_mut(player_index) {
+                character.respawn(&mut self.physics, spawn.x, spawn.y);
+            }
+        }
+    }
+
+    fn test_explosion(&mut self) {
+        let Some((x, y)) = self
+            .characters
+            .get_by_player(0)
+            .and_then(|c| c.position(&self.physics))
+        else {
+            return;
+        };
+        info!("Test explosion at ({:.1}, {:.1})", x, y);
+        self.destructibles
+            .apply_explosion(&mut self.physics, Vec2::new(x, y), 4.0, 60);
+    }
+
+    /// R respawns Player 1 while in the lobby
+    /// During a match the controller decides who comes back
     fn respawn_character(&mut self) {
-        info!("Respawning Player 1 character");
+        if self.match_controller.phase() != MatchPhase::Lobby {
+            return;
+        }
+        let spawn = self.choose_spawn_point(0);
+        info!(
+            "Respawning Player 1 character at ({:.1}, {:.1})",
+            spawn.x, spawn.y
+        );
         if let Some(character) = self.characters.get_by_player_mut(0) {
-            character.respawn(&mut self.physics, 0.0, 10.0);
+            character.respawn(&mut self.physics, spawn.x, spawn.y);
         }
     }
+
+    /// Pick the safest spawn point for a player
+    /// Keeps away from living enemies, hazard volumes, the water and projectiles
+    /// Teammates don't count as enemies, so teams can spawn together
+    fn choose_spawn_point(&self, player_index: usize) -> Vec2 {
+        let mut context = SpawnContext::new();
+        let teams = self.match_controller.teams();
+
+        for character in self.characters.all() {
+            if character.player_index == Some(player_index) || !character.is_alive() {
+                continue;
+            }
+            if character
+                .player_index
+                .is_some_and(|other| teams.are_teammates(player_index, other))
+            {
+                continue;
+            }
+            if let Some((x, y)) = character.position(&self.physics) {
+                context.enemies.push(Vec2::new(x, y));
+            }
+        }
+
+        context.hazards.extend(
+            self.hazards
+                .hazards()
+                .iter()
+                .filter_map(|h| h.bounds(&self.physics)),
+        );
+        if let Some(level) = self.round_hazard.as_ref().and_then(|h| h.water_level()) {
+            let water = ArenaBounds::new(
+                self.blast_zone.min,
+                Vec2::new(self.blast_zone.max.x, level.max(self.blast_zone.min.y)),
+            );
+            context.hazards.push(water);
+        }
+        if let Some(hazard) = &self.round_hazard {
+            let zone = hazard.blast_zone(&self.blast_zone);
+            if zone != self.blast_zone {
+                // Points outside the shrinking blast zone are as bad as hazards
+                let outside = [
+                    ArenaBounds::new(
+                        self.blast_zone.min,
+                        Vec2::new(zone.min.x, self.blast_zone.max.y),
+                    ),
+                    ArenaBounds::new(
+                        Vec2::new(zone.max.x, self.blast_zone.min.y),
+                        self.blast_zone.max,
+                    ),
+                    ArenaBounds::new(
+                        self.blast_zone.min,
+                        Vec2::new(self.blast_zone.max.x, zone.min.y),
+                    ),
+                    ArenaBounds::new(
+                        Vec2::new(self.blast_zone.min.x, zone.max.y),
+                        self.blast_zone.max,
+                    ),
+                ];
+                context.hazards.extend(outside);
+            }
+        }
+
+        context.projectiles.extend(
+            self.physics
+                .collider_positions_in_group(CollisionGroups::Projectile)
+                .into_iter()
+                .map(|p| Vec2::new(p.x, p.y)),
+        );
+
+        context
+            .choose(&arena_spawn_points(self.editor.document()))
+            .unwrap_or(Vec2::new(0.0, 10.0))
+    }
+}
+
+/// Add the arena's destructible platforms (also used to rebuild them between rounds)
+fn spawn_destructibles(
+    destructibles: &mut DestructibleManager,
+    physics: &mut PhysicsWorld,
+    arena: &ArenaFile,
+) {
+    destructibles.spawn_platform(physics, -6.0, 4.0, 4.0, 0.5, 100);
+    destructibles.sync_arena(physics, &arena.platforms);
+}
+
+/// Spawn points of an arena, falling back to the built-in ones
+fn arena_spawn_points(arena: &ArenaFile) -> Vec<Vec2> {
+    if arena.spawns.is_empty() {
+        DEFAULT_SPAWN_POINTS
+            .iter()
+            .map(|&(x, y)| Vec2::new(x, y))
+            .collect()
+    } else {
+        arena
+            .spawns
+            .iter()
+            .map(|spawn| Vec2::from(spawn.position))
+            .collect()
+    }
+}
+
+/// Build an arena's parallax background, falling back to built-in hills
+fn arena_background(renderer: &mut Renderer, arena: &ArenaFile) -> Result<Background> {
+    let white = renderer.create_color_texture([255, 255, 255, 255], "background")?;
+    let mut background = Background::default();
+    if let Some(color) = arena.background_color {
+        background.clear_color = Vec4::from(color);
+    }
+
+    if arena.background_layers.is_empty() {
+        // Distant and near hill bands, both tinted flat colors
+        return Ok(background
+            .with_layer(
+                BackgroundLayer::new(white, 0.15, Vec2::new(40.0, 10.0))
+                    .with_offset(Vec2::new(0.0, -6.0))
+                    .with_tint(Vec4::new(0.16, 0.17, 0.26, 1.0)),
+            )
+            .with_layer(
+                BackgroundLayer::new(white, 0.4, Vec2::new(30.0, 8.0))
+                    .with_offset(Vec2::new(0.0, -9.0))
+                    .with_tint(Vec4::new(0.2, 0.24, 0.3, 1.0)),
+            ));
+    }
+
+    let assets = std::env::current_dir()?.join("assets");
+    for def in &arena.background_layers {
+        let texture = match &def.image {
+            Some(image) => renderer
+                .load_texture(&assets.join(image))
+                .unwrap_or_else(|e| {
+                    log::warn!("Could not load background image {}: {}", image, e);
+                    white
+                }),
+            None => white,
+        };
+        background = background.with_layer(
+            BackgroundLayer::new(texture, def.parallax, Vec2::from(def.size))
+                .with_offset(Vec2::from(def.offset))
+                .with_repeat(def.repeat_x, def.repeat_y)
+                .with_tint(Vec4::from(def.tint)),
+        );
+    }
+    Ok(background)
 }
 
 fn main() -> Result<()> {
@@ -462,7 +1700,7 @@ fn main() -> Result<()> {
                     // Let input manager handle all keyboard input
                     game_world.handle_keyboard_input(key_event);
 
-                    // Keep legacy debug keys for now (F and R)
+                    // Keep legacy debug keys for now (F, R and X), plus Enter for the match
                     if let KeyEvent {
                         physical_key: PhysicalKey::Code(key_code),
                         state: winit::event::ElementState::Pressed,
@@ -476,10 +1714,41 @@ fn main() -> Result<()> {
                             KeyCode::KeyR => {
                                 game_world.respawn_character();
                             }
-                            _ => {}
+                            KeyCode::KeyX => {
+                                game_world.test_explosion();
+                            }
+                            KeyCode::Enter if !key_event.repeat => {
+                                game_world.advance_match();
+                            }
+                            KeyCode::Tab if !key_event.repeat => {
+                                game_world.cycle_scoring_mode();
+                            }
+                            KeyCode::F2 if !key_event.repeat => {
+                                game_world.cycle_team_mode();
+                            }
+                            KeyCode::F3 if !key_event.repeat => {
+                                game_world.cycle_friendly_fire();
+                            }
+                            KeyCode::F4 if !key_event.repeat => {
+                                game_world.toggle_player_contact();
+                            }
+                            KeyCode::F6 if !key_event.repeat => {
+                                game_world.cycle_round_hazard();
+                            }
+                            _ => {
+                                if !key_event.repeat {
+                                    game_world.handle_editor_key(*key_code);
+                                }
+                            }
                         }
                     }
                 }
+                Event::WindowEvent {
+                    event: WindowEvent::CursorMoved { position, .. },
+                    ..
+                } => {
+                    game_world.handle_cursor_moved(position);
+                }
                 Event::WindowEvent {
                     event: WindowEvent::MouseInput { state, button, .. },
                     ..